@@ -4,7 +4,9 @@ use actix_web::web::Json;
 use lemmy_api_common::{
   comment::{CommentReportResponse, CreateCommentReport},
   context::LemmyContext,
+  push::send_report_push_notifications,
   send_activity::{ActivityChannel, SendActivityData},
+  send_webhook::send_webhook_event,
   utils::{
     check_comment_deleted_or_removed,
     check_community_user_action,
@@ -17,6 +19,7 @@ use lemmy_db_schema::{
     local_site::LocalSite,
   },
   traits::Reportable,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::{CommentReportView, CommentView, LocalUserView};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
@@ -79,6 +82,23 @@ pub async fn create_comment_report(
     .await?;
   }
 
+  // Notify the moderators
+  send_report_push_notifications(
+    comment_view.community.id,
+    &local_user_view.person.name,
+    &report.reason,
+    &context,
+  )
+  .await;
+
+  send_webhook_event(
+    WebhookEventType::ReportCreated,
+    Some(comment_view.community.id),
+    &comment_report_view,
+    &context,
+  )
+  .await;
+
   ActivityChannel::submit_activity(
     SendActivityData::CreateReport {
       object_id: comment_view.comment.ap_id.inner().clone(),
@@ -2,13 +2,21 @@ use actix_web::web::{Data, Json};
 use lemmy_api_common::{
   comment::{CommentReportResponse, ResolveCommentReport},
   context::LemmyContext,
-  utils::check_community_mod_action,
+  utils::{check_community_mod_action, report_outcome, send_report_resolved_notification},
+};
+use lemmy_db_schema::{
+  source::{
+    comment_report::CommentReport,
+    mod_action_notification::ModActionNotificationInsertForm,
+  },
+  traits::Reportable,
+  ModActionNotificationType,
 };
-use lemmy_db_schema::{source::comment_report::CommentReport, traits::Reportable};
 use lemmy_db_views::structs::{CommentReportView, LocalUserView};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
-/// Resolves or unresolves a comment report and notifies the moderators of the community
+/// Resolves or unresolves a comment report and notifies the moderators of the community, as well
+/// as the reporter
 #[tracing::instrument(skip(context))]
 pub async fn resolve_comment_report(
   data: Json<ResolveCommentReport>,
@@ -34,6 +42,21 @@ pub async fn resolve_comment_report(
     CommentReport::resolve(&mut context.pool(), report_id, person_id)
       .await
       .with_lemmy_type(LemmyErrorType::CouldntResolveReport)?;
+
+    if !report.comment_report.resolved {
+      let creator_banned = report.comment_creator.banned || report.creator_banned_from_community;
+      let notification_form = ModActionNotificationInsertForm {
+        post_id: Some(report.post.id),
+        comment_id: Some(report.comment.id),
+        report_outcome: Some(report_outcome(report.comment.removed, creator_banned)),
+        ..ModActionNotificationInsertForm::new(
+          report.creator.id,
+          ModActionNotificationType::ReportResolved,
+          Some(report.community.id),
+        )
+      };
+      send_report_resolved_notification(&notification_form, person_id, &mut context.pool()).await?;
+    }
   } else {
     CommentReport::unresolve(&mut context.pool(), report_id, person_id)
       .await
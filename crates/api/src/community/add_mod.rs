@@ -4,6 +4,7 @@ use lemmy_api_common::{
   community::{AddModToCommunity, AddModToCommunityResponse},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
+  send_webhook::{send_webhook_event, WebhookModAction},
   utils::check_community_mod_action,
 };
 use lemmy_db_schema::{
@@ -11,8 +12,10 @@ use lemmy_db_schema::{
     community::{Community, CommunityModerator, CommunityModeratorForm},
     local_user::LocalUser,
     moderator::{ModAddCommunity, ModAddCommunityForm},
+    person::Person,
   },
   traits::{Crud, Joinable},
+  WebhookEventType,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_db_views_actor::structs::CommunityModeratorView;
@@ -88,7 +91,16 @@ pub async fn add_mod_to_community(
     removed: Some(!data.added),
   };
 
-  ModAddCommunity::create(&mut context.pool(), &form).await?;
+  let entry = ModAddCommunity::create(&mut context.pool(), &form).await?;
+  if let Some(person) = Person::read(&mut context.pool(), data.person_id).await? {
+    send_webhook_event(
+      WebhookEventType::ModAction,
+      Some(data.community_id),
+      &WebhookModAction::AddModerator { entry, person },
+      &context,
+    )
+    .await;
+  }
 
   // Note: in case a remote mod is added, this returns the old moderators list, it will only get
   //       updated once we receive an activity from the community (like `Announce/Add/Moderator`)
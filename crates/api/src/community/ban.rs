@@ -4,7 +4,13 @@ use lemmy_api_common::{
   community::{BanFromCommunity, BanFromCommunityResponse},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{check_community_mod_action, check_expire_time, remove_user_data_in_community},
+  send_webhook::{send_webhook_event, WebhookModAction},
+  utils::{
+    check_community_mod_action,
+    check_expire_time,
+    remove_user_data_in_community,
+    send_mod_action_notification,
+  },
 };
 use lemmy_db_schema::{
   source::{
@@ -15,9 +21,12 @@ use lemmy_db_schema::{
       CommunityPersonBanForm,
     },
     local_user::LocalUser,
+    mod_action_notification::ModActionNotificationInsertForm,
     moderator::{ModBanFromCommunity, ModBanFromCommunityForm},
   },
   traits::{Bannable, Crud, Followable},
+  ModActionNotificationType,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_db_views_actor::structs::PersonView;
@@ -99,12 +108,40 @@ pub async fn ban_from_community(
     expires,
   };
 
-  ModBanFromCommunity::create(&mut context.pool(), &form).await?;
+  let entry = ModBanFromCommunity::create(&mut context.pool(), &form).await?;
+
+  if data.ban {
+    let notification_form = ModActionNotificationInsertForm {
+      reason: data.reason.clone(),
+      ..ModActionNotificationInsertForm::new(
+        banned_person_id,
+        ModActionNotificationType::BanFromCommunity,
+        Some(data.community_id),
+      )
+    };
+    send_mod_action_notification(
+      &notification_form,
+      local_user_view.person.id,
+      &mut context.pool(),
+    )
+    .await?;
+  }
 
   let person_view = PersonView::read(&mut context.pool(), data.person_id)
     .await?
     .ok_or(LemmyErrorType::CouldntFindPerson)?;
 
+  send_webhook_event(
+    WebhookEventType::ModAction,
+    Some(data.community_id),
+    &WebhookModAction::BanFromCommunity {
+      entry,
+      person: person_view.person.clone(),
+    },
+    &context,
+  )
+  .await;
+
   ActivityChannel::submit_activity(
     SendActivityData::BanFromCommunity {
       moderator: local_user_view.person,
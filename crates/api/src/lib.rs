@@ -12,6 +12,7 @@ use lemmy_api_common::{
 };
 use lemmy_db_schema::{
   source::{
+    api_token::ApiToken,
     community::{
       CommunityFollower,
       CommunityFollowerForm,
@@ -250,6 +251,23 @@ pub(crate) async fn ban_nonlocal_user_from_local_communities(
   Ok(())
 }
 
+/// Read the user for an api token, along with the token itself so that its scopes can be checked.
+#[tracing::instrument(skip_all)]
+pub async fn local_user_view_from_api_token(
+  token: &str,
+  context: &LemmyContext,
+) -> LemmyResult<(LocalUserView, ApiToken)> {
+  let api_token = ApiToken::validate(&mut context.pool(), token)
+    .await?
+    .ok_or(LemmyErrorType::NotLoggedIn)?;
+  let local_user_view = LocalUserView::read(&mut context.pool(), api_token.local_user_id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindLocalUser)?;
+  check_user_valid(&local_user_view.person)?;
+
+  Ok((local_user_view, api_token))
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn local_user_view_from_jwt(
   jwt: &str,
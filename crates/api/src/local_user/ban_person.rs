@@ -5,6 +5,7 @@ use lemmy_api_common::{
   context::LemmyContext,
   person::{BanPerson, BanPersonResponse},
   send_activity::{ActivityChannel, SendActivityData},
+  send_webhook::{send_webhook_event, WebhookModAction},
   utils::{check_expire_time, is_admin, remove_user_data},
 };
 use lemmy_db_schema::{
@@ -15,6 +16,7 @@ use lemmy_db_schema::{
     person::{Person, PersonUpdateForm},
   },
   traits::Crud,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_db_views_actor::structs::PersonView;
@@ -79,7 +81,17 @@ pub async fn ban_from_site(
     expires,
   };
 
-  ModBan::create(&mut context.pool(), &form).await?;
+  let entry = ModBan::create(&mut context.pool(), &form).await?;
+  send_webhook_event(
+    WebhookEventType::ModAction,
+    None,
+    &WebhookModAction::BanFromSite {
+      entry,
+      person: person.clone(),
+    },
+    &context,
+  )
+  .await;
 
   let person_view = PersonView::read(&mut context.pool(), person.id)
     .await?
@@ -0,0 +1,56 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{CreateApiToken, CreateApiTokenResponse},
+  utils::{check_expire_time, is_admin},
+};
+use lemmy_db_schema::{
+  source::api_token::{ApiToken, ApiTokenInsertForm},
+  ApiTokenScope,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_api_token_name,
+};
+
+/// Create a long-lived api token with limited scopes, for use by bots and integrations. The token
+/// is only returned once, afterwards only its metadata can be listed.
+#[tracing::instrument(skip(context))]
+pub async fn create_api_token(
+  data: Json<CreateApiToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CreateApiTokenResponse>> {
+  let name = data.name.trim().to_string();
+  is_valid_api_token_name(&name)?;
+
+  if data.scopes.is_empty() {
+    Err(LemmyErrorType::ApiTokenScopesRequired)?
+  }
+  if data.scopes.contains(&ApiTokenScope::Admin) {
+    is_admin(&local_user_view)?;
+  }
+  let expires = check_expire_time(data.expires)?;
+
+  let mut scopes = data.scopes.clone();
+  scopes.sort();
+  scopes.dedup();
+
+  let token = ApiToken::generate_token();
+  let form = ApiTokenInsertForm {
+    expires,
+    ..ApiTokenInsertForm::new(
+      local_user_view.local_user.id,
+      name,
+      ApiToken::hash_token(&token),
+      scopes,
+    )
+  };
+  let api_token = ApiToken::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(CreateApiTokenResponse {
+    api_token,
+    token: token.into(),
+  }))
+}
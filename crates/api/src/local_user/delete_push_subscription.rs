@@ -0,0 +1,21 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::DeletePushSubscription, SuccessResponse};
+use lemmy_db_schema::source::push_subscription::PushSubscription;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_push_subscription(
+  data: Json<DeletePushSubscription>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  PushSubscription::delete_for_local_user(
+    &mut context.pool(),
+    local_user_view.local_user.id,
+    &data.endpoint,
+  )
+  .await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
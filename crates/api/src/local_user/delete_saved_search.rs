@@ -0,0 +1,23 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, saved_search::DeleteSavedSearch, SuccessResponse};
+use lemmy_db_schema::{source::saved_search::SavedSearch, traits::Crud};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_saved_search(
+  data: Json<DeleteSavedSearch>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let saved_search = SavedSearch::read(&mut context.pool(), data.id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindSavedSearch)?;
+  if saved_search.local_user_id != local_user_view.local_user.id {
+    Err(LemmyErrorType::CouldntFindSavedSearch)?
+  }
+
+  SavedSearch::delete(&mut context.pool(), saved_search.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
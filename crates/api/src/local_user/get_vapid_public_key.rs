@@ -0,0 +1,12 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::GetVapidPublicKeyResponse};
+use lemmy_utils::error::LemmyResult;
+
+#[tracing::instrument(skip(context))]
+pub async fn get_vapid_public_key(
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<GetVapidPublicKeyResponse>> {
+  Ok(Json(GetVapidPublicKeyResponse {
+    vapid_public_key: context.secret().vapid_public_key.clone(),
+  }))
+}
@@ -0,0 +1,14 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::ListApiTokensResponse};
+use lemmy_db_schema::source::api_token::ApiToken;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_api_tokens(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListApiTokensResponse>> {
+  let api_tokens = ApiToken::list(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListApiTokensResponse { api_tokens }))
+}
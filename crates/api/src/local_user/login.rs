@@ -4,19 +4,34 @@ use actix_web::{
   HttpRequest,
 };
 use bcrypt::verify;
+use chrono::Utc;
 use lemmy_api_common::{
-  claims::Claims,
+  claims::{login_ip_and_user_agent, Claims},
   context::LemmyContext,
   person::{Login, LoginResponse},
-  utils::check_user_valid,
+  utils::{check_user_valid, send_login_locked_email, send_new_login_email},
 };
 use lemmy_db_schema::{
-  source::{local_site::LocalSite, registration_application::RegistrationApplication},
+  source::{
+    local_site::LocalSite,
+    local_user::LocalUser,
+    login_token::LoginToken,
+    registration_application::RegistrationApplication,
+    totp_recovery_code::TotpRecoveryCode,
+  },
   utils::DbPool,
   RegistrationMode,
 };
 use lemmy_db_views::structs::{LocalUserView, SiteView};
-use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  spawn_try_task,
+};
+
+/// Number of failed logins after which the account gets locked temporarily.
+const MAX_FAILED_LOGINS: i32 = 5;
+/// The lockout duration doubles with each further failed login, up to this limit.
+const MAX_LOCKOUT_MINUTES: i32 = 24 * 60;
 
 #[tracing::instrument(skip(context))]
 pub async fn login(
@@ -35,6 +50,15 @@ pub async fn login(
       .await?
       .ok_or(LemmyErrorType::IncorrectLogin)?;
 
+  // Check this before the password, otherwise guessing could simply continue
+  if local_user_view
+    .local_user
+    .login_locked_until
+    .is_some_and(|locked_until| locked_until > Utc::now())
+  {
+    Err(LemmyErrorType::LoginTemporarilyLocked)?
+  }
+
   // Verify the password
   let valid: bool = verify(
     &data.password,
@@ -42,6 +66,7 @@ pub async fn login(
   )
   .unwrap_or(false);
   if !valid {
+    record_failed_login(&local_user_view, &context).await?;
     Err(LemmyErrorType::IncorrectLogin)?
   }
   check_user_valid(&local_user_view.person)?;
@@ -50,17 +75,59 @@ pub async fn login(
   check_registration_application(&local_user_view, &site_view.local_site, &mut context.pool())
     .await?;
 
-  // Check the totp if enabled
+  // Check the totp if enabled, or alternatively a recovery code
   if local_user_view.local_user.totp_2fa_enabled {
-    check_totp_2fa_valid(
+    if let Err(e) = check_totp_2fa_valid(
       &local_user_view,
       &data.totp_2fa_token,
       &context.settings().hostname,
-    )?;
+    ) {
+      let Some(recovery_code) = data.totp_2fa_token.as_deref() else {
+        return Err(e);
+      };
+      let used = TotpRecoveryCode::use_code(
+        &mut context.pool(),
+        local_user_view.local_user.id,
+        recovery_code,
+      )
+      .await?;
+      if !used {
+        record_failed_login(&local_user_view, &context).await?;
+        return Err(e);
+      }
+    }
   }
 
+  if local_user_view.local_user.failed_login_count > 0 {
+    LocalUser::reset_failed_logins(&mut context.pool(), local_user_view.local_user.id).await?;
+  }
+
+  // Needs to be checked before the new login is stored
+  let (ip, user_agent) = login_ip_and_user_agent(&req);
+  let is_known_device = LoginToken::is_known_device(
+    &mut context.pool(),
+    local_user_view.local_user.id,
+    ip.as_deref(),
+    user_agent.as_deref(),
+  )
+  .await?;
+
   let jwt = Claims::generate(local_user_view.local_user.id, req, &context).await?;
 
+  if !is_known_device && local_user_view.local_user.send_login_alerts {
+    let context = context.clone();
+    spawn_try_task(async move {
+      send_new_login_email(
+        &local_user_view,
+        ip,
+        user_agent,
+        &mut context.pool(),
+        context.settings(),
+      )
+      .await
+    });
+  }
+
   Ok(Json(LoginResponse {
     jwt: Some(jwt.clone()),
     verify_email_sent: false,
@@ -68,6 +135,35 @@ pub async fn login(
   }))
 }
 
+/// Count the failed login, and lock the account temporarily if there were too many. The owner is
+/// notified by email when the lockout begins.
+async fn record_failed_login(
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let local_user = LocalUser::record_failed_login(
+    &mut context.pool(),
+    local_user_view.local_user.id,
+    MAX_FAILED_LOGINS,
+    MAX_LOCKOUT_MINUTES,
+  )
+  .await?;
+
+  if let (MAX_FAILED_LOGINS, Some(locked_until)) =
+    (local_user.failed_login_count, local_user.login_locked_until)
+  {
+    let user = LocalUserView {
+      local_user,
+      ..local_user_view.clone()
+    };
+    let context = context.clone();
+    spawn_try_task(async move {
+      send_login_locked_email(&user, locked_until, &mut context.pool(), context.settings()).await
+    });
+  }
+  Ok(())
+}
+
 async fn check_registration_application(
   local_user_view: &LocalUserView,
   local_site: &LocalSite,
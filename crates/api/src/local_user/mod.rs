@@ -6,17 +6,30 @@ pub mod ban_person;
 pub mod block;
 pub mod change_password;
 pub mod change_password_after_reset;
+pub mod create_api_token;
+pub mod delete_push_subscription;
+pub mod delete_saved_search;
 pub mod generate_totp_secret;
 pub mod get_captcha;
+pub mod get_vapid_public_key;
+pub mod list_api_tokens;
 pub mod list_banned;
 pub mod list_logins;
 pub mod list_media;
+pub mod list_saved_searches;
 pub mod login;
 pub mod logout;
 pub mod notifications;
+pub mod regenerate_totp_recovery_codes;
+pub mod register_push_subscription;
 pub mod report_count;
 pub mod reset_password;
+pub mod revoke_api_token;
+pub mod revoke_login;
+pub mod revoke_other_logins;
 pub mod save_settings;
+pub mod trust_bot;
+pub mod unsubscribe;
 pub mod update_totp;
 pub mod validate_auth;
 pub mod verify_email;
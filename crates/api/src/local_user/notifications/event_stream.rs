@@ -0,0 +1,100 @@
+use actix_web::{
+  http::header::{CacheControl, CacheDirective},
+  web::{Data, Query},
+  HttpResponse,
+};
+use lemmy_api_common::{
+  context::LemmyContext,
+  event_stream::GetEventStream,
+  send_stream_event::{event_stream, EventStreamFilter},
+  utils::check_community_user_action,
+};
+use lemmy_db_views::structs::{LocalUserView, PostView};
+use lemmy_db_views_actor::structs::{
+  CommunityBlockView,
+  CommunityFollowerView,
+  InstanceBlockView,
+  PersonBlockView,
+};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use std::collections::HashSet;
+
+/// Keeps the connection open and sends new events for the user as they happen, so that clients
+/// don't need to poll the unread count.
+///
+/// Follows and blocks are read once when the stream is opened, so clients need to reconnect after
+/// changing them. The number of open streams per user isn't limited, each one holds a connection
+/// until the client disconnects.
+#[tracing::instrument(skip(context))]
+pub async fn get_event_stream(
+  data: Query<GetEventStream>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<HttpResponse> {
+  let posts = data.posts.unwrap_or_default();
+  let comments = data.comments.unwrap_or_default();
+  let person_id = local_user_view.person.id;
+
+  // Comments of a single post are only streamed if the user can read the post
+  if let Some(post_id) = data.post_id {
+    let post_view = PostView::read(
+      &mut context.pool(),
+      post_id,
+      Some(&local_user_view.local_user),
+      false,
+    )
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindPost)?;
+    check_community_user_action(
+      &local_user_view.person,
+      post_view.community.id,
+      &mut context.pool(),
+    )
+    .await?;
+  }
+
+  let blocked_instances: HashSet<_> = InstanceBlockView::for_person(&mut context.pool(), person_id)
+    .await?
+    .into_iter()
+    .map(|b| b.instance.id)
+    .collect();
+  // Posts and comments in communities of blocked instances are hidden, like in the listings
+  let followed_communities = if posts || comments {
+    CommunityFollowerView::for_person(&mut context.pool(), person_id)
+      .await?
+      .into_iter()
+      .filter(|f| !blocked_instances.contains(&f.community.instance_id))
+      .map(|f| f.community.id)
+      .collect()
+  } else {
+    Default::default()
+  };
+  let blocked_persons = PersonBlockView::for_person(&mut context.pool(), person_id)
+    .await?
+    .into_iter()
+    .map(|b| b.target.id)
+    .collect();
+  let blocked_communities = CommunityBlockView::for_person(&mut context.pool(), person_id)
+    .await?
+    .into_iter()
+    .map(|b| b.community.id)
+    .collect();
+
+  let filter = EventStreamFilter {
+    person_id,
+    posts,
+    comments,
+    post_id: data.post_id,
+    followed_communities,
+    blocked_persons,
+    blocked_communities,
+    blocked_instances,
+  };
+
+  Ok(
+    HttpResponse::Ok()
+      .content_type("text/event-stream")
+      .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+      .streaming(event_stream(filter)),
+  )
+}
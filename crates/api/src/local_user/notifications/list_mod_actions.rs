@@ -0,0 +1,26 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{GetModActionNotifications, GetModActionNotificationsResponse},
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_db_views_actor::mod_action_notification_view::ModActionNotificationQuery;
+use lemmy_utils::error::LemmyResult;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_mod_action_notifications(
+  data: Query<GetModActionNotifications>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetModActionNotificationsResponse>> {
+  let notifications = ModActionNotificationQuery {
+    recipient_id: local_user_view.person.id,
+    unread_only: data.unread_only.unwrap_or_default(),
+    page: data.page,
+    limit: data.limit,
+  }
+  .list(&mut context.pool())
+  .await?;
+
+  Ok(Json(GetModActionNotificationsResponse { notifications }))
+}
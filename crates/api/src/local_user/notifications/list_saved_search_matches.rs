@@ -0,0 +1,27 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  saved_search::{GetSavedSearchMatches, GetSavedSearchMatchesResponse},
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_db_views_actor::saved_search_match_view::SavedSearchMatchQuery;
+use lemmy_utils::error::LemmyResult;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_saved_search_matches(
+  data: Query<GetSavedSearchMatches>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetSavedSearchMatchesResponse>> {
+  let matches = SavedSearchMatchQuery {
+    local_user_id: local_user_view.local_user.id,
+    saved_search_id: data.saved_search_id,
+    unread_only: data.unread_only.unwrap_or_default(),
+    page: data.page,
+    limit: data.limit,
+  }
+  .list(&mut context.pool())
+  .await?;
+
+  Ok(Json(GetSavedSearchMatchesResponse { matches }))
+}
@@ -2,8 +2,10 @@ use actix_web::web::{Data, Json};
 use lemmy_api_common::{context::LemmyContext, person::GetRepliesResponse};
 use lemmy_db_schema::source::{
   comment_reply::CommentReply,
+  mod_action_notification::ModActionNotification,
   person_mention::PersonMention,
   private_message::PrivateMessage,
+  saved_search::SavedSearchMatch,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
@@ -25,6 +27,16 @@ pub async fn mark_all_notifications_read(
     .await
     .with_lemmy_type(LemmyErrorType::CouldntUpdateComment)?;
 
+  // Mark all mod action notifications as read
+  ModActionNotification::mark_all_as_read(&mut context.pool(), person_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateModActionNotification)?;
+
+  // Mark all saved search matches as read
+  SavedSearchMatch::mark_all_as_read(&mut context.pool(), local_user_view.local_user.id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateSavedSearchMatch)?;
+
   // Mark all private_messages as read
   PrivateMessage::mark_all_as_read(&mut context.pool(), person_id)
     .await
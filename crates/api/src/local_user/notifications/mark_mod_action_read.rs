@@ -0,0 +1,40 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::MarkModActionNotificationAsRead,
+  SuccessResponse,
+};
+use lemmy_db_schema::{
+  source::mod_action_notification::{ModActionNotification, ModActionNotificationUpdateForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn mark_mod_action_notification_as_read(
+  data: Json<MarkModActionNotificationAsRead>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let notification =
+    ModActionNotification::read(&mut context.pool(), data.mod_action_notification_id)
+      .await?
+      .ok_or(LemmyErrorType::CouldntFindModActionNotification)?;
+
+  if local_user_view.person.id != notification.recipient_id {
+    Err(LemmyErrorType::CouldntUpdateModActionNotification)?
+  }
+
+  ModActionNotification::update(
+    &mut context.pool(),
+    notification.id,
+    &ModActionNotificationUpdateForm {
+      read: Some(data.read),
+    },
+  )
+  .await
+  .with_lemmy_type(LemmyErrorType::CouldntUpdateModActionNotification)?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,42 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  saved_search::MarkSavedSearchMatchAsRead,
+  SuccessResponse,
+};
+use lemmy_db_schema::{
+  source::saved_search::{SavedSearch, SavedSearchMatch, SavedSearchMatchUpdateForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn mark_saved_search_match_as_read(
+  data: Json<MarkSavedSearchMatchAsRead>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let saved_search_match = SavedSearchMatch::read(&mut context.pool(), data.saved_search_match_id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindSavedSearchMatch)?;
+  let saved_search = SavedSearch::read(&mut context.pool(), saved_search_match.saved_search_id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindSavedSearch)?;
+
+  if local_user_view.local_user.id != saved_search.local_user_id {
+    Err(LemmyErrorType::CouldntUpdateSavedSearchMatch)?
+  }
+
+  SavedSearchMatch::update(
+    &mut context.pool(),
+    saved_search_match.id,
+    &SavedSearchMatchUpdateForm {
+      read: Some(data.read),
+    },
+  )
+  .await
+  .with_lemmy_type(LemmyErrorType::CouldntUpdateSavedSearchMatch)?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -1,6 +1,11 @@
+pub mod event_stream;
 pub mod list_mentions;
+pub mod list_mod_actions;
 pub mod list_replies;
+pub mod list_saved_search_matches;
 pub mod mark_all_read;
 pub mod mark_mention_read;
+pub mod mark_mod_action_read;
 pub mod mark_reply_read;
+pub mod mark_saved_search_match_read;
 pub mod unread_count;
@@ -1,5 +1,9 @@
 use actix_web::web::{Data, Json};
 use lemmy_api_common::{context::LemmyContext, person::GetUnreadCountResponse};
+use lemmy_db_schema::source::{
+  mod_action_notification::ModActionNotification,
+  saved_search::SavedSearchMatch,
+};
 use lemmy_db_views::structs::{LocalUserView, PrivateMessageView};
 use lemmy_db_views_actor::structs::{CommentReplyView, PersonMentionView};
 use lemmy_utils::error::LemmyResult;
@@ -21,9 +25,16 @@ pub async fn unread_count(
   let private_messages =
     PrivateMessageView::get_unread_messages(&mut context.pool(), person_id).await?;
 
+  let mod_actions = ModActionNotification::get_unread_count(&mut context.pool(), person_id).await?;
+
+  let saved_search_matches =
+    SavedSearchMatch::get_unread_count(&mut context.pool(), local_user_view.local_user.id).await?;
+
   Ok(Json(GetUnreadCountResponse {
     replies,
     mentions,
     private_messages,
+    mod_actions,
+    saved_search_matches,
   }))
 }
@@ -0,0 +1,32 @@
+use crate::check_totp_2fa_valid;
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{RegenerateTotpRecoveryCodes, TotpRecoveryCodesResponse},
+};
+use lemmy_db_schema::source::totp_recovery_code::TotpRecoveryCode;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Replace the recovery codes for two-factor-authentication with new ones, for example if the old
+/// codes were lost or used up. Only possible while 2FA is enabled, and requires a valid token.
+#[tracing::instrument(skip(context))]
+pub async fn regenerate_totp_recovery_codes(
+  data: Json<RegenerateTotpRecoveryCodes>,
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<TotpRecoveryCodesResponse>> {
+  if !local_user_view.local_user.totp_2fa_enabled {
+    Err(LemmyErrorType::TotpNotEnabled)?
+  }
+  check_totp_2fa_valid(
+    &local_user_view,
+    &Some(data.totp_token.clone()),
+    &context.settings().hostname,
+  )?;
+
+  let recovery_codes =
+    TotpRecoveryCode::regenerate(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(TotpRecoveryCodesResponse { recovery_codes }))
+}
@@ -0,0 +1,39 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::RegisterPushSubscription,
+  push::validate_push_subscription,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::push_subscription::{PushSubscription, PushSubscriptionInsertForm};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Maximum number of devices per user which receive push notifications.
+const MAX_PUSH_SUBSCRIPTIONS: usize = 20;
+
+#[tracing::instrument(skip(context))]
+pub async fn register_push_subscription(
+  data: Json<RegisterPushSubscription>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  validate_push_subscription(&data.endpoint, &data.p256dh, &data.auth).await?;
+
+  let local_user_id = local_user_view.local_user.id;
+  let existing = PushSubscription::list_for_local_user(&mut context.pool(), local_user_id).await?;
+  let is_new = !existing.iter().any(|s| s.endpoint == data.endpoint);
+  if is_new && existing.len() >= MAX_PUSH_SUBSCRIPTIONS {
+    Err(LemmyErrorType::TooManyPushSubscriptions)?
+  }
+
+  let form = PushSubscriptionInsertForm::new(
+    local_user_id,
+    data.endpoint.clone(),
+    data.p256dh.clone(),
+    data.auth.clone(),
+  );
+  PushSubscription::upsert(&mut context.pool(), &form).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,20 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::RevokeApiToken, SuccessResponse};
+use lemmy_db_schema::source::api_token::ApiToken;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn revoke_api_token(
+  data: Json<RevokeApiToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let revoked =
+    ApiToken::revoke(&mut context.pool(), local_user_view.local_user.id, data.id).await?;
+  if revoked == 0 {
+    Err(LemmyErrorType::CouldntFindApiToken)?
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
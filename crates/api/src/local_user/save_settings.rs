@@ -146,6 +146,12 @@ pub async fn save_user_settings(
     enable_keyboard_navigation: data.enable_keyboard_navigation,
     enable_animated_images: data.enable_animated_images,
     collapse_bot_comments: data.collapse_bot_comments,
+    send_login_alerts: data.send_login_alerts,
+    email_notification_frequency: data.email_notification_frequency,
+    send_reply_emails: data.send_reply_emails,
+    send_mention_emails: data.send_mention_emails,
+    send_private_message_emails: data.send_private_message_emails,
+    report_resolved_notifications: data.report_resolved_notifications,
     ..Default::default()
   };
 
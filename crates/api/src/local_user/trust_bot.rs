@@ -0,0 +1,35 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_common::{context::LemmyContext, person::TrustBot, utils::is_admin, SuccessResponse};
+use lemmy_db_schema::source::local_user::{LocalUser, LocalUserUpdateForm};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn trust_bot(
+  data: Json<TrustBot>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  is_admin(&local_user_view)?;
+
+  // Only local bot accounts can be trusted
+  let bot = LocalUserView::read_person(&mut context.pool(), data.person_id)
+    .await?
+    .ok_or(LemmyErrorType::ObjectNotLocal)?;
+  if !bot.person.bot_account {
+    Err(LemmyErrorType::NotABotAccount)?
+  }
+
+  LocalUser::update(
+    &mut context.pool(),
+    bot.local_user.id,
+    &LocalUserUpdateForm {
+      trusted_bot: Some(data.trusted),
+      ..Default::default()
+    },
+  )
+  .await
+  .with_lemmy_type(LemmyErrorType::CouldntUpdateUser)?;
+
+  Ok(Json(SuccessResponse::default()))
+}
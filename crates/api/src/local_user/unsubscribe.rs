@@ -0,0 +1,204 @@
+use actix_web::{
+  web::{Data, Json, Query},
+  HttpResponse,
+};
+use lemmy_api_common::{
+  claims::{EmailNotificationCategory, UnsubscribeClaims},
+  context::LemmyContext,
+  person::Unsubscribe,
+  SuccessResponse,
+};
+use lemmy_db_schema::source::{
+  local_user::{LocalUser, LocalUserUpdateForm},
+  saved_search::SavedSearch,
+};
+use lemmy_utils::error::LemmyResult;
+
+/// Used for one-click unsubscribe by mail clients, which send a POST request to the link. The
+/// token is passed in the query, as required by RFC 8058.
+#[tracing::instrument(skip(context))]
+pub async fn unsubscribe(
+  data: Query<Unsubscribe>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<SuccessResponse>> {
+  turn_off_emails(&data.token, &context).await?;
+  Ok(Json(SuccessResponse::default()))
+}
+
+/// Used for the unsubscribe link in the footer of notification emails, which is opened in the
+/// browser. Turns off the emails, and shows a page which confirms it.
+#[tracing::instrument(skip(context))]
+pub async fn unsubscribe_page(
+  data: Query<Unsubscribe>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<HttpResponse> {
+  let category = turn_off_emails(&data.token, &context).await?;
+  let emails = match category {
+    EmailNotificationCategory::All => "notification emails",
+    EmailNotificationCategory::Replies => "emails about replies",
+    EmailNotificationCategory::Mentions => "emails about mentions",
+    EmailNotificationCategory::PrivateMessages => "emails about private messages",
+    EmailNotificationCategory::SavedSearches => "emails about saved searches",
+  };
+  let settings_link = format!(
+    "{}/settings",
+    context.settings().get_protocol_and_hostname()
+  );
+  let html = format!(
+    "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Unsubscribed</title></head>\
+     <body><h1>Unsubscribed</h1><p>You won't receive {emails} anymore.</p>\
+     <p>You can turn them on again in your <a href=\"{settings_link}\">settings</a>.</p>\
+     </body></html>"
+  );
+  Ok(
+    HttpResponse::Ok()
+      .content_type("text/html; charset=utf-8")
+      .body(html),
+  )
+}
+
+/// Turns off the emails of the category in the token, and returns the category.
+async fn turn_off_emails(
+  token: &str,
+  context: &LemmyContext,
+) -> LemmyResult<EmailNotificationCategory> {
+  let (local_user_id, category) = UnsubscribeClaims::validate(token, context)?;
+
+  let off = Some(false);
+  let form = match category {
+    EmailNotificationCategory::All => LocalUserUpdateForm {
+      send_notifications_to_email: off,
+      ..Default::default()
+    },
+    EmailNotificationCategory::Replies => LocalUserUpdateForm {
+      send_reply_emails: off,
+      ..Default::default()
+    },
+    EmailNotificationCategory::Mentions => LocalUserUpdateForm {
+      send_mention_emails: off,
+      ..Default::default()
+    },
+    EmailNotificationCategory::PrivateMessages => LocalUserUpdateForm {
+      send_private_message_emails: off,
+      ..Default::default()
+    },
+    EmailNotificationCategory::SavedSearches => {
+      // Emails are enabled for each saved search, so turn them off for all of them
+      SavedSearch::disable_emails(&mut context.pool(), local_user_id).await?;
+      return Ok(category);
+    }
+  };
+  LocalUser::update(&mut context.pool(), local_user_id, &form).await?;
+
+  Ok(category)
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use lemmy_db_schema::{
+    newtypes::LocalUserId,
+    source::{
+      instance::Instance,
+      local_user::LocalUserInsertForm,
+      person::{Person, PersonInsertForm},
+      saved_search::SavedSearchInsertForm,
+    },
+    traits::Crud,
+  };
+  use lemmy_db_views::structs::LocalUserView;
+  use lemmy_utils::error::LemmyErrorType;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  /// The email settings of the user: all, replies, mentions, private messages and saved searches.
+  async fn email_flags(
+    local_user_id: LocalUserId,
+    context: &LemmyContext,
+  ) -> LemmyResult<[bool; 5]> {
+    let user = LocalUserView::read(&mut context.pool(), local_user_id)
+      .await?
+      .ok_or(LemmyErrorType::CouldntFindLocalUser)?
+      .local_user;
+    let saved_searches = SavedSearch::list(&mut context.pool(), local_user_id).await?;
+    Ok([
+      user.send_notifications_to_email,
+      user.send_reply_emails,
+      user.send_mention_emails,
+      user.send_private_message_emails,
+      saved_searches.iter().any(|s| s.send_email),
+    ])
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_unsubscribe() -> LemmyResult<()> {
+    let federation_context = LemmyContext::init_test_context().await;
+    let context = Data::new(LemmyContext::clone(&federation_context));
+    let pool = &mut context.pool();
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "unsubscribe_person");
+    let person = Person::create(pool, &person_form).await?;
+
+    let categories = [
+      EmailNotificationCategory::All,
+      EmailNotificationCategory::Replies,
+      EmailNotificationCategory::Mentions,
+      EmailNotificationCategory::PrivateMessages,
+      EmailNotificationCategory::SavedSearches,
+    ];
+    for (i, category) in categories.into_iter().enumerate() {
+      // A user with all emails turned on
+      let local_user_form = LocalUserInsertForm {
+        send_notifications_to_email: Some(true),
+        ..LocalUserInsertForm::test_form(person.id)
+      };
+      let local_user = LocalUser::create(pool, &local_user_form, vec![]).await?;
+      let saved_search_form = SavedSearchInsertForm {
+        local_user_id: local_user.id,
+        query: "lemmy".to_string(),
+        community_id: None,
+        creator_id: None,
+        post_title_only: false,
+        send_email: true,
+      };
+      SavedSearch::create(pool, &saved_search_form).await?;
+
+      let token = UnsubscribeClaims::generate(local_user.id, category, &context)?;
+      unsubscribe(Query(Unsubscribe { token }), context.clone()).await?;
+
+      // Only the emails of this category are turned off
+      let mut expected = [true; 5];
+      if let Some(flag) = expected.get_mut(i) {
+        *flag = false;
+      }
+      assert_eq!(expected, email_flags(local_user.id, &context).await?);
+
+      LocalUser::delete(pool, local_user.id).await?;
+    }
+
+    // The page for the link in the email footer turns off the emails too
+    let local_user_form = LocalUserInsertForm::test_form(person.id);
+    let local_user = LocalUser::create(pool, &local_user_form, vec![]).await?;
+    let category = EmailNotificationCategory::Replies;
+    let token = UnsubscribeClaims::generate(local_user.id, category, &context)?;
+    let page = unsubscribe_page(Query(Unsubscribe { token }), context.clone()).await?;
+    assert!(page.status().is_success());
+    let [_, reply_emails, ..] = email_flags(local_user.id, &context).await?;
+    assert!(!reply_emails);
+
+    // Invalid tokens are rejected
+    let token = "invalid".to_string();
+    let res = unsubscribe(Query(Unsubscribe { token }), context.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::InvalidUnsubscribeToken),
+      res.err().map(|e| e.error_type)
+    );
+
+    LocalUser::delete(pool, local_user.id).await?;
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
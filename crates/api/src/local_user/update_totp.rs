@@ -4,7 +4,10 @@ use lemmy_api_common::{
   context::LemmyContext,
   person::{UpdateTotp, UpdateTotpResponse},
 };
-use lemmy_db_schema::source::local_user::{LocalUser, LocalUserUpdateForm};
+use lemmy_db_schema::source::{
+  local_user::{LocalUser, LocalUserUpdateForm},
+  totp_recovery_code::TotpRecoveryCode,
+};
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::error::LemmyResult;
 
@@ -16,6 +19,8 @@ use lemmy_utils::error::LemmyResult;
 ///
 /// Disabling is only possible if 2FA was previously enabled. Again it is necessary to pass a valid
 /// token.
+///
+/// When enabling, new recovery codes are generated and returned. They are deleted when disabling.
 #[tracing::instrument(skip(context))]
 pub async fn update_totp(
   data: Json<UpdateTotp>,
@@ -43,7 +48,16 @@ pub async fn update_totp(
   )
   .await?;
 
+  let local_user_id = local_user_view.local_user.id;
+  let recovery_codes = if data.enabled {
+    Some(TotpRecoveryCode::regenerate(&mut context.pool(), local_user_id).await?)
+  } else {
+    TotpRecoveryCode::delete_all(&mut context.pool(), local_user_id).await?;
+    None
+  };
+
   Ok(Json(UpdateTotpResponse {
     enabled: data.enabled,
+    recovery_codes,
   }))
 }
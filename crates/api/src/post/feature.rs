@@ -5,6 +5,7 @@ use lemmy_api_common::{
   context::LemmyContext,
   post::{FeaturePost, PostResponse},
   send_activity::{ActivityChannel, SendActivityData},
+  send_webhook::{send_webhook_event, WebhookModAction},
   utils::{check_community_mod_action, is_admin},
 };
 use lemmy_db_schema::{
@@ -14,6 +15,7 @@ use lemmy_db_schema::{
   },
   traits::Crud,
   PostFeatureType,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::{error::LemmyResult, LemmyErrorType};
@@ -64,7 +66,17 @@ pub async fn feature_post(
     is_featured_community: data.feature_type == PostFeatureType::Community,
   };
 
-  ModFeaturePost::create(&mut context.pool(), &form).await?;
+  let entry = ModFeaturePost::create(&mut context.pool(), &form).await?;
+  send_webhook_event(
+    WebhookEventType::ModAction,
+    Some(orig_post.community_id),
+    &WebhookModAction::FeaturePost {
+      entry,
+      post: post.clone(),
+    },
+    &context,
+  )
+  .await;
 
   ActivityChannel::submit_activity(
     SendActivityData::FeaturePost(post, local_user_view.person.clone(), data.featured),
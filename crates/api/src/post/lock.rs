@@ -5,14 +5,18 @@ use lemmy_api_common::{
   context::LemmyContext,
   post::{LockPost, PostResponse},
   send_activity::{ActivityChannel, SendActivityData},
-  utils::check_community_mod_action,
+  send_webhook::{send_webhook_event, WebhookModAction},
+  utils::{check_community_mod_action, send_mod_action_notification},
 };
 use lemmy_db_schema::{
   source::{
+    mod_action_notification::ModActionNotificationInsertForm,
     moderator::{ModLockPost, ModLockPostForm},
     post::{Post, PostUpdateForm},
   },
   traits::Crud,
+  ModActionNotificationType,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::{error::LemmyResult, LemmyErrorType};
@@ -55,7 +59,34 @@ pub async fn lock_post(
     post_id: data.post_id,
     locked: Some(locked),
   };
-  ModLockPost::create(&mut context.pool(), &form).await?;
+  let entry = ModLockPost::create(&mut context.pool(), &form).await?;
+  send_webhook_event(
+    WebhookEventType::ModAction,
+    Some(orig_post.community_id),
+    &WebhookModAction::LockPost {
+      entry,
+      post: post.clone(),
+    },
+    &context,
+  )
+  .await;
+
+  if locked {
+    let notification_form = ModActionNotificationInsertForm {
+      post_id: Some(post_id),
+      ..ModActionNotificationInsertForm::new(
+        orig_post.creator_id,
+        ModActionNotificationType::LockPost,
+        Some(orig_post.community_id),
+      )
+    };
+    send_mod_action_notification(
+      &notification_form,
+      local_user_view.person.id,
+      &mut context.pool(),
+    )
+    .await?;
+  }
 
   ActivityChannel::submit_activity(
     SendActivityData::LockPost(post, local_user_view.person.clone(), data.locked),
@@ -4,7 +4,9 @@ use actix_web::web::Json;
 use lemmy_api_common::{
   context::LemmyContext,
   post::{CreatePostReport, PostReportResponse},
+  push::send_report_push_notifications,
   send_activity::{ActivityChannel, SendActivityData},
+  send_webhook::send_webhook_event,
   utils::{
     check_community_user_action,
     check_post_deleted_or_removed,
@@ -17,6 +19,7 @@ use lemmy_db_schema::{
     post_report::{PostReport, PostReportForm},
   },
   traits::Reportable,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::{LocalUserView, PostReportView, PostView};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
@@ -76,6 +79,23 @@ pub async fn create_post_report(
     .await?;
   }
 
+  // Notify the moderators
+  send_report_push_notifications(
+    post_view.community.id,
+    &local_user_view.person.name,
+    &report.reason,
+    &context,
+  )
+  .await;
+
+  send_webhook_event(
+    WebhookEventType::ReportCreated,
+    Some(post_view.community.id),
+    &post_report_view,
+    &context,
+  )
+  .await;
+
   ActivityChannel::submit_activity(
     SendActivityData::CreateReport {
       object_id: post_view.post.ap_id.inner().clone(),
@@ -2,13 +2,18 @@ use actix_web::web::{Data, Json};
 use lemmy_api_common::{
   context::LemmyContext,
   post::{PostReportResponse, ResolvePostReport},
-  utils::check_community_mod_action,
+  utils::{check_community_mod_action, report_outcome, send_report_resolved_notification},
+};
+use lemmy_db_schema::{
+  source::{mod_action_notification::ModActionNotificationInsertForm, post_report::PostReport},
+  traits::Reportable,
+  ModActionNotificationType,
 };
-use lemmy_db_schema::{source::post_report::PostReport, traits::Reportable};
 use lemmy_db_views::structs::{LocalUserView, PostReportView};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
-/// Resolves or unresolves a post report and notifies the moderators of the community
+/// Resolves or unresolves a post report and notifies the moderators of the community, as well as
+/// the reporter
 #[tracing::instrument(skip(context))]
 pub async fn resolve_post_report(
   data: Json<ResolvePostReport>,
@@ -34,6 +39,20 @@ pub async fn resolve_post_report(
     PostReport::resolve(&mut context.pool(), report_id, person_id)
       .await
       .with_lemmy_type(LemmyErrorType::CouldntResolveReport)?;
+
+    if !report.post_report.resolved {
+      let creator_banned = report.post_creator.banned || report.creator_banned_from_community;
+      let notification_form = ModActionNotificationInsertForm {
+        post_id: Some(report.post.id),
+        report_outcome: Some(report_outcome(report.post.removed, creator_banned)),
+        ..ModActionNotificationInsertForm::new(
+          report.creator.id,
+          ModActionNotificationType::ReportResolved,
+          Some(report.community.id),
+        )
+      };
+      send_report_resolved_notification(&notification_form, person_id, &mut context.pool()).await?;
+    }
   } else {
     PostReport::unresolve(&mut context.pool(), report_id, person_id)
       .await
@@ -3,6 +3,7 @@ use actix_web::web::{Data, Json};
 use lemmy_api_common::{
   context::LemmyContext,
   private_message::{CreatePrivateMessageReport, PrivateMessageReportResponse},
+  send_webhook::send_webhook_event,
   utils::send_new_report_email_to_admins,
 };
 use lemmy_db_schema::{
@@ -12,6 +13,7 @@ use lemmy_db_schema::{
     private_message_report::{PrivateMessageReport, PrivateMessageReportForm},
   },
   traits::{Crud, Reportable},
+  WebhookEventType,
 };
 use lemmy_db_views::structs::{LocalUserView, PrivateMessageReportView};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
@@ -64,6 +66,15 @@ pub async fn create_pm_report(
     .await?;
   }
 
+  // Private messages don't belong to a community, so only site webhooks get this
+  send_webhook_event(
+    WebhookEventType::ReportCreated,
+    None,
+    &private_message_report_view,
+    &context,
+  )
+  .await;
+
   // TODO: consider federating this
 
   Ok(Json(PrivateMessageReportResponse {
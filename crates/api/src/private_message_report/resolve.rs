@@ -2,9 +2,16 @@ use actix_web::web::{Data, Json};
 use lemmy_api_common::{
   context::LemmyContext,
   private_message::{PrivateMessageReportResponse, ResolvePrivateMessageReport},
-  utils::is_admin,
+  utils::{is_admin, report_outcome, send_report_resolved_notification},
+};
+use lemmy_db_schema::{
+  source::{
+    mod_action_notification::ModActionNotificationInsertForm,
+    private_message_report::PrivateMessageReport,
+  },
+  traits::Reportable,
+  ModActionNotificationType,
 };
-use lemmy_db_schema::{source::private_message_report::PrivateMessageReport, traits::Reportable};
 use lemmy_db_views::structs::{LocalUserView, PrivateMessageReportView};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -18,10 +25,29 @@ pub async fn resolve_pm_report(
 
   let report_id = data.report_id;
   let person_id = local_user_view.person.id;
+  let report = PrivateMessageReportView::read(&mut context.pool(), report_id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindPrivateMessageReport)?;
+
   if data.resolved {
     PrivateMessageReport::resolve(&mut context.pool(), report_id, person_id)
       .await
       .with_lemmy_type(LemmyErrorType::CouldntResolveReport)?;
+
+    if !report.private_message_report.resolved {
+      // Private messages can't be removed, only their creator can be banned
+      let outcome = report_outcome(false, report.private_message_creator.banned);
+      let notification_form = ModActionNotificationInsertForm {
+        private_message_id: Some(report.private_message.id),
+        report_outcome: Some(outcome),
+        ..ModActionNotificationInsertForm::new(
+          report.creator.id,
+          ModActionNotificationType::ReportResolved,
+          None,
+        )
+      };
+      send_report_resolved_notification(&notification_form, person_id, &mut context.pool()).await?;
+    }
   } else {
     PrivateMessageReport::unresolve(&mut context.pool(), report_id, person_id)
       .await
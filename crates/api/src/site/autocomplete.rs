@@ -0,0 +1,69 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{Autocomplete, AutocompleteResponse},
+  utils::check_private_instance,
+};
+use lemmy_db_schema::{
+  impls::local_user::LocalUserOptionHelper,
+  source::custom_emoji::CustomEmoji,
+  utils::limit_and_offset,
+  AutocompleteType,
+};
+use lemmy_db_views::structs::{LocalUserView, SiteView};
+use lemmy_db_views_actor::autocomplete_view::AutocompleteQuery;
+use lemmy_utils::{error::LemmyResult, LemmyErrorType};
+
+const MIN_PREFIX_LENGTH: usize = 2;
+
+#[tracing::instrument(skip(context))]
+pub async fn autocomplete(
+  data: Query<Autocomplete>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<AutocompleteResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool())
+    .await?
+    .ok_or(LemmyErrorType::LocalSiteNotSetup)?;
+
+  check_private_instance(&local_user_view, &site_view.local_site)?;
+
+  let prefix = data
+    .q
+    .trim()
+    .trim_start_matches(['@', '!', ':'])
+    .trim_end_matches(':');
+  // Single characters match too much to be useful, and can't use the prefix indexes well
+  if prefix.chars().count() < MIN_PREFIX_LENGTH {
+    Err(LemmyErrorType::AutocompletePrefixTooShort)?
+  }
+  let local_user = local_user_view.as_ref().map(|l| &l.local_user);
+  let query = AutocompleteQuery {
+    prefix: prefix.to_string(),
+    local_user,
+    show_nsfw: local_user.show_nsfw(&site_view.site),
+    limit: data.limit,
+  };
+
+  let mut users = Vec::new();
+  let mut communities = Vec::new();
+  let mut emojis = Vec::new();
+  match data.type_ {
+    AutocompleteType::Users => users = query.list_persons(&mut context.pool()).await?,
+    AutocompleteType::Communities => {
+      communities = query.list_communities(&mut context.pool()).await?
+    }
+    AutocompleteType::Emojis => {
+      let (limit, _) = limit_and_offset(None, data.limit)?;
+      emojis =
+        CustomEmoji::autocomplete(&mut context.pool(), site_view.local_site.id, prefix, limit)
+          .await?
+    }
+  }
+
+  Ok(Json(AutocompleteResponse {
+    users,
+    communities,
+    emojis,
+  }))
+}
@@ -0,0 +1,21 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{ListFailedEmails, ListFailedEmailsResponse},
+  utils::is_admin,
+};
+use lemmy_db_schema::source::email_queue::EmailQueue;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_failed_emails(
+  data: Query<ListFailedEmails>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFailedEmailsResponse>> {
+  is_admin(&local_user_view)?;
+
+  let emails = EmailQueue::list_failed(&mut context.pool(), data.page, data.limit).await?;
+  Ok(Json(ListFailedEmailsResponse { emails }))
+}
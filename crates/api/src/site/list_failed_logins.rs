@@ -0,0 +1,20 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{ListFailedLogins, ListFailedLoginsResponse},
+  utils::is_admin,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_failed_logins(
+  data: Query<ListFailedLogins>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFailedLoginsResponse>> {
+  is_admin(&local_user_view)?;
+
+  let users = LocalUserView::list_failed_logins(&mut context.pool(), data.page, data.limit).await?;
+  Ok(Json(ListFailedLoginsResponse { users }))
+}
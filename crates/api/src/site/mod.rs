@@ -1,7 +1,10 @@
+pub mod autocomplete;
 pub mod block;
 pub mod federated_instances;
 pub mod leave_admin;
 pub mod list_all_media;
+pub mod list_failed_emails;
+pub mod list_failed_logins;
 pub mod mod_log;
 pub mod purge;
 pub mod registration_applications;
@@ -66,7 +66,12 @@ pub async fn approve_registration_application(
       .ok_or(LemmyErrorType::CouldntFindLocalUser)?;
     if approved_local_user_view.local_user.email.is_some() {
       // Email sending may fail, but this won't revert the application approval
-      send_application_approved_email(&approved_local_user_view, context.settings()).await?;
+      send_application_approved_email(
+        &approved_local_user_view,
+        &mut context.pool(),
+        context.settings(),
+      )
+      .await?;
     }
   };
 
@@ -1,13 +1,19 @@
 use crate::{
+  claims::EmailNotificationCategory,
   comment::CommentResponse,
   community::CommunityResponse,
   context::LemmyContext,
+  event_stream::StreamEvent,
+  person::PushNotification,
   post::PostResponse,
+  push::send_push_notification,
+  send_stream_event::send_stream_event,
   utils::{
+    build_email,
     check_person_instance_community_block,
-    get_interface_language,
     is_mod_or_admin,
     send_email_to_user,
+    EmailTemplateVars,
   },
 };
 use actix_web::web::Json;
@@ -21,6 +27,7 @@ use lemmy_db_schema::{
     person_mention::{PersonMention, PersonMentionInsertForm},
   },
   traits::Crud,
+  EmailTemplateType,
 };
 use lemmy_db_views::structs::{CommentView, LocalUserView, PostView};
 use lemmy_db_views_actor::structs::CommunityView;
@@ -103,7 +110,8 @@ pub async fn send_local_notifs(
   local_user_view: Option<&LocalUserView>,
 ) -> LemmyResult<Vec<LocalUserId>> {
   let mut recipient_ids = Vec::new();
-  let inbox_link = format!("{}/inbox", context.settings().get_protocol_and_hostname());
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+  let inbox_link = format!("{protocol_and_hostname}/inbox");
 
   // let person = my_local_user.person;
   // Read the comment view to get extra info
@@ -117,6 +125,12 @@ pub async fn send_local_notifs(
   let comment = comment_view.comment;
   let post = comment_view.post;
   let community = comment_view.community;
+  let comment_link = format!("{protocol_and_hostname}/comment/{}", comment.id);
+  let push_notification = |title: String| PushNotification {
+    title,
+    body: comment.content.clone(),
+    url: comment_link.clone(),
+  };
 
   // Send the local mentions
   for mention in mentions
@@ -140,19 +154,46 @@ pub async fn send_local_notifs(
 
       // Allow this to fail softly, since comment edits might re-update or replace it
       // Let the uniqueness handle this fail
-      PersonMention::create(&mut context.pool(), &user_mention_form)
+      let person_mention = PersonMention::create(&mut context.pool(), &user_mention_form)
         .await
         .ok();
 
       // Send an email to those local users that have notifications on
       if do_send_email {
-        let lang = get_interface_language(&mention_user_view);
+        if let Some(person_mention) = person_mention {
+          send_stream_event(|| StreamEvent::PersonMention {
+            person_mention,
+            comment: comment.clone(),
+            post: post.clone(),
+          });
+        }
         let content = markdown_to_html(&comment.content);
+        let vars = EmailTemplateVars {
+          username: &mention_user_view.person.name,
+          link: &inbox_link,
+          sender: &person.name,
+          content: &content,
+        };
+        let (subject, body) = build_email(
+          &mut context.pool(),
+          EmailTemplateType::Mention,
+          &mention_user_view.local_user.interface_language,
+          &vars,
+          context.settings(),
+        )
+        .await;
         send_email_to_user(
           &mention_user_view,
-          &lang.notification_mentioned_by_subject(&person.name),
-          &lang.notification_mentioned_by_body(&content, &inbox_link, &person.name),
-          context.settings(),
+          EmailNotificationCategory::Mentions,
+          &subject,
+          &body,
+          context,
+        )
+        .await;
+        send_push_notification(
+          mention_user_view.local_user.id,
+          &push_notification(format!("Mentioned by {}", person.name)),
+          context,
         )
         .await
       }
@@ -195,18 +236,45 @@ pub async fn send_local_notifs(
 
           // Allow this to fail softly, since comment edits might re-update or replace it
           // Let the uniqueness handle this fail
-          CommentReply::create(&mut context.pool(), &comment_reply_form)
+          let comment_reply = CommentReply::create(&mut context.pool(), &comment_reply_form)
             .await
             .ok();
 
           if do_send_email {
-            let lang = get_interface_language(&parent_user_view);
+            if let Some(comment_reply) = comment_reply {
+              send_stream_event(|| StreamEvent::CommentReply {
+                comment_reply,
+                comment: comment.clone(),
+                post: post.clone(),
+              });
+            }
             let content = markdown_to_html(&comment.content);
+            let vars = EmailTemplateVars {
+              username: &parent_user_view.person.name,
+              link: &inbox_link,
+              sender: &person.name,
+              content: &content,
+            };
+            let (subject, body) = build_email(
+              &mut context.pool(),
+              EmailTemplateType::CommentReply,
+              &parent_user_view.local_user.interface_language,
+              &vars,
+              context.settings(),
+            )
+            .await;
             send_email_to_user(
               &parent_user_view,
-              &lang.notification_comment_reply_subject(&person.name),
-              &lang.notification_comment_reply_body(&content, &inbox_link, &person.name),
-              context.settings(),
+              EmailNotificationCategory::Replies,
+              &subject,
+              &body,
+              context,
+            )
+            .await;
+            send_push_notification(
+              parent_user_view.local_user.id,
+              &push_notification(format!("Reply from {}", person.name)),
+              context,
             )
             .await
           }
@@ -241,18 +309,45 @@ pub async fn send_local_notifs(
 
           // Allow this to fail softly, since comment edits might re-update or replace it
           // Let the uniqueness handle this fail
-          CommentReply::create(&mut context.pool(), &comment_reply_form)
+          let comment_reply = CommentReply::create(&mut context.pool(), &comment_reply_form)
             .await
             .ok();
 
           if do_send_email {
-            let lang = get_interface_language(&parent_user_view);
+            if let Some(comment_reply) = comment_reply {
+              send_stream_event(|| StreamEvent::CommentReply {
+                comment_reply,
+                comment: comment.clone(),
+                post: post.clone(),
+              });
+            }
             let content = markdown_to_html(&comment.content);
+            let vars = EmailTemplateVars {
+              username: &parent_user_view.person.name,
+              link: &inbox_link,
+              sender: &person.name,
+              content: &content,
+            };
+            let (subject, body) = build_email(
+              &mut context.pool(),
+              EmailTemplateType::PostReply,
+              &parent_user_view.local_user.interface_language,
+              &vars,
+              context.settings(),
+            )
+            .await;
             send_email_to_user(
               &parent_user_view,
-              &lang.notification_post_reply_subject(&person.name),
-              &lang.notification_post_reply_body(&content, &inbox_link, &person.name),
-              context.settings(),
+              EmailNotificationCategory::Replies,
+              &subject,
+              &body,
+              context,
+            )
+            .await;
+            send_push_notification(
+              parent_user_view.local_user.id,
+              &push_notification(format!("Reply from {}", person.name)),
+              context,
             )
             .await
           }
@@ -261,5 +356,12 @@ pub async fn send_local_notifs(
     }
   }
 
+  if do_send_email {
+    send_stream_event(|| StreamEvent::Comment {
+      comment,
+      community_id: community.id,
+    });
+  }
+
   Ok(recipient_ids)
 }
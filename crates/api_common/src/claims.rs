@@ -52,15 +52,7 @@ impl Claims {
     let secret = &context.secret().jwt_secret;
     let key = EncodingKey::from_secret(secret.as_ref());
     let token: SensitiveString = encode(&Header::default(), &my_claims, &key)?.into();
-    let ip = req
-      .connection_info()
-      .realip_remote_addr()
-      .map(ToString::to_string);
-    let user_agent = req
-      .headers()
-      .get(USER_AGENT)
-      .and_then(|ua| ua.to_str().ok())
-      .map(ToString::to_string);
+    let (ip, user_agent) = login_ip_and_user_agent(&req);
     let form = LoginTokenCreateForm {
       token: token.clone(),
       user_id,
@@ -72,12 +64,84 @@ impl Claims {
   }
 }
 
+/// A category of notification emails, which can be turned off with an unsubscribe link.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmailNotificationCategory {
+  /// All notification emails, for digests which contain every category.
+  All,
+  Replies,
+  Mentions,
+  PrivateMessages,
+  /// New matches of saved searches, which are only sent for searches with email enabled.
+  SavedSearches,
+}
+
+/// Signed token in the unsubscribe links of notification emails, which works without login.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct UnsubscribeClaims {
+  /// local_user_id, standard claim by RFC 7519.
+  pub sub: String,
+  pub iss: String,
+  pub category: EmailNotificationCategory,
+}
+
+impl UnsubscribeClaims {
+  /// Returns the user and category which the token was generated for.
+  pub fn validate(
+    token: &str,
+    context: &LemmyContext,
+  ) -> LemmyResult<(LocalUserId, EmailNotificationCategory)> {
+    let mut validation = Validation::default();
+    validation.validate_exp = false;
+    validation.required_spec_claims.remove("exp");
+    validation.set_issuer(&[&context.settings().hostname]);
+    let jwt_secret = &context.secret().jwt_secret;
+    let key = DecodingKey::from_secret(jwt_secret.as_ref());
+    let claims = decode::<UnsubscribeClaims>(token, &key, &validation)
+      .with_lemmy_type(LemmyErrorType::InvalidUnsubscribeToken)?;
+    let user_id = LocalUserId(claims.claims.sub.parse()?);
+    Ok((user_id, claims.claims.category))
+  }
+
+  pub fn generate(
+    user_id: LocalUserId,
+    category: EmailNotificationCategory,
+    context: &LemmyContext,
+  ) -> LemmyResult<String> {
+    let claims = UnsubscribeClaims {
+      sub: user_id.0.to_string(),
+      iss: context.settings().hostname.clone(),
+      category,
+    };
+    let secret = &context.secret().jwt_secret;
+    let key = EncodingKey::from_secret(secret.as_ref());
+    Ok(encode(&Header::default(), &claims, &key)?)
+  }
+}
+
+/// Returns the ip address and user agent which are stored for a login.
+pub fn login_ip_and_user_agent(req: &HttpRequest) -> (Option<String>, Option<String>) {
+  let ip = req
+    .connection_info()
+    .realip_remote_addr()
+    .map(ToString::to_string);
+  let user_agent = req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|ua| ua.to_str().ok())
+    .map(ToString::to_string);
+  (ip, user_agent)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 #[allow(clippy::indexing_slicing)]
 mod tests {
 
-  use crate::{claims::Claims, context::LemmyContext};
+  use crate::{
+    claims::{Claims, EmailNotificationCategory, UnsubscribeClaims},
+    context::LemmyContext,
+  };
   use actix_web::test::TestRequest;
   use lemmy_db_schema::{
     source::{
@@ -130,6 +194,20 @@ mod tests {
     let valid = Claims::validate(&jwt, &context).await;
     assert!(valid.is_ok());
 
+    // Login tokens and unsubscribe tokens can't be used for each other
+    let unsubscribe = UnsubscribeClaims::generate(
+      inserted_local_user.id,
+      EmailNotificationCategory::Mentions,
+      &context,
+    )
+    .unwrap();
+    assert_eq!(
+      (inserted_local_user.id, EmailNotificationCategory::Mentions),
+      UnsubscribeClaims::validate(&unsubscribe, &context).unwrap()
+    );
+    assert!(UnsubscribeClaims::validate(&jwt, &context).is_err());
+    assert!(Claims::validate(&unsubscribe, &context).await.is_err());
+
     let num_deleted = Person::delete(pool, inserted_person.id).await.unwrap();
     assert_eq!(1, num_deleted);
   }
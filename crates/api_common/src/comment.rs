@@ -3,7 +3,7 @@ use lemmy_db_schema::{
   CommentSortType,
   ListingType,
 };
-use lemmy_db_views::structs::{CommentReportView, CommentView, VoteView};
+use lemmy_db_views::structs::{CommentReportView, CommentView, PaginationCursor, VoteView};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
@@ -110,6 +110,7 @@ pub struct GetComments {
   pub type_: Option<ListingType>,
   pub sort: Option<CommentSortType>,
   pub max_depth: Option<i32>,
+  /// DEPRECATED, use page_cursor
   pub page: Option<i64>,
   pub limit: Option<i64>,
   pub community_id: Option<CommunityId>,
@@ -119,14 +120,62 @@ pub struct GetComments {
   pub saved_only: Option<bool>,
   pub liked_only: Option<bool>,
   pub disliked_only: Option<bool>,
+  /// Only for flat lists, comment trees fetched with max_depth can't be paginated.
+  pub page_cursor: Option<PaginationCursor>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
 /// The comment list response.
 pub struct GetCommentsResponse {
   pub comments: Vec<CommentView>,
+  /// the pagination cursor to use to fetch the next page
+  pub next_page: Option<PaginationCursor>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get a comment tree, with a limited number of children for each comment. Either the top level
+/// comments of a post, or the replies to a comment (without the comment itself).
+pub struct GetCommentTree {
+  pub post_id: Option<PostId>,
+  pub parent_id: Option<CommentId>,
+  pub sort: Option<CommentSortType>,
+  pub max_depth: Option<i32>,
+  /// The maximum number of children for each comment.
+  pub children_limit: Option<i64>,
+  /// Continue the first level after this cursor, from `next_page` of the response or of a node.
+  pub page_cursor: Option<PaginationCursor>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The comment tree response. The nodes are in the same order as in `GetCommentsResponse`.
+pub struct GetCommentTreeResponse {
+  pub comments: Vec<CommentTreeNode>,
+  /// Cursor for more comments on the first level, if the children limit was reached.
+  pub next_page: Option<PaginationCursor>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A comment in a tree, with information for loading the replies which weren't returned.
+pub struct CommentTreeNode {
+  pub comment_view: CommentView,
+  /// The number of replies, including nested ones, which are in branches that weren't loaded yet.
+  /// Some of them may be hidden when they are loaded, eg if their creator is blocked.
+  pub missing_replies: i64,
+  /// To load the missing branches, use this comment as `parent_id`, together with this cursor.
+  /// Not set if none of the direct replies were loaded, then only `parent_id` is needed.
+  pub next_page: Option<PaginationCursor>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
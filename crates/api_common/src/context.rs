@@ -65,6 +65,8 @@ impl LemmyContext {
     let secret = Secret {
       id: 0,
       jwt_secret: String::new().into(),
+      vapid_private_key: None,
+      vapid_public_key: None,
     };
 
     let rate_limit_cell = RateLimitCell::with_test_config();
@@ -0,0 +1,81 @@
+use lemmy_db_schema::{
+  newtypes::EmailTemplateId,
+  source::email_template::EmailTemplate,
+  EmailTemplateType,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create an email template, which replaces the built-in email. Only for admins.
+pub struct CreateEmailTemplate {
+  pub template_type: EmailTemplateType,
+  /// An interface language like `de`. Leave empty to use the template for all languages.
+  pub language: Option<String>,
+  pub subject: String,
+  pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Edit an email template.
+pub struct EditEmailTemplate {
+  pub id: EmailTemplateId,
+  pub subject: String,
+  pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete an email template, so that the built-in email is used again.
+pub struct DeleteEmailTemplate {
+  pub id: EmailTemplateId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for an email template.
+pub struct EmailTemplateResponse {
+  pub email_template: EmailTemplate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// All email templates which were created by admins.
+pub struct ListEmailTemplatesResponse {
+  pub email_templates: Vec<EmailTemplate>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Show how an email looks with example values.
+///
+/// If subject and body are given they are rendered, for previewing changes before saving them.
+/// Otherwise the email is built the same way as when it is sent.
+pub struct PreviewEmailTemplate {
+  pub template_type: EmailTemplateType,
+  /// The interface language, defaults to English.
+  pub language: Option<String>,
+  pub subject: Option<String>,
+  pub body: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The rendered email, with the body as html.
+pub struct PreviewEmailTemplateResponse {
+  pub subject: String,
+  pub body: String,
+}
@@ -0,0 +1,57 @@
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PostId},
+  source::{
+    comment::Comment,
+    comment_reply::CommentReply,
+    person_mention::PersonMention,
+    post::Post,
+  },
+};
+use lemmy_db_views::structs::PrivateMessageView;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Open a stream of server-sent events for the logged in user. Notifications and private messages
+/// are always included, new posts and comments only if requested.
+pub struct GetEventStream {
+  /// Include new posts in followed communities.
+  pub posts: Option<bool>,
+  /// Include new comments in followed communities.
+  pub comments: Option<bool>,
+  /// Include new comments in this post, even if its community isn't followed.
+  pub post_id: Option<PostId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+#[serde(tag = "type")]
+/// An event in the event stream. The type is also used as the name of the server-sent event.
+pub enum StreamEvent {
+  CommentReply {
+    comment_reply: CommentReply,
+    comment: Comment,
+    post: Post,
+  },
+  PersonMention {
+    person_mention: PersonMention,
+    comment: Comment,
+    post: Post,
+  },
+  PrivateMessage {
+    private_message_view: Box<PrivateMessageView>,
+  },
+  Post {
+    post: Post,
+  },
+  Comment {
+    comment: Comment,
+    community_id: CommunityId,
+  },
+}
@@ -7,16 +7,28 @@ pub mod community;
 #[cfg(feature = "full")]
 pub mod context;
 pub mod custom_emoji;
+pub mod email_template;
+pub mod event_stream;
 pub mod person;
 pub mod post;
 pub mod private_message;
 #[cfg(feature = "full")]
+pub mod push;
+#[cfg(feature = "full")]
 pub mod request;
+pub mod saved_search;
+#[cfg(feature = "full")]
+pub mod search_operators;
 #[cfg(feature = "full")]
 pub mod send_activity;
+#[cfg(feature = "full")]
+pub mod send_stream_event;
+#[cfg(feature = "full")]
+pub mod send_webhook;
 pub mod site;
 #[cfg(feature = "full")]
 pub mod utils;
+pub mod webhook;
 
 pub extern crate lemmy_db_schema;
 pub extern crate lemmy_db_views;
@@ -1,8 +1,19 @@
 use lemmy_db_schema::{
-  newtypes::{CommentReplyId, CommunityId, LanguageId, PersonId, PersonMentionId},
+  newtypes::{
+    ApiTokenId,
+    CommentReplyId,
+    CommunityId,
+    LanguageId,
+    LoginTokenId,
+    ModActionNotificationId,
+    PersonId,
+    PersonMentionId,
+  },
   sensitive::SensitiveString,
-  source::site::Site,
+  source::{api_token::ApiToken, site::Site},
+  ApiTokenScope,
   CommentSortType,
+  EmailNotificationFrequency,
   ListingType,
   PostListingMode,
   SortType,
@@ -11,6 +22,7 @@ use lemmy_db_views::structs::{CommentView, LocalImageView, PostView};
 use lemmy_db_views_actor::structs::{
   CommentReplyView,
   CommunityModeratorView,
+  ModActionNotificationView,
   PersonMentionView,
   PersonView,
 };
@@ -27,7 +39,8 @@ use ts_rs::TS;
 pub struct Login {
   pub username_or_email: SensitiveString,
   pub password: SensitiveString,
-  /// May be required, if totp is enabled for their account.
+  /// May be required, if totp is enabled for their account. A recovery code can also be used
+  /// instead of the totp token.
   pub totp_2fa_token: Option<String>,
 }
 
@@ -129,6 +142,18 @@ pub struct SaveUserSettings {
   pub enable_animated_images: Option<bool>,
   /// Whether to auto-collapse bot comments.
   pub collapse_bot_comments: Option<bool>,
+  /// Sends an email when you log in from a new ip address or device.
+  pub send_login_alerts: Option<bool>,
+  /// Receive notification emails instantly, or as an hourly, daily or weekly digest.
+  pub email_notification_frequency: Option<EmailNotificationFrequency>,
+  /// Get emails about replies to your posts and comments.
+  pub send_reply_emails: Option<bool>,
+  /// Get emails when you are mentioned.
+  pub send_mention_emails: Option<bool>,
+  /// Get emails about new private messages.
+  pub send_private_message_emails: Option<bool>,
+  /// Get notified when a report you filed is resolved.
+  pub report_resolved_notifications: Option<bool>,
   /// Some vote display mode settings
   pub show_scores: Option<bool>,
   pub show_upvotes: Option<bool>,
@@ -201,6 +226,15 @@ pub struct AddAdmin {
   pub added: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Marks a local bot account as trusted, which gives it the same rate limits as admins.
+pub struct TrustBot {
+  pub person_id: PersonId,
+  pub trusted: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -337,6 +371,34 @@ pub struct CommentReplyResponse {
   pub comment_reply_view: CommentReplyView,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get notifications about moderator actions on your content, and about your resolved reports.
+pub struct GetModActionNotifications {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub unread_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response of moderator action notifications.
+pub struct GetModActionNotificationsResponse {
+  pub notifications: Vec<ModActionNotificationView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Mark a moderator action notification as read.
+pub struct MarkModActionNotificationAsRead {
+  pub mod_action_notification_id: ModActionNotificationId,
+  pub read: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -393,6 +455,8 @@ pub struct GetUnreadCountResponse {
   pub replies: i64,
   pub mentions: i64,
   pub private_messages: i64,
+  pub mod_actions: i64,
+  pub saved_search_matches: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, Hash)]
@@ -403,6 +467,14 @@ pub struct VerifyEmail {
   pub token: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Turn off notification emails with the token from an unsubscribe link. Doesn't require login.
+pub struct Unsubscribe {
+  pub token: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -418,11 +490,30 @@ pub struct UpdateTotp {
   pub enabled: bool,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
 pub struct UpdateTotpResponse {
   pub enabled: bool,
+  /// One-time codes which can be used to login if the totp device is lost. Only returned when
+  /// enabling 2fa, and can't be retrieved later.
+  pub recovery_codes: Option<Vec<SensitiveString>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Replace your totp recovery codes with new ones. Requires a valid totp token.
+pub struct RegenerateTotpRecoveryCodes {
+  pub totp_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct TotpRecoveryCodesResponse {
+  pub recovery_codes: Vec<SensitiveString>,
 }
 
 #[skip_serializing_none]
@@ -441,3 +532,90 @@ pub struct ListMedia {
 pub struct ListMediaResponse {
   pub images: Vec<LocalImageView>,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create an api token for bots and integrations.
+pub struct CreateApiToken {
+  /// A name to tell your tokens apart.
+  pub name: String,
+  /// The actions which can be performed with this token.
+  pub scopes: Vec<ApiTokenScope>,
+  /// The time when the token stops working, as a unix timestamp. Never expires if not given.
+  pub expires: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct CreateApiTokenResponse {
+  pub api_token: ApiToken,
+  /// The token to use in the auth header. It is only shown once and can't be retrieved later.
+  pub token: SensitiveString,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListApiTokensResponse {
+  pub api_tokens: Vec<ApiToken>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Revoke one of your api tokens.
+pub struct RevokeApiToken {
+  pub id: ApiTokenId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Log out a single session, as returned by list_logins.
+pub struct RevokeLogin {
+  pub id: LoginTokenId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Receive Web Push notifications on this device. The values come from the `PushSubscription`
+/// returned by `PushManager.subscribe()`, which needs the key from get_vapid_public_key.
+pub struct RegisterPushSubscription {
+  pub endpoint: String,
+  /// The `p256dh` key, base64url encoded.
+  pub p256dh: String,
+  /// The `auth` secret, base64url encoded.
+  pub auth: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Stop sending Web Push notifications to a device.
+pub struct DeletePushSubscription {
+  pub endpoint: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct GetVapidPublicKeyResponse {
+  /// Use this as `applicationServerKey` when subscribing to push notifications.
+  pub vapid_public_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The JSON payload of a Web Push notification, as received by the service worker.
+pub struct PushNotification {
+  pub title: String,
+  pub body: String,
+  /// Page to open when the notification is clicked.
+  pub url: String,
+}
@@ -0,0 +1,536 @@
+use crate::{context::LemmyContext, person::PushNotification, request::check_url_is_global};
+use base64::{
+  engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+  Engine,
+};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, LocalUserId},
+  source::{push_subscription::PushSubscription, secret::Secret},
+  utils::DbPool,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_db_views_actor::structs::CommunityModeratorView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  spawn_try_task,
+};
+use reqwest::StatusCode;
+use ring::{
+  aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_128_GCM},
+  agreement::{agree_ephemeral, EphemeralPrivateKey, UnparsedPublicKey, ECDH_P256},
+  error::Unspecified,
+  hkdf,
+  rand::{SecureRandom, SystemRandom},
+  signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+};
+use serde::Serialize;
+use tracing::warn;
+use url::Url;
+
+/// How long push services keep a notification if the device is offline
+const PUSH_TTL: Duration = Duration::days(1);
+/// Size of the single encrypted record, the payload needs to fit into it
+const RECORD_SIZE: u32 = 4096;
+/// Push services reject larger request bodies, including the header of the content encoding
+const MAX_ENCRYPTED_LENGTH: usize = 4096;
+/// Header with salt, record size and 65 byte key id, then the padding delimiter and 16 byte tag
+const ENCRYPTION_OVERHEAD: usize = 16 + 4 + 1 + 65 + 1 + 16;
+/// Notification bodies are cut to this many bytes, and further if the payload is still too large
+const MAX_BODY_LENGTH: usize = 1000;
+
+#[derive(Serialize)]
+struct VapidClaims {
+  aud: String,
+  exp: i64,
+  sub: String,
+}
+
+/// Generate the VAPID keys for Web Push, if they don't exist yet.
+pub async fn init_vapid_keys(secret: Secret, pool: &mut DbPool<'_>) -> LemmyResult<Secret> {
+  if secret.vapid_private_key.is_some() && secret.vapid_public_key.is_some() {
+    return Ok(secret);
+  }
+  let (private_key, public_key) = generate_vapid_keys()?;
+  Ok(Secret::update_vapid_keys(pool, &private_key, &public_key).await?)
+}
+
+/// Returns the private key as base64 encoded PKCS#8, and the public key as base64url.
+fn generate_vapid_keys() -> LemmyResult<(String, String)> {
+  let rng = SystemRandom::new();
+  let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)?;
+  let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)?;
+  Ok((
+    STANDARD.encode(pkcs8.as_ref()),
+    URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+  ))
+}
+
+/// Check that the keys of a subscription can be used for encryption, and that the endpoint is a
+/// public https url, so that subscriptions can't be used to reach internal services.
+pub async fn validate_push_subscription(
+  endpoint: &str,
+  p256dh: &str,
+  auth: &str,
+) -> LemmyResult<()> {
+  let endpoint = Url::parse(endpoint).map_err(|_| LemmyErrorType::InvalidPushSubscription)?;
+  let p256dh = decode_key(p256dh)?;
+  let auth = decode_key(auth)?;
+  // Uncompressed P-256 point and 16 byte secret, see RFC 8291
+  let valid_keys = p256dh.len() == 65 && p256dh.first() == Some(&4) && auth.len() == 16;
+  if endpoint.scheme() != "https" || !valid_keys {
+    Err(LemmyErrorType::InvalidPushSubscription)?
+  }
+  check_url_is_global(&endpoint)
+    .await
+    .map_err(|_| LemmyErrorType::InvalidPushSubscription)?;
+  Ok(())
+}
+
+/// Notify the local moderators of a community about a new post or comment report.
+pub async fn send_report_push_notifications(
+  community_id: CommunityId,
+  reporter_name: &str,
+  reason: &str,
+  context: &LemmyContext,
+) {
+  let moderators =
+    match CommunityModeratorView::for_community(&mut context.pool(), community_id).await {
+      Ok(m) => m,
+      Err(e) => {
+        warn!("Failed to read community moderators: {e}");
+        return;
+      }
+    };
+  let notification = PushNotification {
+    title: format!("New report from {reporter_name}"),
+    body: reason.to_string(),
+    url: format!("{}/reports", context.settings().get_protocol_and_hostname()),
+  };
+  for moderator in moderators.iter().filter(|m| m.moderator.local) {
+    if let Ok(Some(local_user_view)) =
+      LocalUserView::read_person(&mut context.pool(), moderator.moderator.id).await
+    {
+      send_push_notification(local_user_view.local_user.id, &notification, context).await;
+    }
+  }
+}
+
+/// Push a notification to all devices of the user. The requests are sent in the background.
+pub async fn send_push_notification(
+  local_user_id: LocalUserId,
+  notification: &PushNotification,
+  context: &LemmyContext,
+) {
+  if context.secret().vapid_private_key.is_none() {
+    return;
+  }
+  let subscriptions =
+    match PushSubscription::list_for_local_user(&mut context.pool(), local_user_id).await {
+      Ok(s) => s,
+      Err(e) => {
+        warn!("Failed to read push subscriptions: {e}");
+        return;
+      }
+    };
+  if subscriptions.is_empty() {
+    return;
+  }
+  let payload = match push_payload(notification) {
+    Ok(p) => p,
+    Err(e) => {
+      warn!("Failed to serialize push notification: {e}");
+      return;
+    }
+  };
+  for subscription in subscriptions {
+    let context = context.clone();
+    let payload = payload.clone();
+    spawn_try_task(async move { deliver(&subscription, &payload, &context).await });
+  }
+}
+
+/// Serialize the notification, with the body cut so that the encrypted payload fits into the
+/// limit of push services. Escaping in JSON can make the body longer than its text.
+fn push_payload(notification: &PushNotification) -> LemmyResult<Vec<u8>> {
+  let mut body_length = MAX_BODY_LENGTH;
+  loop {
+    let payload = serde_json::to_vec(&PushNotification {
+      body: truncate_at_char_boundary(&notification.body, body_length).to_string(),
+      ..notification.clone()
+    })?;
+    let overflow = (payload.len() + ENCRYPTION_OVERHEAD).saturating_sub(MAX_ENCRYPTED_LENGTH);
+    if overflow == 0 || body_length == 0 {
+      return Ok(payload);
+    }
+    // A byte of the body takes up to 6 bytes in JSON, eg `\u0001`
+    body_length = body_length.saturating_sub(overflow.div_ceil(6));
+  }
+}
+
+/// The longest prefix with at most `max_bytes` bytes which doesn't split a character.
+fn truncate_at_char_boundary(text: &str, max_bytes: usize) -> &str {
+  let end = text
+    .char_indices()
+    .map(|(i, c)| i + c.len_utf8())
+    .take_while(|end| *end <= max_bytes)
+    .last()
+    .unwrap_or(0);
+  text.get(..end).unwrap_or_default()
+}
+
+async fn deliver(
+  subscription: &PushSubscription,
+  payload: &[u8],
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let secret = context.secret();
+  let (Some(private_key), Some(public_key)) = (&secret.vapid_private_key, &secret.vapid_public_key)
+  else {
+    return Ok(());
+  };
+  // The domain may resolve to a different address than during registration
+  check_url_is_global(&Url::parse(&subscription.endpoint)?).await?;
+  let body = encrypt(payload, &subscription.p256dh, &subscription.auth)?;
+  let authorization = vapid_authorization(
+    &subscription.endpoint,
+    private_key,
+    public_key,
+    &context.settings().get_protocol_and_hostname(),
+  )?;
+
+  let res = context
+    .client()
+    .post(&subscription.endpoint)
+    .header("TTL", PUSH_TTL.num_seconds().to_string())
+    .header("Content-Encoding", "aes128gcm")
+    .header("Content-Type", "application/octet-stream")
+    .header("Authorization", authorization)
+    .body(body)
+    .send()
+    .await?;
+  match res.status() {
+    // The subscription expired or was cancelled by the user
+    StatusCode::NOT_FOUND | StatusCode::GONE => {
+      PushSubscription::delete(&mut context.pool(), subscription.id).await?;
+      Ok(())
+    }
+    status if !status.is_success() => Err(anyhow::anyhow!(
+      "Push service {} returned status {status}",
+      subscription.endpoint
+    ))?,
+    _ => Ok(()),
+  }
+}
+
+/// Build the `Authorization` header which identifies this instance to the push service, see
+/// RFC 8292.
+fn vapid_authorization(
+  endpoint: &str,
+  private_key: &str,
+  public_key: &str,
+  protocol_and_hostname: &str,
+) -> LemmyResult<String> {
+  let endpoint = Url::parse(endpoint)?;
+  let claims = VapidClaims {
+    aud: endpoint.origin().ascii_serialization(),
+    exp: (Utc::now() + Duration::hours(12)).timestamp(),
+    sub: protocol_and_hostname.to_string(),
+  };
+  let key = EncodingKey::from_ec_der(&STANDARD.decode(private_key)?);
+  let token = encode(&Header::new(Algorithm::ES256), &claims, &key)?;
+  Ok(format!("vapid t={token}, k={public_key}"))
+}
+
+/// Encrypt the payload for a subscription with the `aes128gcm` content encoding, see RFC 8291.
+fn encrypt(payload: &[u8], p256dh: &str, auth: &str) -> LemmyResult<Vec<u8>> {
+  let ua_public = decode_key(p256dh)?;
+  let auth_secret = decode_key(auth)?;
+  let rng = SystemRandom::new();
+  let as_private = EphemeralPrivateKey::generate(&ECDH_P256, &rng)?;
+  let as_public = as_private.compute_public_key()?;
+  let mut salt = [0; 16];
+  rng.fill(&mut salt)?;
+
+  let ikm = agree_ephemeral(
+    as_private,
+    &UnparsedPublicKey::new(&ECDH_P256, &ua_public),
+    |ecdh_secret| {
+      hkdf_sha256(
+        &auth_secret,
+        ecdh_secret,
+        &[b"WebPush: info\0", &ua_public, as_public.as_ref()],
+        32,
+      )
+    },
+  )??;
+  let (cek, nonce) = content_encryption_key(&salt, &ikm)?;
+
+  // A single record, so the padding delimiter is 2
+  let mut record = payload.to_vec();
+  record.push(2);
+  let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &cek)?);
+  key.seal_in_place_append_tag(
+    Nonce::try_assume_unique_for_key(&nonce)?,
+    Aad::empty(),
+    &mut record,
+  )?;
+  if record.len() > RECORD_SIZE as usize {
+    Err(anyhow::anyhow!("Push notification is too large"))?
+  }
+
+  let key_id = as_public.as_ref();
+  let mut body = Vec::with_capacity(salt.len() + 5 + key_id.len() + record.len());
+  body.extend_from_slice(&salt);
+  body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+  body.push(u8::try_from(key_id.len())?);
+  body.extend_from_slice(key_id);
+  body.extend_from_slice(&record);
+  if body.len() > MAX_ENCRYPTED_LENGTH {
+    Err(anyhow::anyhow!("Push notification is too large"))?
+  }
+  Ok(body)
+}
+
+/// Derive the key and nonce for the content encryption from the salt and shared secret.
+fn content_encryption_key(salt: &[u8], ikm: &[u8]) -> Result<(Vec<u8>, Vec<u8>), Unspecified> {
+  let cek = hkdf_sha256(salt, ikm, &[b"Content-Encoding: aes128gcm\0"], 16)?;
+  let nonce = hkdf_sha256(salt, ikm, &[b"Content-Encoding: nonce\0"], 12)?;
+  Ok((cek, nonce))
+}
+
+struct HkdfLength(usize);
+
+impl hkdf::KeyType for HkdfLength {
+  fn len(&self) -> usize {
+    self.0
+  }
+}
+
+fn hkdf_sha256(
+  salt: &[u8],
+  ikm: &[u8],
+  info: &[&[u8]],
+  length: usize,
+) -> Result<Vec<u8>, Unspecified> {
+  let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(ikm);
+  let mut out = vec![0; length];
+  prk.expand(info, HkdfLength(length))?.fill(&mut out)?;
+  Ok(out)
+}
+
+/// Browsers encode the keys as base64url, with or without padding.
+fn decode_key(key: &str) -> LemmyResult<Vec<u8>> {
+  Ok(
+    URL_SAFE_NO_PAD
+      .decode(key.trim_end_matches('='))
+      .map_err(|_| LemmyErrorType::InvalidPushSubscription)?,
+  )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use super::*;
+  use crate::request::client_builder;
+  use lemmy_db_schema::utils::build_db_pool_for_tests;
+  use lemmy_utils::{rate_limit::RateLimitCell, settings::SETTINGS};
+  use pretty_assertions::assert_eq;
+  use reqwest_middleware::ClientBuilder;
+  use serial_test::serial;
+  use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    sync::oneshot,
+  };
+
+  /// A push service which accepts a single request, and returns its headers and body.
+  async fn mock_push_service() -> LemmyResult<(String, oneshot::Receiver<(String, Vec<u8>)>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}/push/device1", listener.local_addr()?);
+    let (sender, receiver) = oneshot::channel();
+    tokio::spawn(async move {
+      let (mut stream, _) = listener.accept().await.unwrap();
+      let mut request = Vec::new();
+      let mut buf = [0; 4096];
+      let (headers, body_start, content_length) = loop {
+        let n = stream.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..n]);
+        if let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+          let headers = String::from_utf8_lossy(&request[..end]).to_lowercase();
+          let content_length = headers
+            .lines()
+            .find_map(|l| l.strip_prefix("content-length: "))
+            .map(|l| l.trim().parse::<usize>().unwrap())
+            .unwrap_or(0);
+          break (headers, end + 4, content_length);
+        }
+      };
+      while request.len() < body_start + content_length {
+        let n = stream.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..n]);
+      }
+      stream
+        .write_all(b"HTTP/1.1 201 Created\r\ncontent-length: 0\r\n\r\n")
+        .await
+        .unwrap();
+      sender
+        .send((headers, request[body_start..].to_vec()))
+        .unwrap();
+    });
+    Ok((endpoint, receiver))
+  }
+
+  /// Decrypt the request body like a browser does.
+  fn decrypt(
+    body: &[u8],
+    ua_private: EphemeralPrivateKey,
+    ua_public: &[u8],
+    auth: &[u8],
+  ) -> Vec<u8> {
+    let salt = &body[..16];
+    assert_eq!(RECORD_SIZE.to_be_bytes(), body[16..20]);
+    let key_id_length = usize::from(body[20]);
+    let as_public = &body[21..21 + key_id_length];
+    let ikm = agree_ephemeral(
+      ua_private,
+      &UnparsedPublicKey::new(&ECDH_P256, as_public),
+      |ecdh_secret| {
+        hkdf_sha256(
+          auth,
+          ecdh_secret,
+          &[b"WebPush: info\0", ua_public, as_public],
+          32,
+        )
+      },
+    )
+    .unwrap()
+    .unwrap();
+    let (cek, nonce) = content_encryption_key(salt, &ikm).unwrap();
+    let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &cek).unwrap());
+    let mut record = body[21 + key_id_length..].to_vec();
+    let plaintext = key
+      .open_in_place(
+        Nonce::try_assume_unique_for_key(&nonce).unwrap(),
+        Aad::empty(),
+        &mut record,
+      )
+      .unwrap();
+    assert_eq!(Some(&2), plaintext.last());
+    plaintext[..plaintext.len() - 1].to_vec()
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_deliver_push_notification() -> LemmyResult<()> {
+    let (private_key, public_key) = generate_vapid_keys()?;
+    let secret = Secret {
+      id: 0,
+      jwt_secret: String::new().into(),
+      vapid_private_key: Some(private_key.into()),
+      vapid_public_key: Some(public_key.clone()),
+    };
+    let client = ClientBuilder::new(client_builder(&SETTINGS).build()?).build();
+    let context = LemmyContext::create(
+      build_db_pool_for_tests().await,
+      client,
+      secret,
+      RateLimitCell::with_test_config(),
+    );
+
+    // Keys of the browser
+    let rng = SystemRandom::new();
+    let ua_private = EphemeralPrivateKey::generate(&ECDH_P256, &rng)?;
+    let ua_public = ua_private.compute_public_key()?;
+    let mut auth = [0; 16];
+    rng.fill(&mut auth)?;
+
+    let (endpoint, request) = mock_push_service().await?;
+    let p256dh = URL_SAFE_NO_PAD.encode(ua_public.as_ref());
+    let auth_encoded = URL_SAFE_NO_PAD.encode(auth);
+    let subscription = PushSubscription {
+      id: 0,
+      local_user_id: LocalUserId(0),
+      endpoint,
+      p256dh,
+      auth: auth_encoded,
+      published: Utc::now(),
+    };
+    let notification = PushNotification {
+      title: "Reply from bob".to_string(),
+      body: "Hello there".to_string(),
+      url: "https://example.com/comment/1".to_string(),
+    };
+    deliver(&subscription, &serde_json::to_vec(&notification)?, &context).await?;
+
+    let (headers, body) = request.await?;
+    assert!(headers.contains("content-encoding: aes128gcm"));
+    assert!(headers.contains("ttl: 86400"));
+    assert!(headers.contains(&format!(", k={}", public_key.to_lowercase())));
+    assert!(headers.contains("authorization: vapid t="));
+
+    let payload = decrypt(&body, ua_private, ua_public.as_ref(), &auth);
+    let received: PushNotification = serde_json::from_slice(&payload)?;
+    assert_eq!(notification, received);
+    Ok(())
+  }
+
+  #[test]
+  fn test_push_payload() -> LemmyResult<()> {
+    let notification = |body: &str| PushNotification {
+      title: "Reply from bob".to_string(),
+      body: body.to_string(),
+      url: "https://example.com/comment/1".to_string(),
+    };
+
+    // Multibyte characters are cut at the byte limit, without splitting them
+    assert_eq!("ab", truncate_at_char_boundary("abäc", 3));
+    assert_eq!("abä", truncate_at_char_boundary("abäc", 4));
+    let payload = push_payload(&notification(&"ä".repeat(MAX_BODY_LENGTH)))?;
+    let received: PushNotification = serde_json::from_slice(&payload)?;
+    assert_eq!("ä".repeat(MAX_BODY_LENGTH / 2), received.body);
+
+    // Characters which are escaped in JSON take more space, the body is cut further for them
+    let payload = push_payload(&notification(&"\u{1}".repeat(MAX_BODY_LENGTH)))?;
+    assert!(payload.len() + ENCRYPTION_OVERHEAD <= MAX_ENCRYPTED_LENGTH);
+    let received: PushNotification = serde_json::from_slice(&payload)?;
+    assert!(!received.body.is_empty());
+
+    // The whole encrypted body fits into the limit
+    let rng = SystemRandom::new();
+    let ua_private = EphemeralPrivateKey::generate(&ECDH_P256, &rng)?;
+    let p256dh = URL_SAFE_NO_PAD.encode(ua_private.compute_public_key()?.as_ref());
+    let auth = URL_SAFE_NO_PAD.encode([1; 16]);
+    let body = encrypt(&payload, &p256dh, &auth)?;
+    assert_eq!(payload.len() + ENCRYPTION_OVERHEAD, body.len());
+    assert!(encrypt(&[b'a'; MAX_ENCRYPTED_LENGTH], &p256dh, &auth).is_err());
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_validate_push_subscription() {
+    let p256dh = URL_SAFE_NO_PAD.encode([4; 65]);
+    let auth = URL_SAFE_NO_PAD.encode([1; 16]);
+    let endpoint = "https://push.example.com/abc";
+    assert!(validate_push_subscription(endpoint, &p256dh, &auth)
+      .await
+      .is_ok());
+    assert!(validate_push_subscription("not a url", &p256dh, &auth)
+      .await
+      .is_err());
+    assert!(
+      validate_push_subscription("http://push.example.com/abc", &p256dh, &auth)
+        .await
+        .is_err()
+    );
+    assert!(validate_push_subscription(endpoint, &auth, &auth)
+      .await
+      .is_err());
+    assert!(validate_push_subscription(endpoint, &p256dh, "%%")
+      .await
+      .is_err());
+  }
+}
@@ -50,19 +50,9 @@ pub fn client_builder(settings: &Settings) -> ClientBuilder {
     .redirect(Policy::none())
 }
 
-/// Fetches metadata for the given link and optionally generates thumbnail.
-#[tracing::instrument(skip_all)]
-pub async fn fetch_link_metadata(
-  url: &Url,
-  context: &LemmyContext,
-  recursion: bool,
-) -> LemmyResult<LinkMetadata> {
-  if url.scheme() != "http" && url.scheme() != "https" {
-    return Err(LemmyErrorType::InvalidUrl.into());
-  }
-
-  // Resolve the domain and throw an error if it points to any internal IP,
-  // using logic from nightly IpAddr::is_global.
+/// Resolve the domain and throw an error if it points to any internal IP,
+/// using logic from nightly IpAddr::is_global.
+pub async fn check_url_is_global(url: &Url) -> LemmyResult<()> {
   if !cfg!(debug_assertions) {
     // TODO: Replace with IpAddr::is_global() once stabilized
     //       https://doc.rust-lang.org/std/net/enum.IpAddr.html#method.is_global
@@ -84,6 +74,20 @@ pub async fn fetch_link_metadata(
       return Err(LemmyErrorType::InvalidUrl.into());
     }
   }
+  Ok(())
+}
+
+/// Fetches metadata for the given link and optionally generates thumbnail.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_link_metadata(
+  url: &Url,
+  context: &LemmyContext,
+  recursion: bool,
+) -> LemmyResult<LinkMetadata> {
+  if url.scheme() != "http" && url.scheme() != "https" {
+    return Err(LemmyErrorType::InvalidUrl.into());
+  }
+  check_url_is_global(url).await?;
 
   info!("Fetching site metadata for url: {}", url);
   // We only fetch the first 64kB of data in order to not waste bandwidth especially for large
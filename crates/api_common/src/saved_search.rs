@@ -0,0 +1,80 @@
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PersonId, SavedSearchId, SavedSearchMatchId},
+  source::saved_search::SavedSearch,
+};
+use lemmy_db_views_actor::structs::SavedSearchMatchView;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Save a search, so that new posts and comments which match it are reported as notifications.
+///
+/// The query supports the same operators as the search endpoint. Only content which is created
+/// after saving the search can match.
+pub struct CreateSavedSearch {
+  pub q: String,
+  pub community_id: Option<CommunityId>,
+  pub creator_id: Option<PersonId>,
+  pub post_title_only: Option<bool>,
+  /// Also send an email for new matches.
+  pub send_email: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a saved search.
+pub struct SavedSearchResponse {
+  pub saved_search: SavedSearch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Your saved searches.
+pub struct ListSavedSearchesResponse {
+  pub saved_searches: Vec<SavedSearch>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a saved search, together with its matches.
+pub struct DeleteSavedSearch {
+  pub id: SavedSearchId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get the new posts and comments which matched your saved searches. Most recent first.
+pub struct GetSavedSearchMatches {
+  /// Only the matches of this saved search.
+  pub saved_search_id: Option<SavedSearchId>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub unread_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response of saved search matches.
+pub struct GetSavedSearchMatchesResponse {
+  pub matches: Vec<SavedSearchMatchView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Mark a saved search match as read.
+pub struct MarkSavedSearchMatchAsRead {
+  pub saved_search_match_id: SavedSearchMatchId,
+  pub read: bool,
+}
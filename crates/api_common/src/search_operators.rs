@@ -0,0 +1,127 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+/// Filters which can be written into the search query, like
+/// `author:alice community:rust@lemmy.ml before:2024-01-01 site:github.com "a phrase" -word`.
+/// Dates are in the format `YYYY-MM-DD`, and `before` excludes the given day while `after`
+/// includes it. Anything which isn't a valid operator is kept in the search term.
+#[derive(Debug, Default, PartialEq)]
+pub struct SearchOperators {
+  /// The remaining words and quoted phrases, in the websearch syntax of PostgreSQL.
+  pub search_term: Option<String>,
+  pub author: Option<String>,
+  pub community: Option<String>,
+  pub site: Option<String>,
+  pub after: Option<DateTime<Utc>>,
+  pub before: Option<DateTime<Utc>>,
+  pub exclude_terms: Vec<String>,
+}
+
+impl SearchOperators {
+  pub fn parse(q: &str) -> Self {
+    let mut operators = SearchOperators::default();
+    let mut terms = Vec::new();
+    for token in split_search_query(q) {
+      if let Some(exclude_term) = token.strip_prefix('-').filter(|t| !t.is_empty()) {
+        operators.exclude_terms.push(exclude_term.to_string());
+        continue;
+      }
+      match token.split_once(':') {
+        Some(("author", name)) if !name.is_empty() => {
+          operators.author = Some(name.trim_start_matches('@').to_string())
+        }
+        Some(("community", name)) if !name.is_empty() => {
+          operators.community = Some(name.trim_start_matches('!').to_string())
+        }
+        Some(("site", domain)) if !domain.is_empty() => {
+          operators.site = Some(domain.to_lowercase())
+        }
+        Some(("after", date)) if parse_date(date).is_some() => operators.after = parse_date(date),
+        Some(("before", date)) if parse_date(date).is_some() => operators.before = parse_date(date),
+        _ => terms.push(token),
+      }
+    }
+    operators.search_term = (!terms.is_empty()).then(|| terms.join(" "));
+    operators
+  }
+
+  /// Whether any of the operators which only apply to posts and comments is used.
+  pub fn filters_content(&self) -> bool {
+    self.site.is_some()
+      || self.after.is_some()
+      || self.before.is_some()
+      || !self.exclude_terms.is_empty()
+  }
+}
+
+/// Splits the query at whitespace, except inside of quotes. The quotes are kept, so that phrases
+/// are still matched as a whole.
+fn split_search_query(q: &str) -> Vec<String> {
+  let mut tokens = Vec::new();
+  let mut token = String::new();
+  let mut in_quotes = false;
+  for c in q.chars() {
+    if c.is_whitespace() && !in_quotes {
+      if !token.is_empty() {
+        tokens.push(std::mem::take(&mut token));
+      }
+    } else {
+      if c == '"' {
+        in_quotes = !in_quotes;
+      }
+      token.push(c);
+    }
+  }
+  if !token.is_empty() {
+    tokens.push(token);
+  }
+  tokens
+}
+
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+  let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+  Some(date.and_time(NaiveTime::MIN).and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::search_operators::{parse_date, SearchOperators};
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_parse_search_operators() {
+    let operators = SearchOperators::parse(
+      r#"author:alice community:rust@lemmy.ml before:2024-01-01 site:GitHub.com "a b" -c"#,
+    );
+    assert_eq!(
+      SearchOperators {
+        search_term: Some(r#""a b""#.to_string()),
+        author: Some("alice".to_string()),
+        community: Some("rust@lemmy.ml".to_string()),
+        site: Some("github.com".to_string()),
+        after: None,
+        before: parse_date("2024-01-01"),
+        exclude_terms: vec!["c".to_string()],
+      },
+      operators
+    );
+    assert!(operators.filters_content());
+  }
+
+  #[test]
+  fn test_parse_search_without_operators() {
+    // Invalid operators and other colons stay in the search term
+    let operators = SearchOperators::parse(
+      r#"  rust   after:yesterday https://example.com -"some phrase" - unknown:x"#,
+    );
+    assert_eq!(
+      SearchOperators {
+        search_term: Some("rust after:yesterday https://example.com - unknown:x".to_string()),
+        exclude_terms: vec![r#""some phrase""#.to_string()],
+        ..Default::default()
+      },
+      operators
+    );
+
+    assert_eq!(SearchOperators::default(), SearchOperators::parse(" "));
+  }
+}
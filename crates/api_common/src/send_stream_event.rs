@@ -0,0 +1,359 @@
+use crate::event_stream::StreamEvent;
+use actix_web::web::Bytes;
+use futures::{stream, Stream};
+use lemmy_db_schema::newtypes::{CommunityId, InstanceId, PersonId, PostId};
+use std::{collections::HashSet, convert::Infallible, sync::LazyLock, time::Duration};
+use tokio::{
+  sync::broadcast::{self, error::RecvError},
+  time::timeout,
+};
+
+/// Number of events which are buffered for slow clients. If a client falls further behind, it
+/// skips the missed events.
+const EVENT_STREAM_CAPACITY: usize = 1024;
+/// Send a comment if nothing else happens, so that proxies don't close idle connections.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// All events are sent through this channel, and each open stream filters the ones for its user.
+/// Note that the channel only exists inside the current process, so with multiple Lemmy processes
+/// a client only receives the events which were handled by the same process.
+static EVENT_STREAM: LazyLock<broadcast::Sender<StreamEvent>> =
+  LazyLock::new(|| broadcast::channel(EVENT_STREAM_CAPACITY).0);
+
+/// Pass an event to all open streams. The event is built lazily so that nothing is cloned if no
+/// client is connected.
+pub fn send_stream_event(event: impl FnOnce() -> StreamEvent) {
+  if EVENT_STREAM.receiver_count() > 0 {
+    // Only fails if all clients disconnected in the meantime
+    EVENT_STREAM.send(event()).ok();
+  }
+}
+
+/// Decides which events are sent to a client. The follows and blocks are read when the stream is
+/// opened, and aren't updated while it stays open. Clients need to reconnect after changing them.
+#[derive(Debug, Clone, Default)]
+pub struct EventStreamFilter {
+  pub person_id: PersonId,
+  pub posts: bool,
+  pub comments: bool,
+  pub post_id: Option<PostId>,
+  /// Communities followed by the user when the stream was opened, without those on blocked
+  /// instances.
+  pub followed_communities: HashSet<CommunityId>,
+  /// Persons blocked by the user when the stream was opened.
+  pub blocked_persons: HashSet<PersonId>,
+  /// Communities blocked by the user when the stream was opened.
+  pub blocked_communities: HashSet<CommunityId>,
+  /// Instances blocked by the user when the stream was opened. Private messages from their users
+  /// are not sent.
+  pub blocked_instances: HashSet<InstanceId>,
+}
+
+impl EventStreamFilter {
+  fn matches(&self, event: &StreamEvent) -> bool {
+    if self.is_blocked(event) {
+      return false;
+    }
+    match event {
+      StreamEvent::CommentReply { comment_reply, .. } => {
+        comment_reply.recipient_id == self.person_id
+      }
+      StreamEvent::PersonMention { person_mention, .. } => {
+        person_mention.recipient_id == self.person_id
+      }
+      StreamEvent::PrivateMessage {
+        private_message_view,
+      } => private_message_view.recipient.id == self.person_id,
+      StreamEvent::Post { post } => {
+        self.posts && self.followed_communities.contains(&post.community_id)
+      }
+      StreamEvent::Comment {
+        comment,
+        community_id,
+      } => {
+        self.post_id == Some(comment.post_id)
+          || (self.comments && self.followed_communities.contains(community_id))
+      }
+    }
+  }
+
+  /// Events from blocked persons and communities are never sent.
+  fn is_blocked(&self, event: &StreamEvent) -> bool {
+    if let StreamEvent::PrivateMessage {
+      private_message_view,
+    } = event
+    {
+      if self
+        .blocked_instances
+        .contains(&private_message_view.creator.instance_id)
+      {
+        return true;
+      }
+    }
+    let (creator_id, community_id) = match event {
+      StreamEvent::CommentReply { comment, post, .. }
+      | StreamEvent::PersonMention { comment, post, .. } => {
+        (comment.creator_id, Some(post.community_id))
+      }
+      StreamEvent::PrivateMessage {
+        private_message_view,
+      } => (private_message_view.creator.id, None),
+      StreamEvent::Post { post } => (post.creator_id, Some(post.community_id)),
+      StreamEvent::Comment {
+        comment,
+        community_id,
+      } => (comment.creator_id, Some(*community_id)),
+    };
+    self.blocked_persons.contains(&creator_id)
+      || community_id.is_some_and(|c| self.blocked_communities.contains(&c))
+  }
+}
+
+impl StreamEvent {
+  fn name(&self) -> &'static str {
+    match self {
+      StreamEvent::CommentReply { .. } => "CommentReply",
+      StreamEvent::PersonMention { .. } => "PersonMention",
+      StreamEvent::PrivateMessage { .. } => "PrivateMessage",
+      StreamEvent::Post { .. } => "Post",
+      StreamEvent::Comment { .. } => "Comment",
+    }
+  }
+}
+
+/// Returns the body of a `text/event-stream` response, which runs until the client disconnects.
+pub fn event_stream(filter: EventStreamFilter) -> impl Stream<Item = Result<Bytes, Infallible>> {
+  let receiver = EVENT_STREAM.subscribe();
+  stream::unfold((receiver, filter), |(mut receiver, filter)| async move {
+    loop {
+      let message = match timeout(KEEP_ALIVE_INTERVAL, receiver.recv()).await {
+        Err(_) => ": keep-alive\n\n".to_string(),
+        Ok(Ok(event)) if filter.matches(&event) => match serde_json::to_string(&event) {
+          Ok(data) => format!("event: {}\ndata: {data}\n\n", event.name()),
+          Err(_) => continue,
+        },
+        Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => continue,
+        Ok(Err(RecvError::Closed)) => return None,
+      };
+      return Some((Ok(Bytes::from(message)), (receiver, filter)));
+    }
+  })
+}
+
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use diesel_ltree::Ltree;
+  use lemmy_db_schema::{
+    newtypes::{CommentId, DbUrl, LanguageId},
+    source::{
+      comment::Comment,
+      comment_reply::CommentReply,
+      person::Person,
+      post::Post,
+      private_message::PrivateMessage,
+    },
+  };
+  use lemmy_db_views::structs::PrivateMessageView;
+  use url::Url;
+
+  const ME: PersonId = PersonId(1);
+  const OTHER: PersonId = PersonId(2);
+  const FOLLOWED: CommunityId = CommunityId(1);
+  const UNFOLLOWED: CommunityId = CommunityId(2);
+
+  fn ap_id() -> DbUrl {
+    Url::parse("https://example.com/x")
+      .expect("parse url")
+      .into()
+  }
+
+  fn post(creator_id: PersonId, community_id: CommunityId) -> Post {
+    Post {
+      id: PostId(1),
+      name: "post".to_string(),
+      url: None,
+      body: None,
+      creator_id,
+      community_id,
+      removed: false,
+      locked: false,
+      published: Default::default(),
+      updated: None,
+      deleted: false,
+      nsfw: false,
+      embed_title: None,
+      embed_description: None,
+      thumbnail_url: None,
+      ap_id: ap_id(),
+      local: true,
+      embed_video_url: None,
+      language_id: LanguageId(0),
+      featured_community: false,
+      featured_local: false,
+      url_content_type: None,
+      alt_text: None,
+    }
+  }
+
+  fn comment(creator_id: PersonId, post_id: PostId) -> Comment {
+    Comment {
+      id: CommentId(1),
+      creator_id,
+      post_id,
+      content: "comment".to_string(),
+      removed: false,
+      published: Default::default(),
+      updated: None,
+      deleted: false,
+      ap_id: ap_id(),
+      local: true,
+      path: Ltree("0.1".to_string()),
+      distinguished: false,
+      language_id: LanguageId(0),
+    }
+  }
+
+  fn reply(creator_id: PersonId, recipient_id: PersonId, community_id: CommunityId) -> StreamEvent {
+    StreamEvent::CommentReply {
+      comment_reply: CommentReply {
+        id: Default::default(),
+        recipient_id,
+        comment_id: CommentId(1),
+        read: false,
+        published: Default::default(),
+      },
+      comment: comment(creator_id, PostId(1)),
+      post: post(recipient_id, community_id),
+    }
+  }
+
+  fn person(id: PersonId, instance_id: InstanceId) -> Person {
+    Person {
+      id,
+      name: "person".to_string(),
+      display_name: None,
+      avatar: None,
+      banned: false,
+      published: Default::default(),
+      updated: None,
+      actor_id: ap_id(),
+      bio: None,
+      local: true,
+      private_key: None,
+      public_key: String::new(),
+      last_refreshed_at: Default::default(),
+      banner: None,
+      deleted: false,
+      inbox_url: ap_id(),
+      shared_inbox_url: None,
+      matrix_user_id: None,
+      bot_account: false,
+      ban_expires: None,
+      instance_id,
+    }
+  }
+
+  fn private_message(creator: Person, recipient_id: PersonId) -> StreamEvent {
+    StreamEvent::PrivateMessage {
+      private_message_view: Box::new(PrivateMessageView {
+        private_message: PrivateMessage {
+          id: Default::default(),
+          creator_id: creator.id,
+          recipient_id,
+          content: "message".to_string(),
+          deleted: false,
+          read: false,
+          published: Default::default(),
+          updated: None,
+          ap_id: ap_id(),
+          local: true,
+        },
+        creator,
+        recipient: person(recipient_id, InstanceId(1)),
+      }),
+    }
+  }
+
+  fn filter() -> EventStreamFilter {
+    EventStreamFilter {
+      person_id: ME,
+      followed_communities: HashSet::from([FOLLOWED]),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_recipient() {
+    let filter = filter();
+    assert!(filter.matches(&reply(OTHER, ME, UNFOLLOWED)));
+    assert!(!filter.matches(&reply(ME, OTHER, FOLLOWED)));
+    let other_person = person(OTHER, InstanceId(2));
+    assert!(filter.matches(&private_message(other_person.clone(), ME)));
+    assert!(!filter.matches(&private_message(other_person, PersonId(3))));
+  }
+
+  #[test]
+  fn test_community() {
+    let new_post = |community_id| StreamEvent::Post {
+      post: post(OTHER, community_id),
+    };
+    let new_comment = |community_id, post_id| StreamEvent::Comment {
+      comment: comment(OTHER, post_id),
+      community_id,
+    };
+
+    // Posts and comments are only sent if requested
+    let filter = filter();
+    assert!(!filter.matches(&new_post(FOLLOWED)));
+    assert!(!filter.matches(&new_comment(FOLLOWED, PostId(1))));
+
+    // And only from followed communities
+    let filter = EventStreamFilter {
+      posts: true,
+      comments: true,
+      ..self::filter()
+    };
+    assert!(filter.matches(&new_post(FOLLOWED)));
+    assert!(!filter.matches(&new_post(UNFOLLOWED)));
+    assert!(filter.matches(&new_comment(FOLLOWED, PostId(1))));
+    assert!(!filter.matches(&new_comment(UNFOLLOWED, PostId(1))));
+
+    // Comments of a single post are sent regardless of the community
+    let filter = EventStreamFilter {
+      post_id: Some(PostId(2)),
+      ..self::filter()
+    };
+    assert!(filter.matches(&new_comment(UNFOLLOWED, PostId(2))));
+    assert!(!filter.matches(&new_comment(UNFOLLOWED, PostId(1))));
+  }
+
+  #[test]
+  fn test_blocks() {
+    let filter = EventStreamFilter {
+      posts: true,
+      blocked_persons: HashSet::from([OTHER]),
+      blocked_communities: HashSet::from([UNFOLLOWED]),
+      blocked_instances: HashSet::from([InstanceId(2)]),
+      ..self::filter()
+    };
+
+    // Blocked persons
+    assert!(!filter.matches(&reply(OTHER, ME, FOLLOWED)));
+    assert!(!filter.matches(&StreamEvent::Post {
+      post: post(OTHER, FOLLOWED)
+    }));
+    assert!(!filter.matches(&private_message(person(OTHER, InstanceId(1)), ME)));
+
+    // Blocked communities, even for notifications
+    let third = PersonId(3);
+    assert!(filter.matches(&reply(third, ME, FOLLOWED)));
+    assert!(!filter.matches(&reply(third, ME, UNFOLLOWED)));
+
+    // Private messages from users of blocked instances
+    assert!(filter.matches(&private_message(person(third, InstanceId(1)), ME)));
+    assert!(!filter.matches(&private_message(person(third, InstanceId(2)), ME)));
+  }
+}
@@ -0,0 +1,202 @@
+use crate::{
+  context::LemmyContext,
+  request::check_url_is_global,
+  utils::{check_community_mod_action, is_admin},
+};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    comment::Comment,
+    community::Community,
+    moderator::{
+      ModAddCommunity,
+      ModBan,
+      ModBanFromCommunity,
+      ModFeaturePost,
+      ModLockPost,
+      ModRemoveComment,
+      ModRemoveCommunity,
+      ModRemovePost,
+    },
+    person::Person,
+    post::Post,
+    webhook::{Webhook, WebhookDelivery, WebhookDeliveryInsertForm},
+  },
+  utils::DbPool,
+  WebhookEventType,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use ring::{
+  hmac,
+  rand::{SecureRandom, SystemRandom},
+};
+use serde::Serialize;
+use tracing::warn;
+use url::Url;
+
+/// Contains the event type, like `PostCreated`.
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Lemmy-Event";
+/// Contains the id of the delivery, which stays the same when it is retried.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-Lemmy-Delivery";
+/// Contains the HMAC-SHA256 of the request body as `sha256=<hex>`, with the webhook secret as key.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Lemmy-Signature";
+
+/// The json body of webhook requests.
+#[derive(Serialize)]
+struct WebhookPayload<'a, T: Serialize> {
+  event: WebhookEventType,
+  /// Hostname of the instance which sends the event.
+  instance: &'a str,
+  community_id: Option<CommunityId>,
+  published: DateTime<Utc>,
+  data: &'a T,
+}
+
+/// The data of a `ModAction` event, with the modlog entry and the affected object.
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum WebhookModAction {
+  RemovePost { entry: ModRemovePost, post: Post },
+  RemoveComment { entry: ModRemoveComment, comment: Comment },
+  LockPost { entry: ModLockPost, post: Post },
+  FeaturePost { entry: ModFeaturePost, post: Post },
+  RemoveCommunity { entry: ModRemoveCommunity, community: Community },
+  BanFromCommunity { entry: ModBanFromCommunity, person: Person },
+  BanFromSite { entry: ModBan, person: Person },
+  AddModerator { entry: ModAddCommunity, person: Person },
+}
+
+/// Queue an event for all webhooks which subscribed to it, that is the site webhooks and those
+/// of the community where it happened. The requests are sent in the background, and errors are
+/// only logged so that they don't affect the action which triggered the event.
+pub async fn send_webhook_event<T: Serialize>(
+  event: WebhookEventType,
+  community_id: Option<CommunityId>,
+  data: &T,
+  context: &LemmyContext,
+) {
+  if let Err(e) = queue_webhook_event(event, community_id, data, context).await {
+    warn!("Failed to queue webhook event {event}: {e}");
+  }
+}
+
+async fn queue_webhook_event<T: Serialize>(
+  event: WebhookEventType,
+  community_id: Option<CommunityId>,
+  data: &T,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let webhooks = Webhook::list_for_event(&mut context.pool(), event, community_id).await?;
+  if webhooks.is_empty() {
+    return Ok(());
+  }
+  let payload = serde_json::to_string(&WebhookPayload {
+    event,
+    instance: &context.settings().hostname,
+    community_id,
+    published: Utc::now(),
+    data,
+  })?;
+  let forms: Vec<_> = webhooks
+    .iter()
+    .map(|w| WebhookDeliveryInsertForm::new(w.id, event, payload.clone()))
+    .collect();
+  WebhookDelivery::create_many(&mut context.pool(), &forms).await?;
+  Ok(())
+}
+
+/// Returns the value of the signature header for a request body.
+pub fn sign_webhook_payload(secret: &str, payload: &str) -> String {
+  let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+  let signature = hmac::sign(&key, payload.as_bytes());
+  format!("sha256={}", to_hex(signature.as_ref()))
+}
+
+/// A random secret for signing the requests of a new webhook.
+pub fn generate_webhook_secret() -> LemmyResult<String> {
+  let mut bytes = [0u8; 32];
+  SystemRandom::new().fill(&mut bytes)?;
+  Ok(to_hex(&bytes))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Webhooks without community are managed by admins, the others by the community moderators.
+pub async fn check_webhook_permission(
+  local_user_view: &LocalUserView,
+  community_id: Option<CommunityId>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  match community_id {
+    Some(community_id) => {
+      check_community_mod_action(&local_user_view.person, community_id, false, pool).await
+    }
+    None => is_admin(local_user_view),
+  }
+}
+
+/// Only allow public http(s) urls, so that webhooks can't be used to reach internal services.
+pub async fn check_webhook_url(url: &str) -> LemmyResult<String> {
+  let url = Url::parse(url).map_err(|_| LemmyErrorType::InvalidWebhookUrl)?;
+  if !matches!(url.scheme(), "https" | "http") {
+    Err(LemmyErrorType::InvalidWebhookUrl)?
+  }
+  check_url_is_global(&url)
+    .await
+    .map_err(|_| LemmyErrorType::InvalidWebhookUrl)?;
+  Ok(url.to_string())
+}
+
+/// Removes duplicate events, and makes sure that at least one is given.
+pub fn check_webhook_events(events: &[WebhookEventType]) -> LemmyResult<Vec<WebhookEventType>> {
+  let mut unique = Vec::with_capacity(events.len());
+  for event in events {
+    if !unique.contains(event) {
+      unique.push(*event);
+    }
+  }
+  if unique.is_empty() {
+    Err(LemmyErrorType::WebhookEventsRequired)?
+  }
+  Ok(unique)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use super::{check_webhook_events, generate_webhook_secret, sign_webhook_payload};
+  use lemmy_db_schema::WebhookEventType;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_sign_webhook_payload() {
+    // Test case 2 from RFC 4231
+    assert_eq!(
+      "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+      sign_webhook_payload("Jefe", "what do ya want for nothing?")
+    );
+  }
+
+  #[test]
+  fn test_generate_webhook_secret() {
+    let secret = generate_webhook_secret().unwrap();
+    assert_eq!(64, secret.len());
+    assert_ne!(secret, generate_webhook_secret().unwrap());
+  }
+
+  #[test]
+  fn test_check_webhook_events() {
+    use WebhookEventType::{ModAction, PostCreated};
+    assert_eq!(
+      vec![ModAction, PostCreated],
+      check_webhook_events(&[ModAction, PostCreated, ModAction]).unwrap()
+    );
+    assert!(check_webhook_events(&[]).is_err());
+  }
+}
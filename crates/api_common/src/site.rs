@@ -11,12 +11,15 @@ use lemmy_db_schema::{
     RegistrationApplicationId,
   },
   source::{
+    custom_emoji::CustomEmoji,
+    email_queue::FailedEmail,
     federation_queue_state::FederationQueueState,
     instance::Instance,
     language::Language,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
     tagline::Tagline,
   },
+  AutocompleteType,
   ListingType,
   ModlogActionType,
   PostListingMode,
@@ -33,11 +36,13 @@ use lemmy_db_views::structs::{
   SiteView,
 };
 use lemmy_db_views_actor::structs::{
+  CommunityAutocompleteView,
   CommunityBlockView,
   CommunityFollowerView,
   CommunityModeratorView,
   CommunityView,
   InstanceBlockView,
+  PersonAutocompleteView,
   PersonBlockView,
   PersonView,
 };
@@ -69,6 +74,9 @@ use ts_rs::TS;
 #[cfg_attr(feature = "full", ts(export))]
 /// Searches the site, given a query string, and some optional filters.
 pub struct Search {
+  /// Besides search terms, this can contain the operators `author:name`, `community:name`,
+  /// `site:domain`, `before:YYYY-MM-DD` and `after:YYYY-MM-DD`. Terms prefixed with `-` are
+  /// excluded, and quoted phrases are matched as a whole.
   pub q: String,
   pub community_id: Option<CommunityId>,
   pub community_name: Option<String>,
@@ -94,6 +102,29 @@ pub struct SearchResponse {
   pub users: Vec<PersonView>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Completes a partially typed `@user`, `!community` or `:emoji:` name. Only the start of names
+/// is matched, which is much cheaper than a full search.
+pub struct Autocomplete {
+  /// The typed name, at least two characters long. A leading `@`, `!` or `:` is ignored.
+  pub q: String,
+  pub type_: AutocompleteType,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The autocomplete response. Only the list for the requested type is filled.
+pub struct AutocompleteResponse {
+  pub users: Vec<PersonAutocompleteView>,
+  pub communities: Vec<CommunityAutocompleteView>,
+  pub emojis: Vec<CustomEmoji>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -193,6 +224,16 @@ pub struct CreateSite {
   pub rate_limit_comment_per_second: Option<i32>,
   pub rate_limit_search: Option<i32>,
   pub rate_limit_search_per_second: Option<i32>,
+  pub rate_limit_vote: Option<i32>,
+  pub rate_limit_vote_per_second: Option<i32>,
+  pub rate_limit_report: Option<i32>,
+  pub rate_limit_report_per_second: Option<i32>,
+  pub rate_limit_follow: Option<i32>,
+  pub rate_limit_follow_per_second: Option<i32>,
+  pub rate_limit_federation_fetch: Option<i32>,
+  pub rate_limit_federation_fetch_per_second: Option<i32>,
+  pub rate_limit_account_factor: Option<i32>,
+  pub rate_limit_trusted_account_factor: Option<i32>,
   pub federation_enabled: Option<bool>,
   pub federation_debug: Option<bool>,
   pub captcha_enabled: Option<bool>,
@@ -266,6 +307,22 @@ pub struct EditSite {
   /// The number of searches allowed in a given time frame.
   pub rate_limit_search: Option<i32>,
   pub rate_limit_search_per_second: Option<i32>,
+  /// The number of post and comment votes allowed in a given time frame.
+  pub rate_limit_vote: Option<i32>,
+  pub rate_limit_vote_per_second: Option<i32>,
+  /// The number of reports allowed in a given time frame.
+  pub rate_limit_report: Option<i32>,
+  pub rate_limit_report_per_second: Option<i32>,
+  /// The number of community follows allowed in a given time frame.
+  pub rate_limit_follow: Option<i32>,
+  pub rate_limit_follow_per_second: Option<i32>,
+  /// The number of remote objects which may be fetched with resolve_object in a given time frame.
+  pub rate_limit_federation_fetch: Option<i32>,
+  pub rate_limit_federation_fetch_per_second: Option<i32>,
+  /// Per-account rate limits, as a multiple of the limits above. 0 disables them.
+  pub rate_limit_account_factor: Option<i32>,
+  /// Per-account rate limits for admins and trusted bots, which are not limited by ip address.
+  pub rate_limit_trusted_account_factor: Option<i32>,
   /// Whether to enable federation.
   pub federation_enabled: Option<bool>,
   /// Enables federation debugging.
@@ -485,6 +542,41 @@ pub struct GetUnreadRegistrationApplicationCountResponse {
   pub registration_applications: i64,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Lists local users with failed login attempts, most recent first.
+pub struct ListFailedLogins {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListFailedLoginsResponse {
+  pub users: Vec<LocalUserView>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Lists emails which couldn't be delivered, including those which are still being retried. Most
+/// recent first.
+pub struct ListFailedEmails {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListFailedEmailsResponse {
+  pub emails: Vec<FailedEmail>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
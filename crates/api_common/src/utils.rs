@@ -1,21 +1,25 @@
 use crate::{
+  claims::{EmailNotificationCategory, UnsubscribeClaims},
   context::LemmyContext,
   request::{
     delete_image_from_pictrs,
     fetch_pictrs_proxied_image_details,
     purge_image_from_pictrs,
   },
+  search_operators::SearchOperators,
   site::{FederatedInstances, InstanceWithFederationState},
 };
 use chrono::{DateTime, Days, Local, TimeZone, Utc};
 use enum_map::{enum_map, EnumMap};
 use lemmy_db_schema::{
   aggregates::structs::{PersonPostAggregates, PersonPostAggregatesForm},
-  newtypes::{CommunityId, DbUrl, InstanceId, PersonId, PostId},
+  newtypes::{CommentId, CommunityId, DbUrl, InstanceId, LocalUserId, PersonId, PostId},
   source::{
     comment::{Comment, CommentUpdateForm},
     community::{Community, CommunityModerator, CommunityUpdateForm},
     community_block::CommunityBlock,
+    email_queue::{EmailQueue, EmailQueueInsertForm},
+    email_template::EmailTemplate,
     email_verification::{EmailVerification, EmailVerificationForm},
     images::{ImageDetails, RemoteImage},
     instance::Instance,
@@ -23,28 +27,45 @@ use lemmy_db_schema::{
     local_site::LocalSite,
     local_site_rate_limit::LocalSiteRateLimit,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
+    local_user::{LocalUser, LocalUserUpdateForm},
+    mod_action_notification::{ModActionNotification, ModActionNotificationInsertForm},
     password_reset_request::PasswordResetRequest,
     person::{Person, PersonUpdateForm},
     person_block::PersonBlock,
     post::{Post, PostRead},
+    saved_search::{
+      SavedSearch,
+      SavedSearchMatch,
+      SavedSearchMatchInsertForm,
+      SavedSearchUpdateForm,
+    },
     site::Site,
   },
   traits::Crud,
   utils::DbPool,
+  CommentSortType,
+  EmailNotificationFrequency,
+  EmailTemplateType,
+  ModActionNotificationType,
+  ReportOutcome,
+  SortType,
 };
 use lemmy_db_views::{
   comment_view::CommentQuery,
+  post_view::PostQuery,
+  private_message_view::PrivateMessageQuery,
   structs::{LocalImageView, LocalUserView},
 };
-use lemmy_db_views_actor::structs::{
-  CommunityModeratorView,
-  CommunityPersonBanView,
-  CommunityView,
+use lemmy_db_views_actor::{
+  comment_reply_view::CommentReplyQuery,
+  person_mention_view::PersonMentionQuery,
+  saved_search_match_view::SavedSearchMatchQuery,
+  structs::{CommunityModeratorView, CommunityPersonBanView, CommunityView},
 };
 use lemmy_utils::{
-  email::{send_email, translations::Lang},
+  email::{render_email_template, translations::Lang},
   error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
-  rate_limit::{ActionType, BucketConfig},
+  rate_limit::{AccountLimitConfig, ActionType, BucketConfig},
   settings::structs::{PictrsImageMode, Settings},
   utils::{
     markdown::{markdown_check_for_blocked_urls, markdown_rewrite_image_links},
@@ -63,6 +84,33 @@ use urlencoding::encode;
 
 pub static AUTH_COOKIE_NAME: &str = "jwt";
 
+/// Maximum number of each notification type which is included in an email digest.
+const DIGEST_FETCH_LIMIT: i64 = 50;
+
+/// Maximum number of posts and comments which are stored as matches of a saved search in one
+/// check. Older matches beyond that are skipped, so that a broad search can't flood the inbox.
+const SAVED_SEARCH_MATCH_LIMIT: i64 = 50;
+
+/// Number of ids behind the last check of a saved search which are checked again. Ids are assigned
+/// before the content is committed, so content with a lower id can become visible after a check.
+const SAVED_SEARCH_RECHECK_MARGIN: i32 = 1000;
+
+/// Built-in email for new matches of a saved search, see [EmailTemplateType::SavedSearchMatch].
+const SAVED_SEARCH_MATCH_SUBJECT: &str = "New matches for your saved search on {hostname}";
+const SAVED_SEARCH_MATCH_BODY: &str = "<h1>New search matches</h1><br>New posts and comments \
+  match your saved search:<br><ul>{content}</ul><a href=\"{link}\">Go to your inbox</a>";
+const SAVED_SEARCH_MATCH_POST_ITEM: &str =
+  "<li>Match for \"{query}\": <a href=\"{link}\">{title}</a></li>";
+const SAVED_SEARCH_MATCH_COMMENT_ITEM: &str =
+  "<li>Match for \"{query}\" <a href=\"{link}\">in a comment</a> on {title}</li>";
+
+/// Built-in email for a locked login, see [EmailTemplateType::LoginLocked].
+const LOGIN_LOCKED_SUBJECT: &str = "Login to your account on {hostname} was locked";
+const LOGIN_LOCKED_BODY: &str = "<h1>Too many failed logins</h1><br>There were too many failed \
+  attempts to log in to your account @{username}@{hostname}, so logging in is blocked until \
+  {content}. If this wasn't you, someone might be trying to guess your password. Make sure that it \
+  is strong and unique, or <a href=\"{link}\">reset your password</a>.";
+
 #[tracing::instrument(skip_all)]
 pub async fn is_mod_or_admin(
   pool: &mut DbPool<'_>,
@@ -419,30 +467,100 @@ pub fn honeypot_check(honeypot: &Option<String>) -> LemmyResult<()> {
 
 pub async fn send_email_to_user(
   local_user_view: &LocalUserView,
+  category: EmailNotificationCategory,
   subject: &str,
   body: &str,
-  settings: &Settings,
+  context: &LemmyContext,
 ) {
-  if local_user_view.person.banned || !local_user_view.local_user.send_notifications_to_email {
+  let local_user = &local_user_view.local_user;
+  if local_user_view.person.banned || !local_user.send_notifications_to_email {
+    return;
+  }
+  // Users with a digest get all notifications in one email from the scheduled task instead
+  if local_user.email_notification_frequency != EmailNotificationFrequency::Instant {
+    return;
+  }
+  if !email_category_enabled(local_user, category) {
     return;
   }
 
-  if let Some(user_email) = &local_user_view.local_user.email {
-    match send_email(
-      subject,
-      user_email,
-      &local_user_view.person.name,
-      body,
-      settings,
-    )
-    .await
-    {
+  if let Some(user_email) = &local_user.email {
+    let result = async {
+      let unsubscribe_link = unsubscribe_link(local_user.id, category, context)?;
+      let body = with_unsubscribe_footer(body, &unsubscribe_link);
+      queue_email(
+        subject,
+        user_email,
+        &local_user_view.person.name,
+        &body,
+        Some(&unsubscribe_link),
+        &mut context.pool(),
+        context.settings(),
+      )
+      .await
+    };
+    match result.await {
       Ok(_o) => _o,
       Err(e) => warn!("{}", e),
     };
   }
 }
 
+/// Check if the user turned off notification emails of this category.
+fn email_category_enabled(local_user: &LocalUser, category: EmailNotificationCategory) -> bool {
+  match category {
+    // Saved search emails are enabled for each search instead
+    EmailNotificationCategory::All | EmailNotificationCategory::SavedSearches => true,
+    EmailNotificationCategory::Replies => local_user.send_reply_emails,
+    EmailNotificationCategory::Mentions => local_user.send_mention_emails,
+    EmailNotificationCategory::PrivateMessages => local_user.send_private_message_emails,
+  }
+}
+
+/// Link which turns off notification emails of this category, without login. It is used for the
+/// footer of the email, which opens a confirmation page, and for one-click unsubscribe by the mail
+/// client, which sends a POST request to it.
+fn unsubscribe_link(
+  local_user_id: LocalUserId,
+  category: EmailNotificationCategory,
+  context: &LemmyContext,
+) -> LemmyResult<String> {
+  let token = UnsubscribeClaims::generate(local_user_id, category, context)?;
+  Ok(format!(
+    "{}/api/v3/user/unsubscribe?token={token}",
+    context.settings().get_protocol_and_hostname()
+  ))
+}
+
+fn with_unsubscribe_footer(body: &str, unsubscribe_link: &str) -> String {
+  format!("{body}<br><br><a href=\"{unsubscribe_link}\">Unsubscribe from these emails</a>")
+}
+
+/// Add an email to the queue, from where it is sent in the background. Failed deliveries are
+/// retried and can be checked by admins.
+pub async fn queue_email(
+  subject: &str,
+  to_email: &str,
+  to_username: &str,
+  html: &str,
+  unsubscribe_link: Option<&str>,
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  if settings.email.is_none() {
+    Err(LemmyErrorType::NoEmailSetup)?
+  }
+  let form = EmailQueueInsertForm::new(
+    to_email.to_string(),
+    to_username.to_string(),
+    subject.to_string(),
+    html.to_string(),
+    unsubscribe_link.map(ToString::to_string),
+  );
+  EmailQueue::create(pool, &form).await?;
+  Ok(())
+}
+
 pub async fn send_password_reset_email(
   user: &LocalUserView,
   pool: &mut DbPool<'_>,
@@ -452,20 +570,462 @@ pub async fn send_password_reset_email(
   let token = uuid::Uuid::new_v4().to_string();
 
   let email = &user.local_user.email.clone().expect("email");
-  let lang = get_interface_language(user);
-  let subject = &lang.password_reset_subject(&user.person.name);
   let protocol_and_hostname = settings.get_protocol_and_hostname();
   let reset_link = format!("{}/password_change/{}", protocol_and_hostname, &token);
-  let body = &lang.password_reset_body(reset_link, &user.person.name);
-  send_email(subject, email, &user.person.name, body, settings).await?;
+  let vars = EmailTemplateVars {
+    username: &user.person.name,
+    link: &reset_link,
+    ..Default::default()
+  };
+  let (subject, body) = build_email(
+    pool,
+    EmailTemplateType::PasswordReset,
+    &user.local_user.interface_language,
+    &vars,
+    settings,
+  )
+  .await;
+  queue_email(
+    &subject,
+    email,
+    &user.person.name,
+    &body,
+    None,
+    pool,
+    settings,
+  )
+  .await?;
 
-  // Insert the row after successful send, to avoid using daily reset limit while
-  // email sending is broken.
+  // Insert the row after the email is queued, to avoid using daily reset limit while
+  // email sending is not configured.
   let local_user_id = user.local_user.id;
   PasswordResetRequest::create(pool, local_user_id, token.clone()).await?;
   Ok(())
 }
 
+/// Warn the user about a login from an ip address or device which wasn't used before.
+pub async fn send_new_login_email(
+  user: &LocalUserView,
+  ip: Option<String>,
+  user_agent: Option<String>,
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let Some(email) = &user.local_user.email else {
+    return Ok(());
+  };
+  // Both values are provided by the client, so make sure they don't contain any html.
+  let browser = user_agent
+    .as_deref()
+    .map(user_agent_to_browser)
+    .filter(|b| {
+      b.chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    })
+    .unwrap_or("an unknown browser");
+  let ip = ip
+    .as_deref()
+    .filter(|ip| {
+      ip.chars()
+        .all(|c| c.is_ascii_hexdigit() || ".:[]".contains(c))
+    })
+    .unwrap_or("an unknown ip address");
+  let sessions_link = format!("{}/settings", settings.get_protocol_and_hostname());
+  let subject = format!("New sign-in to your account on {}", settings.hostname);
+  let body = format!(
+    "<h1>New sign-in from {browser} on {ip}</h1><br>Your account @{}@{} was just used to log in \
+     from a new device or location. If this was you, you can ignore this email. Otherwise change \
+     your password immediately, and revoke any sessions which you don't recognize.<br><br>\
+     <a href=\"{sessions_link}\">Review your sessions</a>",
+    user.person.name, settings.hostname
+  );
+  queue_email(
+    &subject,
+    email,
+    &user.person.name,
+    &body,
+    None,
+    pool,
+    settings,
+  )
+  .await
+}
+
+/// Tell the user that their account was locked because of too many failed logins.
+pub async fn send_login_locked_email(
+  user: &LocalUserView,
+  locked_until: DateTime<Utc>,
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let Some(email) = &user.local_user.email else {
+    return Ok(());
+  };
+  let reset_link = format!("{}/login_reset", settings.get_protocol_and_hostname());
+  let locked_until = locked_until.format("%Y-%m-%d %H:%M UTC").to_string();
+  let vars = EmailTemplateVars {
+    username: &user.person.name,
+    link: &reset_link,
+    content: &locked_until,
+    ..Default::default()
+  };
+  let (subject, body) = build_email(
+    pool,
+    EmailTemplateType::LoginLocked,
+    &user.local_user.interface_language,
+    &vars,
+    settings,
+  )
+  .await;
+  queue_email(
+    &subject,
+    email,
+    &user.person.name,
+    &body,
+    None,
+    pool,
+    settings,
+  )
+  .await
+}
+
+/// Send a single email with all unread replies, mentions and private messages since the last
+/// digest, for users who don't want an email for each notification.
+pub async fn send_email_digest(user: &LocalUserView, context: &LemmyContext) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let settings = context.settings();
+  let local_user = &user.local_user;
+  let Some(interval) = user
+    .local_user
+    .email_notification_frequency
+    .digest_interval()
+  else {
+    return Ok(());
+  };
+  let now = Utc::now();
+  let since = user.local_user.last_email_digest.unwrap_or(now - interval);
+  let person_id = user.person.id;
+
+  let replies = CommentReplyQuery {
+    recipient_id: Some(person_id),
+    my_person_id: Some(person_id),
+    show_bot_accounts: user.local_user.show_bot_accounts,
+    sort: Some(CommentSortType::New),
+    unread_only: true,
+    limit: Some(DIGEST_FETCH_LIMIT),
+    ..Default::default()
+  }
+  .list(pool)
+  .await?;
+  let mentions = PersonMentionQuery {
+    recipient_id: Some(person_id),
+    my_person_id: Some(person_id),
+    show_bot_accounts: user.local_user.show_bot_accounts,
+    sort: Some(CommentSortType::New),
+    unread_only: true,
+    limit: Some(DIGEST_FETCH_LIMIT),
+    ..Default::default()
+  }
+  .list(pool)
+  .await?;
+  let messages = PrivateMessageQuery {
+    unread_only: true,
+    limit: Some(DIGEST_FETCH_LIMIT),
+    ..Default::default()
+  }
+  .list(pool, person_id)
+  .await?;
+  let saved_search_matches = SavedSearchMatchQuery {
+    local_user_id: local_user.id,
+    unread_only: true,
+    limit: Some(DIGEST_FETCH_LIMIT),
+    ..Default::default()
+  }
+  .list(pool)
+  .await?;
+
+  let protocol_and_hostname = settings.get_protocol_and_hostname();
+  let comment_item = |kind: &str, creator: &Person, comment: &Comment| {
+    format!(
+      "<li>{kind} {} <a href=\"{protocol_and_hostname}/comment/{}\">in a comment</a></li>",
+      creator.name, comment.id
+    )
+  };
+  let items: Vec<String> = replies
+    .iter()
+    .filter(|r| local_user.send_reply_emails && r.comment_reply.published > since)
+    .map(|r| comment_item("Reply from", &r.creator, &r.comment))
+    .chain(
+      mentions
+        .iter()
+        .filter(|m| local_user.send_mention_emails && m.person_mention.published > since)
+        .map(|m| comment_item("Mentioned by", &m.creator, &m.comment)),
+    )
+    .chain(
+      messages
+        .iter()
+        .filter(|m| local_user.send_private_message_emails && m.private_message.published > since)
+        .map(|m| format!("<li>Private message from {}</li>", m.creator.name)),
+    )
+    .chain(
+      saved_search_matches
+        .iter()
+        .filter(|m| m.saved_search.send_email && m.saved_search_match.published > since)
+        .map(|m| {
+          saved_search_match_item(
+            &m.saved_search,
+            &m.post,
+            m.comment.as_ref(),
+            &protocol_and_hostname,
+          )
+        }),
+    )
+    .collect();
+
+  if let (false, Some(email)) = (items.is_empty(), &user.local_user.email) {
+    let inbox_link = format!("{protocol_and_hostname}/inbox");
+    let subject = format!("{} new notifications on {}", items.len(), settings.hostname);
+    let body = format!(
+      "<h1>New notifications</h1><br>Here is what you missed since the last summary:<br><ul>{}</ul>\
+       <a href=\"{inbox_link}\">Go to your inbox</a>",
+      items.join("")
+    );
+    let unsubscribe_link =
+      unsubscribe_link(local_user.id, EmailNotificationCategory::All, context)?;
+    let body = with_unsubscribe_footer(&body, &unsubscribe_link);
+    queue_email(
+      &subject,
+      email,
+      &user.person.name,
+      &body,
+      Some(&unsubscribe_link),
+      pool,
+      settings,
+    )
+    .await?;
+  }
+
+  // Also mark the digest as sent if there was nothing new, so that the next one starts from here
+  let form = LocalUserUpdateForm {
+    last_email_digest: Some(Some(now)),
+    ..Default::default()
+  };
+  LocalUser::update(pool, user.local_user.id, &form).await?;
+  Ok(())
+}
+
+/// Check the posts and comments which were created since the last check against a saved search,
+/// up to the given ids. Matches are stored as notifications, and emailed if the search has emails
+/// enabled. A new search only remembers the current ids, so that older content doesn't match.
+/// At most `SAVED_SEARCH_MATCH_LIMIT` of the newest matches are stored per check.
+///
+/// Content in `SAVED_SEARCH_RECHECK_MARGIN` behind the last ids is checked again, in case it was
+/// committed after the last check. It only matches once, and only if it was published after the
+/// search was saved.
+pub async fn check_saved_search(
+  saved_search: &SavedSearch,
+  (latest_post_id, latest_comment_id): (PostId, CommentId),
+  site: &Site,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let checkpoint_form = SavedSearchUpdateForm {
+    last_post_id: Some(Some(latest_post_id)),
+    last_comment_id: Some(Some(latest_comment_id)),
+    ..Default::default()
+  };
+  let (Some(last_post_id), Some(last_comment_id)) =
+    (saved_search.last_post_id, saved_search.last_comment_id)
+  else {
+    SavedSearch::update(pool, saved_search.id, &checkpoint_form).await?;
+    return Ok(());
+  };
+  let user = LocalUserView::read(pool, saved_search.local_user_id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindLocalUser)?;
+  let operators = SearchOperators::parse(&saved_search.query);
+  let published_after = Some(
+    operators
+      .after
+      .map_or(saved_search.published, |after| after.max(saved_search.published)),
+  );
+
+  let posts = PostQuery {
+    sort: Some(SortType::New),
+    community_id: saved_search.community_id,
+    creator_id: saved_search.creator_id,
+    local_user: Some(&user.local_user),
+    search_term: operators.search_term.clone(),
+    url_domain: operators.site.clone(),
+    published_after,
+    published_before: operators.before,
+    exclude_terms: operators.exclude_terms.clone(),
+    title_only: Some(saved_search.post_title_only),
+    id_after: Some(PostId(last_post_id.0.saturating_sub(SAVED_SEARCH_RECHECK_MARGIN))),
+    id_before_or_equal: Some(latest_post_id),
+    limit: Some(SAVED_SEARCH_MATCH_LIMIT),
+    ..Default::default()
+  }
+  .list(site, pool)
+  .await?;
+
+  let comments_limit = SAVED_SEARCH_MATCH_LIMIT - i64::try_from(posts.len())?;
+  // Comments don't link anywhere, so none of them can match a site filter
+  let comments = if operators.site.is_none() && comments_limit > 0 {
+    CommentQuery {
+      sort: Some(CommentSortType::New),
+      community_id: saved_search.community_id,
+      creator_id: saved_search.creator_id,
+      local_user: Some(&user.local_user),
+      search_term: operators.search_term.clone(),
+      published_after,
+      published_before: operators.before,
+      exclude_terms: operators.exclude_terms.clone(),
+      id_after: Some(CommentId(last_comment_id.0.saturating_sub(SAVED_SEARCH_RECHECK_MARGIN))),
+      id_before_or_equal: Some(latest_comment_id),
+      limit: Some(comments_limit),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?
+  } else {
+    Vec::new()
+  };
+
+  let forms: Vec<_> = posts
+    .iter()
+    .map(|p| SavedSearchMatchInsertForm::new(saved_search.id, p.post.id, None))
+    .chain(comments.iter().map(|c| {
+      SavedSearchMatchInsertForm::new(saved_search.id, c.post.id, Some(c.comment.id))
+    }))
+    .collect();
+  let new_matches = if forms.is_empty() {
+    Vec::new()
+  } else {
+    SavedSearchMatch::create_many(pool, &forms).await?
+  };
+  SavedSearch::update(pool, saved_search.id, &checkpoint_form).await?;
+
+  // Only email the new matches, not those which were found again in the margin
+  let is_new = |post_id: PostId, comment_id: Option<CommentId>| {
+    new_matches
+      .iter()
+      .any(|m| m.post_id == post_id && m.comment_id == comment_id)
+  };
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+  let items: Vec<String> = posts
+    .iter()
+    .filter(|p| is_new(p.post.id, None))
+    .map(|p| saved_search_match_item(saved_search, &p.post, None, &protocol_and_hostname))
+    .chain(
+      comments
+        .iter()
+        .filter(|c| is_new(c.post.id, Some(c.comment.id)))
+        .map(|c| {
+          saved_search_match_item(
+            saved_search,
+            &c.post,
+            Some(&c.comment),
+            &protocol_and_hostname,
+          )
+        }),
+    )
+    .collect();
+  if saved_search_email_enabled(saved_search, &user.local_user, &items) {
+    let inbox_link = format!("{protocol_and_hostname}/inbox");
+    let matches = items.join("");
+    let vars = EmailTemplateVars {
+      username: &user.person.name,
+      link: &inbox_link,
+      content: &matches,
+      ..Default::default()
+    };
+    let (subject, body) = build_email(
+      pool,
+      EmailTemplateType::SavedSearchMatch,
+      &user.local_user.interface_language,
+      &vars,
+      context.settings(),
+    )
+    .await;
+    send_email_to_user(
+      &user,
+      EmailNotificationCategory::SavedSearches,
+      &subject,
+      &body,
+      context,
+    )
+    .await;
+  }
+  Ok(())
+}
+
+/// Whether new matches of a saved search are emailed right away. Users with a digest get the
+/// matches with their next digest instead.
+fn saved_search_email_enabled(
+  saved_search: &SavedSearch,
+  local_user: &LocalUser,
+  items: &[String],
+) -> bool {
+  saved_search.send_email
+    && !items.is_empty()
+    && local_user.email_notification_frequency == EmailNotificationFrequency::Instant
+}
+
+/// A list item for an email, which links to the post or comment that matched a saved search.
+fn saved_search_match_item(
+  saved_search: &SavedSearch,
+  post: &Post,
+  comment: Option<&Comment>,
+  protocol_and_hostname: &str,
+) -> String {
+  let query = escape_html(&saved_search.query);
+  let title = escape_html(&post.name);
+  let (template, link) = match comment {
+    Some(comment) => (
+      SAVED_SEARCH_MATCH_COMMENT_ITEM,
+      format!("{protocol_and_hostname}/comment/{}", comment.id),
+    ),
+    None => (
+      SAVED_SEARCH_MATCH_POST_ITEM,
+      format!("{protocol_and_hostname}/post/{}", post.id),
+    ),
+  };
+  render_email_template(
+    template,
+    &[("query", &query), ("title", &title), ("link", &link)],
+  )
+}
+
+/// Escapes user provided text for use in html emails.
+fn escape_html(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+/// Get a short, human readable browser name from a user agent string.
+fn user_agent_to_browser(user_agent: &str) -> &str {
+  // Order matters, because for example the Chrome user agent also contains "Safari".
+  const BROWSERS: [(&str, &str); 7] = [
+    ("Edg/", "Edge"),
+    ("OPR/", "Opera"),
+    ("Firefox/", "Firefox"),
+    ("Chromium/", "Chromium"),
+    ("Chrome/", "Chrome"),
+    ("Safari/", "Safari"),
+    ("Jerboa", "Jerboa"),
+  ];
+  BROWSERS
+    .iter()
+    .find(|(pattern, _)| user_agent.contains(pattern))
+    .map(|(_, name)| *name)
+    // Fall back to the product name, eg `curl/8.0`
+    .unwrap_or_else(|| user_agent.split('/').next().unwrap_or(user_agent))
+}
+
 /// Send a verification email
 pub async fn send_verification_email(
   user: &LocalUserView,
@@ -485,14 +1045,156 @@ pub async fn send_verification_email(
   );
   EmailVerification::create(pool, &form).await?;
 
-  let lang = get_interface_language(user);
-  let subject = lang.verify_email_subject(&settings.hostname);
-  let body = lang.verify_email_body(&settings.hostname, &user.person.name, verify_link);
-  send_email(&subject, new_email, &user.person.name, &body, settings).await?;
+  let vars = EmailTemplateVars {
+    username: &user.person.name,
+    link: &verify_link,
+    ..Default::default()
+  };
+  let (subject, body) = build_email(
+    pool,
+    EmailTemplateType::EmailVerification,
+    &user.local_user.interface_language,
+    &vars,
+    settings,
+  )
+  .await;
+  queue_email(
+    &subject,
+    new_email,
+    &user.person.name,
+    &body,
+    None,
+    pool,
+    settings,
+  )
+  .await?;
 
   Ok(())
 }
 
+/// Values for the placeholders in email templates, see [EmailTemplateType] for their meaning.
+#[derive(Default)]
+pub struct EmailTemplateVars<'a> {
+  pub username: &'a str,
+  pub link: &'a str,
+  pub sender: &'a str,
+  pub content: &'a str,
+}
+
+/// Get the subject and body of an email, from the template which an admin defined for this type
+/// and interface language. Falls back to the built-in translation if there is none.
+pub async fn build_email(
+  pool: &mut DbPool<'_>,
+  template_type: EmailTemplateType,
+  interface_language: &str,
+  vars: &EmailTemplateVars<'_>,
+  settings: &Settings,
+) -> (String, String) {
+  match EmailTemplate::read_for_language(pool, template_type, interface_language).await {
+    Ok(Some(template)) => {
+      return render_email(&template.subject, &template.body, vars, settings);
+    }
+    Ok(None) => {}
+    Err(e) => warn!("Failed to read email template: {e}"),
+  }
+  let lang = lang_str_to_lang(interface_language);
+  default_email(template_type, &lang, vars, settings)
+}
+
+/// Insert the values into the subject and body of an email template.
+pub fn render_email(
+  subject: &str,
+  body: &str,
+  vars: &EmailTemplateVars<'_>,
+  settings: &Settings,
+) -> (String, String) {
+  let vars = [
+    ("username", vars.username),
+    ("hostname", settings.hostname.as_str()),
+    ("link", vars.link),
+    ("sender", vars.sender),
+    ("content", vars.content),
+  ];
+  (
+    render_email_template(subject, &vars),
+    render_email_template(body, &vars),
+  )
+}
+
+/// The built-in email for each template type, from the translations.
+pub fn default_email(
+  template_type: EmailTemplateType,
+  lang: &Lang,
+  vars: &EmailTemplateVars<'_>,
+  settings: &Settings,
+) -> (String, String) {
+  let hostname = &settings.hostname;
+  let EmailTemplateVars {
+    username,
+    link,
+    sender,
+    content,
+  } = vars;
+  match template_type {
+    EmailTemplateType::EmailVerification => (
+      lang.verify_email_subject(hostname),
+      lang.verify_email_body(hostname, username, link),
+    ),
+    EmailTemplateType::PasswordReset => (
+      lang.password_reset_subject(username),
+      lang.password_reset_body(link, username),
+    ),
+    EmailTemplateType::ApplicationApproved => (
+      lang.registration_approved_subject(link),
+      lang.registration_approved_body(hostname),
+    ),
+    EmailTemplateType::NewApplication => (
+      lang.new_application_subject(hostname, sender),
+      lang.new_application_body(link),
+    ),
+    EmailTemplateType::NewReport => (
+      lang.new_report_subject(hostname, content, sender),
+      lang.new_report_body(link),
+    ),
+    EmailTemplateType::Mention => (
+      lang.notification_mentioned_by_subject(sender),
+      lang.notification_mentioned_by_body(content, link, sender),
+    ),
+    EmailTemplateType::CommentReply => (
+      lang.notification_comment_reply_subject(sender),
+      lang.notification_comment_reply_body(content, link, sender),
+    ),
+    EmailTemplateType::PostReply => (
+      lang.notification_post_reply_subject(sender),
+      lang.notification_post_reply_body(content, link, sender),
+    ),
+    EmailTemplateType::PrivateMessage => (
+      lang.notification_private_message_subject(sender),
+      lang.notification_private_message_body(link, content, sender),
+    ),
+    // These are not in the translations yet, admins can add a template for other languages
+    EmailTemplateType::SavedSearchMatch => (
+      render_email_template(SAVED_SEARCH_MATCH_SUBJECT, &[("hostname", hostname)]),
+      render_email_template(
+        SAVED_SEARCH_MATCH_BODY,
+        &[("link", link), ("content", content)],
+      ),
+    ),
+    EmailTemplateType::LoginLocked => (
+      render_email_template(LOGIN_LOCKED_SUBJECT, &[("hostname", hostname)]),
+      render_email_template(
+        LOGIN_LOCKED_BODY,
+        &[
+          ("username", username),
+          ("hostname", hostname),
+          ("link", link),
+          ("content", content),
+        ],
+      ),
+    ),
+  }
+}
+
 pub fn get_interface_language(user: &LocalUserView) -> Lang {
   lang_str_to_lang(&user.local_user.interface_language)
 }
@@ -520,6 +1222,10 @@ pub fn local_site_rate_limit_to_rate_limit_config(
     ActionType::Comment => (l.comment, l.comment_per_second),
     ActionType::Search => (l.search, l.search_per_second),
     ActionType::ImportUserSettings => (l.import_user_settings, l.import_user_settings_per_second),
+    ActionType::Vote => (l.vote, l.vote_per_second),
+    ActionType::Report => (l.report, l.report_per_second),
+    ActionType::Follow => (l.follow, l.follow_per_second),
+    ActionType::FederationFetch => (l.federation_fetch, l.federation_fetch_per_second),
   }
   .map(|_key, (capacity, secs_to_refill)| BucketConfig {
     capacity: u32::try_from(capacity).unwrap_or(0),
@@ -527,6 +1233,13 @@ pub fn local_site_rate_limit_to_rate_limit_config(
   })
 }
 
+pub fn local_site_rate_limit_to_account_limit_config(l: &LocalSiteRateLimit) -> AccountLimitConfig {
+  AccountLimitConfig {
+    factor: u32::try_from(l.account_factor).unwrap_or(0),
+    trusted_factor: u32::try_from(l.trusted_account_factor).unwrap_or(0),
+  }
+}
+
 pub fn local_site_to_slur_regex(local_site: &LocalSite) -> Option<Regex> {
   build_slur_regex(local_site.slur_filter_regex.as_deref())
 }
@@ -571,13 +1284,33 @@ pub async fn get_url_blocklist(context: &LemmyContext) -> LemmyResult<RegexSet>
 
 pub async fn send_application_approved_email(
   user: &LocalUserView,
+  pool: &mut DbPool<'_>,
   settings: &Settings,
 ) -> LemmyResult<()> {
   let email = &user.local_user.email.clone().expect("email");
-  let lang = get_interface_language(user);
-  let subject = lang.registration_approved_subject(&user.person.actor_id);
-  let body = lang.registration_approved_body(&settings.hostname);
-  send_email(&subject, email, &user.person.name, &body, settings).await
+  let vars = EmailTemplateVars {
+    username: &user.person.name,
+    link: user.person.actor_id.as_str(),
+    ..Default::default()
+  };
+  let (subject, body) = build_email(
+    pool,
+    EmailTemplateType::ApplicationApproved,
+    &user.local_user.interface_language,
+    &vars,
+    settings,
+  )
+  .await;
+  queue_email(
+    &subject,
+    email,
+    &user.person.name,
+    &body,
+    None,
+    pool,
+    settings,
+  )
+  .await
 }
 
 /// Send a new applicant email notification to all admins
@@ -596,10 +1329,30 @@ pub async fn send_new_applicant_email_to_admins(
 
   for admin in &admins {
     let email = &admin.local_user.email.clone().expect("email");
-    let lang = get_interface_language_from_settings(admin);
-    let subject = lang.new_application_subject(&settings.hostname, applicant_username);
-    let body = lang.new_application_body(applications_link);
-    send_email(&subject, email, &admin.person.name, &body, settings).await?;
+    let vars = EmailTemplateVars {
+      username: &admin.person.name,
+      link: applications_link,
+      sender: applicant_username,
+      ..Default::default()
+    };
+    let (subject, body) = build_email(
+      pool,
+      EmailTemplateType::NewApplication,
+      &admin.local_user.interface_language,
+      &vars,
+      settings,
+    )
+    .await;
+    queue_email(
+      &subject,
+      email,
+      &admin.person.name,
+      &body,
+      None,
+      pool,
+      settings,
+    )
+    .await?;
   }
   Ok(())
 }
@@ -618,10 +1371,103 @@ pub async fn send_new_report_email_to_admins(
 
   for admin in &admins {
     let email = &admin.local_user.email.clone().expect("email");
-    let lang = get_interface_language_from_settings(admin);
-    let subject = lang.new_report_subject(&settings.hostname, reported_username, reporter_username);
-    let body = lang.new_report_body(reports_link);
-    send_email(&subject, email, &admin.person.name, &body, settings).await?;
+    let vars = EmailTemplateVars {
+      username: &admin.person.name,
+      link: reports_link,
+      sender: reporter_username,
+      content: reported_username,
+    };
+    let (subject, body) = build_email(
+      pool,
+      EmailTemplateType::NewReport,
+      &admin.local_user.interface_language,
+      &vars,
+      settings,
+    )
+    .await;
+    queue_email(
+      &subject,
+      email,
+      &admin.person.name,
+      &body,
+      None,
+      pool,
+      settings,
+    )
+    .await?;
+  }
+  Ok(())
+}
+
+/// Tell a local user that a moderator removed or locked their content, or banned them from a
+/// community. Remote users are informed by their own instance.
+pub async fn send_mod_action_notification(
+  form: &ModActionNotificationInsertForm,
+  mod_person_id: PersonId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  // No need to tell moderators about their own actions
+  if form.recipient_id == mod_person_id {
+    return Ok(());
+  }
+  let recipient = Person::read(pool, form.recipient_id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindPerson)?;
+  if recipient.local {
+    ModActionNotification::create(pool, form).await?;
+  }
+  Ok(())
+}
+
+/// Determine what happened to reported content, at the time when the report is resolved.
+pub fn report_outcome(content_removed: bool, creator_banned: bool) -> ReportOutcome {
+  if creator_banned {
+    ReportOutcome::UserBanned
+  } else if content_removed {
+    ReportOutcome::ContentRemoved
+  } else {
+    ReportOutcome::NoAction
+  }
+}
+
+/// Tell a local user that their report was resolved, unless they turned off these notifications.
+pub async fn send_report_resolved_notification(
+  form: &ModActionNotificationInsertForm,
+  resolver_id: PersonId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  if form.recipient_id == resolver_id {
+    return Ok(());
+  }
+  let reporter = LocalUserView::read_person(pool, form.recipient_id).await?;
+  if reporter.is_some_and(|r| r.local_user.report_resolved_notifications) {
+    ModActionNotification::create(pool, form).await?;
+  }
+  Ok(())
+}
+
+/// Tell the reporters of a post or comment that their reports were resolved, when a moderator
+/// removed or restored it and thereby resolved all of its reports.
+pub async fn send_reports_resolved_by_removal_notifications(
+  reporter_ids: Vec<PersonId>,
+  post: &Post,
+  comment_id: Option<CommentId>,
+  content_removed: bool,
+  resolver_id: PersonId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  for reporter_id in reporter_ids {
+    let form = ModActionNotificationInsertForm {
+      post_id: Some(post.id),
+      comment_id,
+      report_outcome: Some(report_outcome(content_removed, false)),
+      ..ModActionNotificationInsertForm::new(
+        reporter_id,
+        ModActionNotificationType::ReportResolved,
+        Some(post.community_id),
+      )
+    };
+    send_report_resolved_notification(&form, resolver_id, pool).await?;
   }
   Ok(())
 }
@@ -1081,6 +1927,18 @@ fn build_proxied_image_url(
 mod tests {
 
   use super::*;
+  use lemmy_db_schema::source::{
+    comment::CommentInsertForm,
+    community::CommunityInsertForm,
+    email_template::EmailTemplateInsertForm,
+    local_user::LocalUserInsertForm,
+    person::PersonInsertForm,
+    post::PostInsertForm,
+    post_report::{PostReport, PostReportForm},
+    saved_search::SavedSearchInsertForm,
+  };
+  use lemmy_db_schema::traits::Reportable;
+  use lemmy_db_views_actor::mod_action_notification_view::ModActionNotificationQuery;
   use pretty_assertions::assert_eq;
   use serial_test::serial;
 
@@ -1093,6 +1951,14 @@ mod tests {
     assert!(password_length_check("looooooooooooooooooooooooooooooooooooooooooooooooooooooooooong").is_err());
   }
 
+  #[test]
+  fn test_report_outcome() {
+    assert_eq!(ReportOutcome::NoAction, report_outcome(false, false));
+    assert_eq!(ReportOutcome::ContentRemoved, report_outcome(true, false));
+    assert_eq!(ReportOutcome::UserBanned, report_outcome(false, true));
+    assert_eq!(ReportOutcome::UserBanned, report_outcome(true, true));
+  }
+
   #[test]
   fn honeypot() {
     assert!(honeypot_check(&None).is_ok());
@@ -1101,6 +1967,17 @@ mod tests {
     assert!(honeypot_check(&Some("message".to_string())).is_err());
   }
 
+  #[test]
+  fn test_user_agent_to_browser() {
+    let firefox = "Mozilla/5.0 (X11; Linux x86_64; rv:132.0) Gecko/20100101 Firefox/132.0";
+    assert_eq!("Firefox", user_agent_to_browser(firefox));
+    let chrome =
+      "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) \
+                  Chrome/131.0.0.0 Safari/537.36";
+    assert_eq!("Chrome", user_agent_to_browser(chrome));
+    assert_eq!("curl", user_agent_to_browser("curl/8.5.0"));
+  }
+
   #[test]
   fn test_limit_ban_term() {
     // Ban expires in past, should throw error
@@ -1122,6 +1999,320 @@ mod tests {
     );
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_build_email() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let settings = context.settings();
+    let vars = EmailTemplateVars {
+      username: "alice",
+      link: "https://example.com/reset",
+      ..Default::default()
+    };
+
+    let form = EmailTemplateInsertForm::new(
+      EmailTemplateType::PasswordReset,
+      Some("de".to_string()),
+      "Hallo {username}".to_string(),
+      "<a href=\"{link}\">{hostname}</a>".to_string(),
+    );
+    let template = EmailTemplate::create(&mut context.pool(), &form).await?;
+
+    let (subject, body) = build_email(
+      &mut context.pool(),
+      EmailTemplateType::PasswordReset,
+      "de",
+      &vars,
+      settings,
+    )
+    .await;
+    assert_eq!("Hallo alice", subject);
+    assert_eq!(
+      format!(
+        "<a href=\"https://example.com/reset\">{}</a>",
+        settings.hostname
+      ),
+      body
+    );
+
+    // Other languages and types still use the built-in translations
+    let en = lang_str_to_lang("en");
+    let built_in = build_email(
+      &mut context.pool(),
+      EmailTemplateType::PasswordReset,
+      "en",
+      &vars,
+      settings,
+    )
+    .await;
+    assert_eq!(
+      default_email(EmailTemplateType::PasswordReset, &en, &vars, settings),
+      built_in
+    );
+    assert_eq!(en.password_reset_subject("alice"), built_in.0);
+
+    // Emails which aren't in the translations yet have a built-in template
+    let locked_vars = EmailTemplateVars {
+      content: "2024-12-01 12:00 UTC",
+      ..vars
+    };
+    let (subject, body) =
+      default_email(EmailTemplateType::LoginLocked, &en, &locked_vars, settings);
+    assert!(subject.contains(&settings.hostname));
+    assert!(body.contains("@alice@") && body.contains("until 2024-12-01 12:00 UTC"));
+    assert!(body.contains("href=\"https://example.com/reset\""));
+
+    EmailTemplate::delete(&mut context.pool(), template.id).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_check_saved_search() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "saved_search_person");
+    let person = Person::create(pool, &person_form).await?;
+    let local_user_form = LocalUserInsertForm::test_form(person.id);
+    let local_user = LocalUser::create(pool, &local_user_form, vec![]).await?;
+    let community_form = CommunityInsertForm::builder()
+      .name("saved_search_community".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(instance.id)
+      .build();
+    let community = Community::create(pool, &community_form).await?;
+    let site = Site {
+      id: Default::default(),
+      name: String::new(),
+      sidebar: None,
+      published: Default::default(),
+      updated: None,
+      icon: None,
+      banner: None,
+      description: None,
+      actor_id: Url::parse("http://example.com")?.into(),
+      last_refreshed_at: Default::default(),
+      inbox_url: Url::parse("http://example.com")?.into(),
+      private_key: None,
+      public_key: String::new(),
+      instance_id: Default::default(),
+      content_warning: None,
+    };
+    let post_form = |name: &str| {
+      PostInsertForm::builder()
+        .name(name.to_string())
+        .creator_id(person.id)
+        .community_id(community.id)
+        .build()
+    };
+
+    Post::create(pool, &post_form("rust before saving")).await?;
+    let form = SavedSearchInsertForm::new(
+      local_user.id,
+      "rust".to_string(),
+      Some(community.id),
+      None,
+      false,
+      true,
+    );
+    let saved_search = SavedSearch::create(pool, &form).await?;
+
+    // The first check only remembers the newest ids, so that older content doesn't match
+    let latest_ids = SavedSearch::latest_content_ids(pool).await?;
+    check_saved_search(&saved_search, latest_ids, &site, &context).await?;
+    let saved_search = SavedSearch::read(pool, saved_search.id)
+      .await?
+      .ok_or(LemmyErrorType::CouldntFindSavedSearch)?;
+    assert_eq!(Some(latest_ids.0), saved_search.last_post_id);
+    assert_eq!(Some(latest_ids.1), saved_search.last_comment_id);
+
+    let post_match = Post::create(pool, &post_form("rust news")).await?;
+    Post::create(pool, &post_form("python news")).await?;
+    let comment_form = CommentInsertForm::builder()
+      .content("a rust comment".to_string())
+      .creator_id(person.id)
+      .post_id(post_match.id)
+      .build();
+    let comment_match = Comment::create(pool, &comment_form, None).await?;
+    let latest_ids = SavedSearch::latest_content_ids(pool).await?;
+    // Created after the ids of the check were read, so only the next check includes it
+    let post_later = Post::create(pool, &post_form("rust later")).await?;
+
+    // Each check reads the saved search again for the ids of the previous one, like the
+    // scheduled job. The last check has no new content, so nothing matches twice.
+    check_saved_search(&saved_search, latest_ids, &site, &context).await?;
+    for _ in 0..2 {
+      let saved_search = SavedSearch::read(pool, saved_search.id)
+        .await?
+        .ok_or(LemmyErrorType::CouldntFindSavedSearch)?;
+      let latest_ids = SavedSearch::latest_content_ids(pool).await?;
+      check_saved_search(&saved_search, latest_ids, &site, &context).await?;
+    }
+
+    let matches = SavedSearchMatchQuery {
+      local_user_id: local_user.id,
+      unread_only: true,
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    let matched: HashSet<_> = matches
+      .iter()
+      .map(|m| (m.post.id, m.comment.as_ref().map(|c| c.id)))
+      .collect();
+    let expected = HashSet::from([
+      (post_match.id, None),
+      (post_match.id, Some(comment_match.id)),
+      (post_later.id, None),
+    ]);
+    assert_eq!(3, matches.len());
+    assert_eq!(expected, matched);
+
+    // Content which was committed after a check that included a higher id still matches once
+    let post_late = Post::create(pool, &post_form("rust committed late")).await?;
+    let post_higher = Post::create(pool, &post_form("python committed first")).await?;
+    let checkpoint_form = SavedSearchUpdateForm {
+      last_post_id: Some(Some(post_higher.id)),
+      ..Default::default()
+    };
+    SavedSearch::update(pool, saved_search.id, &checkpoint_form).await?;
+    for _ in 0..2 {
+      let saved_search = SavedSearch::read(pool, saved_search.id)
+        .await?
+        .ok_or(LemmyErrorType::CouldntFindSavedSearch)?;
+      let latest_ids = SavedSearch::latest_content_ids(pool).await?;
+      check_saved_search(&saved_search, latest_ids, &site, &context).await?;
+    }
+    let matches = SavedSearchMatchQuery {
+      local_user_id: local_user.id,
+      unread_only: true,
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_eq!(4, matches.len());
+    assert!(matches.iter().any(|m| m.post.id == post_late.id));
+
+    // Instant emails are only sent without a digest, the unread matches above go into the digest
+    let items = vec!["<li>match</li>".to_string()];
+    assert!(saved_search_email_enabled(&saved_search, &local_user, &items));
+    assert!(!saved_search_email_enabled(&saved_search, &local_user, &[]));
+    let digest_user = LocalUser {
+      email_notification_frequency: EmailNotificationFrequency::Daily,
+      ..local_user.clone()
+    };
+    assert!(!saved_search_email_enabled(&saved_search, &digest_user, &items));
+    let without_email = SavedSearch {
+      send_email: false,
+      ..saved_search.clone()
+    };
+    assert!(!saved_search_email_enabled(&without_email, &local_user, &items));
+
+    // The items and the built-in email are rendered from templates
+    let item = saved_search_match_item(&saved_search, &post_match, None, "https://example.com");
+    assert_eq!(
+      format!(
+        "<li>Match for \"rust\": <a href=\"https://example.com/post/{}\">rust news</a></li>",
+        post_match.id
+      ),
+      item
+    );
+    let vars = EmailTemplateVars {
+      link: "https://example.com/inbox",
+      content: &item,
+      ..Default::default()
+    };
+    let (subject, body) = build_email(
+      pool,
+      EmailTemplateType::SavedSearchMatch,
+      "en",
+      &vars,
+      context.settings(),
+    )
+    .await;
+    assert!(subject.contains(&context.settings().hostname));
+    assert!(body.contains(&item) && body.contains("https://example.com/inbox"));
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_reports_resolved_by_removal() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let creator_form = PersonInsertForm::test_form(instance.id, "removal_creator");
+    let creator = Person::create(pool, &creator_form).await?;
+    let reporter_form = PersonInsertForm::test_form(instance.id, "removal_reporter");
+    let reporter = Person::create(pool, &reporter_form).await?;
+    LocalUser::create(pool, &LocalUserInsertForm::test_form(reporter.id), vec![]).await?;
+    let moderator_form = PersonInsertForm::test_form(instance.id, "removal_moderator");
+    let moderator = Person::create(pool, &moderator_form).await?;
+    let community_form = CommunityInsertForm::builder()
+      .name("removal_community".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(instance.id)
+      .build();
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::builder()
+      .name("reported post".to_string())
+      .creator_id(creator.id)
+      .community_id(community.id)
+      .build();
+    let post = Post::create(pool, &post_form).await?;
+    let report_form = PostReportForm {
+      creator_id: reporter.id,
+      post_id: post.id,
+      original_post_name: post.name.clone(),
+      reason: "spam".to_string(),
+      ..Default::default()
+    };
+    let report = PostReport::report(pool, &report_form).await?;
+
+    // The moderator removes the post, which resolves the report
+    let resolved = PostReport::resolve_all_for_object(pool, post.id, moderator.id).await?;
+    assert_eq!(vec![report.id], resolved.iter().map(|r| r.id).collect::<Vec<_>>());
+    let reporter_ids = resolved.iter().map(|r| r.creator_id).collect();
+    let mod_id = moderator.id;
+    send_reports_resolved_by_removal_notifications(reporter_ids, &post, None, true, mod_id, pool)
+      .await?;
+
+    // The reporter is told that the post was removed
+    let notifications = ModActionNotificationQuery {
+      recipient_id: reporter.id,
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    let received = notifications
+      .iter()
+      .map(|n| {
+        let n = &n.mod_action_notification;
+        (n.kind, n.post_id, n.report_outcome)
+      })
+      .collect::<Vec<_>>();
+    let expected = vec![(
+      ModActionNotificationType::ReportResolved,
+      Some(post.id),
+      Some(ReportOutcome::ContentRemoved),
+    )];
+    assert_eq!(expected, received);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, creator.id).await?;
+    Person::delete(pool, reporter.id).await?;
+    Person::delete(pool, moderator.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_proxy_image_link() {
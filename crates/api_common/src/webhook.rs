@@ -0,0 +1,87 @@
+use lemmy_db_schema::{
+  newtypes::{CommunityId, WebhookId},
+  source::webhook::{Webhook, WebhookDelivery},
+  WebhookEventType,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create a webhook. Without community it receives the events of the whole site, and can only be
+/// created by admins. Otherwise it is managed by the moderators of the community.
+///
+/// The signing secret is generated by the server.
+pub struct CreateWebhook {
+  pub community_id: Option<CommunityId>,
+  pub url: String,
+  pub events: Vec<WebhookEventType>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Edit a webhook.
+pub struct EditWebhook {
+  pub id: WebhookId,
+  pub url: Option<String>,
+  pub events: Option<Vec<WebhookEventType>>,
+  pub enabled: Option<bool>,
+  /// Replace the signing secret with a new one.
+  pub regenerate_secret: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a webhook, together with its delivery log.
+pub struct DeleteWebhook {
+  pub id: WebhookId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a webhook.
+pub struct WebhookResponse {
+  pub webhook: Webhook,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the webhooks of a community, or the site webhooks if no community is given.
+pub struct ListWebhooks {
+  pub community_id: Option<CommunityId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListWebhooksResponse {
+  pub webhooks: Vec<Webhook>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The delivery log of a webhook. Most recent first.
+pub struct ListWebhookDeliveries {
+  pub webhook_id: WebhookId,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ListWebhookDeliveriesResponse {
+  pub deliveries: Vec<WebhookDelivery>,
+}
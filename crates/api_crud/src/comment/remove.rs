@@ -5,16 +5,24 @@ use lemmy_api_common::{
   comment::{CommentResponse, RemoveComment},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::check_community_mod_action,
+  send_webhook::{send_webhook_event, WebhookModAction},
+  utils::{
+    check_community_mod_action,
+    send_mod_action_notification,
+    send_reports_resolved_by_removal_notifications,
+  },
 };
 use lemmy_db_schema::{
   source::{
     comment::{Comment, CommentUpdateForm},
     comment_report::CommentReport,
     local_user::LocalUser,
+    mod_action_notification::ModActionNotificationInsertForm,
     moderator::{ModRemoveComment, ModRemoveCommentForm},
   },
   traits::{Crud, Reportable},
+  ModActionNotificationType,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::{CommentView, LocalUserView};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
@@ -69,8 +77,18 @@ pub async fn remove_comment(
   .await
   .with_lemmy_type(LemmyErrorType::CouldntUpdateComment)?;
 
-  CommentReport::resolve_all_for_object(&mut context.pool(), comment_id, local_user_view.person.id)
-    .await?;
+  let resolver_id = local_user_view.person.id;
+  let resolved_reports =
+    CommentReport::resolve_all_for_object(&mut context.pool(), comment_id, resolver_id).await?;
+  send_reports_resolved_by_removal_notifications(
+    resolved_reports.iter().map(|r| r.creator_id).collect(),
+    &orig_comment.post,
+    Some(comment_id),
+    removed,
+    resolver_id,
+    &mut context.pool(),
+  )
+  .await?;
 
   // Mod tables
   let form = ModRemoveCommentForm {
@@ -79,7 +97,36 @@ pub async fn remove_comment(
     removed: Some(removed),
     reason: data.reason.clone(),
   };
-  ModRemoveComment::create(&mut context.pool(), &form).await?;
+  let entry = ModRemoveComment::create(&mut context.pool(), &form).await?;
+  send_webhook_event(
+    WebhookEventType::ModAction,
+    Some(orig_comment.community.id),
+    &WebhookModAction::RemoveComment {
+      entry,
+      comment: updated_comment.clone(),
+    },
+    &context,
+  )
+  .await;
+
+  if removed {
+    let notification_form = ModActionNotificationInsertForm {
+      post_id: Some(orig_comment.post.id),
+      comment_id: Some(comment_id),
+      reason: data.reason.clone(),
+      ..ModActionNotificationInsertForm::new(
+        orig_comment.creator.id,
+        ModActionNotificationType::RemoveComment,
+        Some(orig_comment.community.id),
+      )
+    };
+    send_mod_action_notification(
+      &notification_form,
+      local_user_view.person.id,
+      &mut context.pool(),
+    )
+    .await?;
+  }
 
   let recipient_ids = send_local_notifs(
     vec![],
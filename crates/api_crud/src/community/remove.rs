@@ -5,6 +5,7 @@ use lemmy_api_common::{
   community::{CommunityResponse, RemoveCommunity},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
+  send_webhook::{send_webhook_event, WebhookModAction},
   utils::{check_community_mod_action, is_admin},
 };
 use lemmy_db_schema::{
@@ -13,6 +14,7 @@ use lemmy_db_schema::{
     moderator::{ModRemoveCommunity, ModRemoveCommunityForm},
   },
   traits::Crud,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
@@ -55,7 +57,17 @@ pub async fn remove_community(
     removed: Some(removed),
     reason: data.reason.clone(),
   };
-  ModRemoveCommunity::create(&mut context.pool(), &form).await?;
+  let entry = ModRemoveCommunity::create(&mut context.pool(), &form).await?;
+  send_webhook_event(
+    WebhookEventType::ModAction,
+    Some(community_id),
+    &WebhookModAction::RemoveCommunity {
+      entry,
+      community: community.clone(),
+    },
+    &context,
+  )
+  .await;
 
   ActivityChannel::submit_activity(
     SendActivityData::RemoveCommunity {
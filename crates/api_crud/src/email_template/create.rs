@@ -0,0 +1,42 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  email_template::{CreateEmailTemplate, EmailTemplateResponse},
+  utils::is_admin,
+};
+use lemmy_db_schema::source::email_template::{EmailTemplate, EmailTemplateInsertForm};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// Same as the length of `local_user.interface_language`.
+const MAX_LANGUAGE_LENGTH: usize = 20;
+
+#[tracing::instrument(skip(context))]
+pub async fn create_email_template(
+  data: Json<CreateEmailTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<EmailTemplateResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let language = data.language.clone().filter(|l| !l.is_empty());
+  if let Some(language) = &language {
+    if language.len() > MAX_LANGUAGE_LENGTH {
+      Err(LemmyErrorType::InvalidEmailTemplateLanguage)?
+    }
+  }
+
+  let form = EmailTemplateInsertForm::new(
+    data.template_type,
+    language,
+    data.subject.clone(),
+    data.body.clone(),
+  );
+  let email_template = EmailTemplate::create(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::EmailTemplateAlreadyExists)?;
+
+  Ok(Json(EmailTemplateResponse { email_template }))
+}
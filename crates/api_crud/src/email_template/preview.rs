@@ -0,0 +1,43 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  email_template::{PreviewEmailTemplate, PreviewEmailTemplateResponse},
+  utils::{build_email, is_admin, render_email, EmailTemplateVars},
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+#[tracing::instrument(skip(context))]
+pub async fn preview_email_template(
+  data: Json<PreviewEmailTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PreviewEmailTemplateResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  // Example values, with the admin as recipient
+  let link = format!("{}/example", context.settings().get_protocol_and_hostname());
+  let vars = EmailTemplateVars {
+    username: &local_user_view.person.name,
+    link: &link,
+    sender: "example_user",
+    content: "<p>Example content</p>",
+  };
+  let (subject, body) = match (&data.subject, &data.body) {
+    (Some(subject), Some(body)) => render_email(subject, body, &vars, context.settings()),
+    _ => {
+      build_email(
+        &mut context.pool(),
+        data.template_type,
+        data.language.as_deref().unwrap_or("en"),
+        &vars,
+        context.settings(),
+      )
+      .await
+    }
+  };
+
+  Ok(Json(PreviewEmailTemplateResponse { subject, body }))
+}
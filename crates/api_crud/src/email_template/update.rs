@@ -0,0 +1,34 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  email_template::{EditEmailTemplate, EmailTemplateResponse},
+  utils::is_admin,
+};
+use lemmy_db_schema::{
+  source::email_template::{EmailTemplate, EmailTemplateUpdateForm},
+  utils::naive_now,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn update_email_template(
+  data: Json<EditEmailTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<EmailTemplateResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let form = EmailTemplateUpdateForm {
+    subject: Some(data.subject.clone()),
+    body: Some(data.body.clone()),
+    updated: Some(Some(naive_now())),
+  };
+  let email_template = EmailTemplate::update(&mut context.pool(), data.id, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindEmailTemplate)?;
+
+  Ok(Json(EmailTemplateResponse { email_template }))
+}
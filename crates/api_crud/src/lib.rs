@@ -1,7 +1,9 @@
 pub mod comment;
 pub mod community;
 pub mod custom_emoji;
+pub mod email_template;
 pub mod post;
 pub mod private_message;
 pub mod site;
 pub mod user;
+pub mod webhook;
@@ -3,9 +3,12 @@ use actix_web::web::Json;
 use lemmy_api_common::{
   build_response::build_post_response,
   context::LemmyContext,
+  event_stream::StreamEvent,
   post::{CreatePost, PostResponse},
   request::generate_post_link_metadata,
   send_activity::SendActivityData,
+  send_stream_event::send_stream_event,
+  send_webhook::send_webhook_event,
   utils::{
     check_community_user_action,
     get_url_blocklist,
@@ -26,6 +29,7 @@ use lemmy_db_schema::{
   traits::{Crud, Likeable},
   utils::diesel_url_create,
   CommunityVisibility,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_db_views_actor::structs::CommunityModeratorView;
@@ -169,6 +173,17 @@ pub async fn create_post(
 
   mark_post_as_read(person_id, post_id, &mut context.pool()).await?;
 
+  send_webhook_event(
+    WebhookEventType::PostCreated,
+    Some(community_id),
+    &inserted_post,
+    &context,
+  )
+  .await;
+  send_stream_event(|| StreamEvent::Post {
+    post: inserted_post.clone(),
+  });
+
   if let Some(url) = inserted_post.url.clone() {
     if community.visibility == CommunityVisibility::Public {
       spawn_try_task(async move {
@@ -5,16 +5,24 @@ use lemmy_api_common::{
   context::LemmyContext,
   post::{PostResponse, RemovePost},
   send_activity::{ActivityChannel, SendActivityData},
-  utils::check_community_mod_action,
+  send_webhook::{send_webhook_event, WebhookModAction},
+  utils::{
+    check_community_mod_action,
+    send_mod_action_notification,
+    send_reports_resolved_by_removal_notifications,
+  },
 };
 use lemmy_db_schema::{
   source::{
     local_user::LocalUser,
+    mod_action_notification::ModActionNotificationInsertForm,
     moderator::{ModRemovePost, ModRemovePostForm},
     post::{Post, PostUpdateForm},
     post_report::PostReport,
   },
   traits::{Crud, Reportable},
+  ModActionNotificationType,
+  WebhookEventType,
 };
 use lemmy_db_views::structs::LocalUserView;
 use lemmy_utils::{error::LemmyResult, LemmyErrorType};
@@ -59,8 +67,18 @@ pub async fn remove_post(
   )
   .await?;
 
-  PostReport::resolve_all_for_object(&mut context.pool(), post_id, local_user_view.person.id)
-    .await?;
+  let resolved_reports =
+    PostReport::resolve_all_for_object(&mut context.pool(), post_id, local_user_view.person.id)
+      .await?;
+  send_reports_resolved_by_removal_notifications(
+    resolved_reports.iter().map(|r| r.creator_id).collect(),
+    &post,
+    None,
+    removed,
+    local_user_view.person.id,
+    &mut context.pool(),
+  )
+  .await?;
 
   // Mod tables
   let form = ModRemovePostForm {
@@ -69,7 +87,35 @@ pub async fn remove_post(
     removed: Some(removed),
     reason: data.reason.clone(),
   };
-  ModRemovePost::create(&mut context.pool(), &form).await?;
+  let entry = ModRemovePost::create(&mut context.pool(), &form).await?;
+  send_webhook_event(
+    WebhookEventType::ModAction,
+    Some(orig_post.community_id),
+    &WebhookModAction::RemovePost {
+      entry,
+      post: post.clone(),
+    },
+    &context,
+  )
+  .await;
+
+  if removed {
+    let notification_form = ModActionNotificationInsertForm {
+      post_id: Some(post_id),
+      reason: data.reason.clone(),
+      ..ModActionNotificationInsertForm::new(
+        orig_post.creator_id,
+        ModActionNotificationType::RemovePost,
+        Some(orig_post.community_id),
+      )
+    };
+    send_mod_action_notification(
+      &notification_form,
+      local_user_view.person.id,
+      &mut context.pool(),
+    )
+    .await?;
+  }
 
   ActivityChannel::submit_activity(
     SendActivityData::RemovePost {
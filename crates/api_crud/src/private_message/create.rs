@@ -1,16 +1,22 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_common::{
+  claims::EmailNotificationCategory,
   context::LemmyContext,
+  event_stream::StreamEvent,
+  person::PushNotification,
   private_message::{CreatePrivateMessage, PrivateMessageResponse},
+  push::send_push_notification,
   send_activity::{ActivityChannel, SendActivityData},
+  send_stream_event::send_stream_event,
   utils::{
+    build_email,
     check_person_block,
-    get_interface_language,
     get_url_blocklist,
     local_site_to_slur_regex,
     process_markdown,
     send_email_to_user,
+    EmailTemplateVars,
   },
 };
 use lemmy_db_schema::{
@@ -19,6 +25,7 @@ use lemmy_db_schema::{
     private_message::{PrivateMessage, PrivateMessageInsertForm},
   },
   traits::Crud,
+  EmailTemplateType,
 };
 use lemmy_db_views::structs::{LocalUserView, PrivateMessageView};
 use lemmy_utils::{
@@ -66,17 +73,40 @@ pub async fn create_private_message(
     let local_recipient = LocalUserView::read_person(&mut context.pool(), recipient_id)
       .await?
       .ok_or(LemmyErrorType::CouldntFindPerson)?;
-    let lang = get_interface_language(&local_recipient);
     let inbox_link = format!("{}/inbox", context.settings().get_protocol_and_hostname());
     let sender_name = &local_user_view.person.name;
     let content = markdown_to_html(&content);
+    let vars = EmailTemplateVars {
+      username: &local_recipient.person.name,
+      link: &inbox_link,
+      sender: sender_name,
+      content: &content,
+    };
+    let (subject, body) = build_email(
+      &mut context.pool(),
+      EmailTemplateType::PrivateMessage,
+      &local_recipient.local_user.interface_language,
+      &vars,
+      context.settings(),
+    )
+    .await;
     send_email_to_user(
       &local_recipient,
-      &lang.notification_private_message_subject(sender_name),
-      &lang.notification_private_message_body(inbox_link, &content, sender_name),
-      context.settings(),
+      EmailNotificationCategory::PrivateMessages,
+      &subject,
+      &body,
+      &context,
     )
     .await;
+    let notification = PushNotification {
+      title: format!("Private message from {sender_name}"),
+      body: view.private_message.content.clone(),
+      url: inbox_link.clone(),
+    };
+    send_push_notification(local_recipient.local_user.id, &notification, &context).await;
+    send_stream_event(|| StreamEvent::PrivateMessage {
+      private_message_view: Box::new(view.clone()),
+    });
   }
 
   ActivityChannel::submit_activity(
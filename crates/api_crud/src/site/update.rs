@@ -9,6 +9,7 @@ use lemmy_api_common::{
   utils::{
     get_url_blocklist,
     is_admin,
+    local_site_rate_limit_to_account_limit_config,
     local_site_rate_limit_to_rate_limit_config,
     local_site_to_slur_regex,
     process_markdown_opt,
@@ -143,6 +144,16 @@ pub async fn update_site(
     comment_per_second: not_zero(data.rate_limit_comment_per_second),
     search: data.rate_limit_search,
     search_per_second: not_zero(data.rate_limit_search_per_second),
+    vote: data.rate_limit_vote,
+    vote_per_second: not_zero(data.rate_limit_vote_per_second),
+    report: data.rate_limit_report,
+    report_per_second: not_zero(data.rate_limit_report_per_second),
+    follow: data.rate_limit_follow,
+    follow_per_second: not_zero(data.rate_limit_follow_per_second),
+    federation_fetch: data.rate_limit_federation_fetch,
+    federation_fetch_per_second: not_zero(data.rate_limit_federation_fetch_per_second),
+    account_factor: data.rate_limit_account_factor,
+    trusted_account_factor: data.rate_limit_trusted_account_factor,
     ..Default::default()
   };
 
@@ -199,6 +210,11 @@ pub async fn update_site(
   let rate_limit_config =
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
   context.rate_limit_cell().set_config(rate_limit_config);
+  context
+    .rate_limit_cell()
+    .set_account_config(local_site_rate_limit_to_account_limit_config(
+      &site_view.local_site_rate_limit,
+    ));
 
   Ok(Json(SiteResponse {
     site_view,
@@ -601,6 +617,16 @@ mod tests {
       rate_limit_comment_per_second: None,
       rate_limit_search: None,
       rate_limit_search_per_second: None,
+      rate_limit_vote: None,
+      rate_limit_vote_per_second: None,
+      rate_limit_report: None,
+      rate_limit_report_per_second: None,
+      rate_limit_follow: None,
+      rate_limit_follow_per_second: None,
+      rate_limit_federation_fetch: None,
+      rate_limit_federation_fetch_per_second: None,
+      rate_limit_account_factor: None,
+      rate_limit_trusted_account_factor: None,
       federation_enabled: site_is_federated,
       federation_debug: None,
       captcha_enabled: None,
@@ -4,6 +4,7 @@ use lemmy_api_common::{
   claims::Claims,
   context::LemmyContext,
   person::{LoginResponse, Register},
+  send_webhook::send_webhook_event,
   utils::{
     generate_inbox_url,
     generate_local_apub_endpoint,
@@ -29,8 +30,9 @@ use lemmy_db_schema::{
   },
   traits::Crud,
   RegistrationMode,
+  WebhookEventType,
 };
-use lemmy_db_views::structs::{LocalUserView, SiteView};
+use lemmy_db_views::structs::{LocalUserView, RegistrationApplicationView, SiteView};
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::{
@@ -192,7 +194,18 @@ pub async fn register(
       answer: data.answer.clone().expect("must have an answer"),
     };
 
-    RegistrationApplication::create(&mut context.pool(), &form).await?;
+    let application = RegistrationApplication::create(&mut context.pool(), &form).await?;
+    if let Some(application_view) =
+      RegistrationApplicationView::read(&mut context.pool(), application.id).await?
+    {
+      send_webhook_event(
+        WebhookEventType::RegistrationApplicationCreated,
+        None,
+        &application_view,
+        &context,
+      )
+      .await;
+    }
   }
 
   // Email the admins, only if email verification is not required
@@ -0,0 +1,49 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  send_webhook::{
+    check_webhook_events,
+    check_webhook_permission,
+    check_webhook_url,
+    generate_webhook_secret,
+  },
+  webhook::{CreateWebhook, WebhookResponse},
+};
+use lemmy_db_schema::{
+  source::webhook::{Webhook, WebhookInsertForm},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Maximum number of webhooks per community, or for the whole site. Each of them gets a delivery
+/// for every matching event.
+const MAX_WEBHOOKS: usize = 10;
+
+#[tracing::instrument(skip(context))]
+pub async fn create_webhook(
+  data: Json<CreateWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebhookResponse>> {
+  check_webhook_permission(&local_user_view, data.community_id, &mut context.pool()).await?;
+
+  let existing = Webhook::list(&mut context.pool(), data.community_id).await?;
+  if existing.len() >= MAX_WEBHOOKS {
+    Err(LemmyErrorType::TooManyWebhooks)?
+  }
+
+  let url = check_webhook_url(&data.url).await?;
+  let events = check_webhook_events(&data.events)?;
+  let form = WebhookInsertForm::new(
+    local_user_view.person.id,
+    data.community_id,
+    url,
+    generate_webhook_secret()?,
+    events,
+  );
+  let webhook = Webhook::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(WebhookResponse { webhook }))
+}
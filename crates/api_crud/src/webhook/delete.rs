@@ -0,0 +1,27 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  send_webhook::check_webhook_permission,
+  webhook::DeleteWebhook,
+  SuccessResponse,
+};
+use lemmy_db_schema::{source::webhook::Webhook, traits::Crud};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_webhook(
+  data: Json<DeleteWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let webhook = Webhook::read(&mut context.pool(), data.id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindWebhook)?;
+  check_webhook_permission(&local_user_view, webhook.community_id, &mut context.pool()).await?;
+
+  Webhook::delete(&mut context.pool(), data.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,31 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  send_webhook::check_webhook_permission,
+  webhook::{ListWebhookDeliveries, ListWebhookDeliveriesResponse},
+};
+use lemmy_db_schema::{
+  source::webhook::{Webhook, WebhookDelivery},
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn list_webhook_deliveries(
+  data: Query<ListWebhookDeliveries>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListWebhookDeliveriesResponse>> {
+  let webhook = Webhook::read(&mut context.pool(), data.webhook_id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindWebhook)?;
+  check_webhook_permission(&local_user_view, webhook.community_id, &mut context.pool()).await?;
+
+  let deliveries =
+    WebhookDelivery::list_for_webhook(&mut context.pool(), webhook.id, data.page, data.limit)
+      .await?;
+
+  Ok(Json(ListWebhookDeliveriesResponse { deliveries }))
+}
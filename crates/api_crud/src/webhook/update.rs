@@ -0,0 +1,61 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  send_webhook::{
+    check_webhook_events,
+    check_webhook_permission,
+    check_webhook_url,
+    generate_webhook_secret,
+  },
+  webhook::{EditWebhook, WebhookResponse},
+};
+use lemmy_db_schema::{
+  source::webhook::{Webhook, WebhookUpdateForm},
+  traits::Crud,
+  utils::naive_now,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn update_webhook(
+  data: Json<EditWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebhookResponse>> {
+  let orig_webhook = Webhook::read(&mut context.pool(), data.id)
+    .await?
+    .ok_or(LemmyErrorType::CouldntFindWebhook)?;
+  check_webhook_permission(
+    &local_user_view,
+    orig_webhook.community_id,
+    &mut context.pool(),
+  )
+  .await?;
+
+  let url = match &data.url {
+    Some(url) => Some(check_webhook_url(url).await?),
+    None => None,
+  };
+  let events = data
+    .events
+    .as_deref()
+    .map(check_webhook_events)
+    .transpose()?;
+  let secret = if data.regenerate_secret.unwrap_or_default() {
+    Some(generate_webhook_secret()?)
+  } else {
+    None
+  };
+  let form = WebhookUpdateForm {
+    url,
+    secret,
+    events,
+    enabled: data.enabled,
+    updated: Some(Some(naive_now())),
+  };
+  let webhook = Webhook::update(&mut context.pool(), data.id, &form).await?;
+
+  Ok(Json(WebhookResponse { webhook }))
+}
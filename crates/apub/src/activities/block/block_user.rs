@@ -23,7 +23,8 @@ use anyhow::anyhow;
 use chrono::{DateTime, Utc};
 use lemmy_api_common::{
   context::LemmyContext,
-  utils::{remove_user_data, remove_user_data_in_community},
+  send_webhook::{send_webhook_event, WebhookModAction},
+  utils::{remove_user_data, remove_user_data_in_community, send_mod_action_notification},
 };
 use lemmy_db_schema::{
   source::{
@@ -34,10 +35,13 @@ use lemmy_db_schema::{
       CommunityPersonBan,
       CommunityPersonBanForm,
     },
+    mod_action_notification::ModActionNotificationInsertForm,
     moderator::{ModBan, ModBanForm, ModBanFromCommunity, ModBanFromCommunityForm},
     person::{Person, PersonUpdateForm},
   },
   traits::{Bannable, Crud, Followable},
+  ModActionNotificationType,
+  WebhookEventType,
 };
 use lemmy_utils::{
   error::{LemmyError, LemmyResult},
@@ -215,11 +219,28 @@ impl ActivityHandler for BlockUser {
           mod_person_id: mod_person.id,
           other_person_id: blocked_person.id,
           community_id: community.id,
-          reason: self.summary,
+          reason: self.summary.clone(),
           banned: Some(true),
           expires,
         };
-        ModBanFromCommunity::create(&mut context.pool(), &form).await?;
+        let entry = ModBanFromCommunity::create(&mut context.pool(), &form).await?;
+        let action = WebhookModAction::BanFromCommunity {
+          entry,
+          person: blocked_person.0.clone(),
+        };
+        let event = WebhookEventType::ModAction;
+        send_webhook_event(event, Some(community.id), &action, context).await;
+
+        let notification_form = ModActionNotificationInsertForm {
+          reason: self.summary,
+          ..ModActionNotificationInsertForm::new(
+            blocked_person.id,
+            ModActionNotificationType::BanFromCommunity,
+            Some(community.id),
+          )
+        };
+        send_mod_action_notification(&notification_form, mod_person.id, &mut context.pool())
+          .await?;
       }
     }
 
@@ -21,16 +21,23 @@ use activitypub_federation::{
   kinds::{activity::UndoType, public},
   traits::ActivityHandler,
 };
-use lemmy_api_common::context::LemmyContext;
+use lemmy_api_common::{
+  context::LemmyContext,
+  send_webhook::{send_webhook_event, WebhookModAction},
+  utils::send_mod_action_notification,
+};
 use lemmy_db_schema::{
   source::{
     activity::ActivitySendTargets,
     community::Community,
+    mod_action_notification::ModActionNotificationInsertForm,
     moderator::{ModLockPost, ModLockPostForm},
     person::Person,
     post::{Post, PostUpdateForm},
   },
   traits::Crud,
+  ModActionNotificationType,
+  WebhookEventType,
 };
 use lemmy_utils::{
   error::{LemmyError, LemmyResult},
@@ -68,14 +75,32 @@ impl ActivityHandler for LockPage {
       ..Default::default()
     };
     let post = self.object.dereference(context).await?;
-    Post::update(&mut context.pool(), post.id, &form).await?;
+    let updated_post = Post::update(&mut context.pool(), post.id, &form).await?;
 
+    let mod_person_id = self.actor.dereference(context).await?.id;
     let form = ModLockPostForm {
-      mod_person_id: self.actor.dereference(context).await?.id,
+      mod_person_id,
       post_id: post.id,
       locked,
     };
-    ModLockPost::create(&mut context.pool(), &form).await?;
+    let entry = ModLockPost::create(&mut context.pool(), &form).await?;
+
+    let notification_form = ModActionNotificationInsertForm {
+      post_id: Some(post.id),
+      ..ModActionNotificationInsertForm::new(
+        post.creator_id,
+        ModActionNotificationType::LockPost,
+        Some(post.community_id),
+      )
+    };
+    send_mod_action_notification(&notification_form, mod_person_id, &mut context.pool()).await?;
+
+    let community_id = Some(post.community_id);
+    let action = WebhookModAction::LockPost {
+      entry,
+      post: updated_post,
+    };
+    send_webhook_event(WebhookEventType::ModAction, community_id, &action, context).await;
 
     Ok(())
   }
@@ -16,6 +16,8 @@ use activitypub_federation::{
 };
 use lemmy_api_common::{
   context::LemmyContext,
+  push::send_report_push_notifications,
+  send_webhook::send_webhook_event,
   utils::{check_comment_deleted_or_removed, check_post_deleted_or_removed},
 };
 use lemmy_db_schema::{
@@ -28,7 +30,9 @@ use lemmy_db_schema::{
     site::Site,
   },
   traits::{Crud, Reportable},
+  WebhookEventType,
 };
+use lemmy_db_views::structs::{CommentReportView, PostReportView};
 use lemmy_utils::{
   error::{LemmyError, LemmyResult},
   LemmyErrorType,
@@ -110,6 +114,8 @@ impl ActivityHandler for Report {
     insert_received_activity(&self.id, context).await?;
     let actor = self.actor.dereference(context).await?;
     let reason = self.reason()?;
+    let push_reason = reason.clone();
+    let community = self.community(context).await?;
     match self.object.dereference(context).await? {
       PostOrComment::Post(post) => {
         check_post_deleted_or_removed(&post)?;
@@ -122,7 +128,13 @@ impl ActivityHandler for Report {
           reason,
           original_post_body: post.body.clone(),
         };
-        PostReport::report(&mut context.pool(), &report_form).await?;
+        let report = PostReport::report(&mut context.pool(), &report_form).await?;
+        if let Some(report_view) =
+          PostReportView::read(&mut context.pool(), report.id, actor.id).await?
+        {
+          let event = WebhookEventType::ReportCreated;
+          send_webhook_event(event, Some(community.id), &report_view, context).await;
+        }
       }
       PostOrComment::Comment(comment) => {
         check_comment_deleted_or_removed(&comment)?;
@@ -133,9 +145,16 @@ impl ActivityHandler for Report {
           original_comment_text: comment.content.clone(),
           reason,
         };
-        CommentReport::report(&mut context.pool(), &report_form).await?;
+        let report = CommentReport::report(&mut context.pool(), &report_form).await?;
+        if let Some(report_view) =
+          CommentReportView::read(&mut context.pool(), report.id, actor.id).await?
+        {
+          let event = WebhookEventType::ReportCreated;
+          send_webhook_event(event, Some(community.id), &report_view, context).await;
+        }
       }
     };
+    send_report_push_notifications(community.id, &actor.name, &push_reason, context).await;
     Ok(())
   }
 }
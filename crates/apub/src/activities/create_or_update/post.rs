@@ -20,7 +20,12 @@ use activitypub_federation::{
   protocol::verification::{verify_domains_match, verify_urls_match},
   traits::{ActivityHandler, Actor, Object},
 };
-use lemmy_api_common::context::LemmyContext;
+use lemmy_api_common::{
+  context::LemmyContext,
+  event_stream::StreamEvent,
+  send_stream_event::send_stream_event,
+  send_webhook::send_webhook_event,
+};
 use lemmy_db_schema::{
   aggregates::structs::PostAggregates,
   newtypes::PersonId,
@@ -31,6 +36,7 @@ use lemmy_db_schema::{
     post::{Post, PostLike, PostLikeForm},
   },
   traits::{Crud, Likeable},
+  WebhookEventType,
 };
 use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
 use url::Url;
@@ -119,6 +125,7 @@ impl ActivityHandler for CreateOrUpdatePage {
   #[tracing::instrument(skip_all)]
   async fn receive(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
     insert_received_activity(&self.id, context).await?;
+    let is_create = self.kind == CreateOrUpdateType::Create;
     let post = ApubPost::from_json(self.object, context).await?;
 
     // author likes their own post by default
@@ -132,6 +139,14 @@ impl ActivityHandler for CreateOrUpdatePage {
     // Calculate initial hot_rank for post
     PostAggregates::update_ranks(&mut context.pool(), post.id).await?;
 
+    if is_create {
+      let event = WebhookEventType::PostCreated;
+      send_webhook_event(event, Some(post.community_id), &post.0, context).await;
+      send_stream_event(|| StreamEvent::Post {
+        post: post.0.clone(),
+      });
+    }
+
     Ok(())
   }
 }
@@ -12,9 +12,15 @@ use activitypub_federation::{
   protocol::verification::{verify_domains_match, verify_urls_match},
   traits::{ActivityHandler, Actor, Object},
 };
-use lemmy_api_common::context::LemmyContext;
+use lemmy_api_common::{
+  context::LemmyContext,
+  event_stream::StreamEvent,
+  person::PushNotification,
+  push::send_push_notification,
+  send_stream_event::send_stream_event,
+};
 use lemmy_db_schema::source::activity::ActivitySendTargets;
-use lemmy_db_views::structs::PrivateMessageView;
+use lemmy_db_views::structs::{LocalUserView, PrivateMessageView};
 use lemmy_utils::error::{LemmyError, LemmyResult};
 use url::Url;
 
@@ -69,7 +75,26 @@ impl ActivityHandler for CreateOrUpdateChatMessage {
   #[tracing::instrument(skip_all)]
   async fn receive(self, context: &Data<Self::DataType>) -> LemmyResult<()> {
     insert_received_activity(&self.id, context).await?;
-    ApubPrivateMessage::from_json(self.object, context).await?;
+    let private_message = ApubPrivateMessage::from_json(self.object, context).await?;
+    if self.kind == CreateOrUpdateType::Create {
+      let recipient =
+        LocalUserView::read_person(&mut context.pool(), private_message.recipient_id).await?;
+      if let Some(recipient) = recipient {
+        let sender = self.actor.dereference(context).await?;
+        let notification = PushNotification {
+          title: format!("Private message from {}", sender.name),
+          body: private_message.content.clone(),
+          url: format!("{}/inbox", context.settings().get_protocol_and_hostname()),
+        };
+        send_push_notification(recipient.local_user.id, &notification, context).await;
+        let view = PrivateMessageView::read(&mut context.pool(), private_message.id).await?;
+        if let Some(private_message_view) = view {
+          send_stream_event(|| StreamEvent::PrivateMessage {
+            private_message_view: Box::new(private_message_view),
+          });
+        }
+      }
+    }
     Ok(())
   }
 }
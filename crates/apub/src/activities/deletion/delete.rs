@@ -8,12 +8,17 @@ use crate::{
   protocol::{activities::deletion::delete::Delete, IdOrNestedObject},
 };
 use activitypub_federation::{config::Data, kinds::activity::DeleteType, traits::ActivityHandler};
-use lemmy_api_common::context::LemmyContext;
+use lemmy_api_common::{
+  context::LemmyContext,
+  send_webhook::{send_webhook_event, WebhookModAction},
+  utils::{send_mod_action_notification, send_reports_resolved_by_removal_notifications},
+};
 use lemmy_db_schema::{
   source::{
     comment::{Comment, CommentUpdateForm},
     comment_report::CommentReport,
     community::{Community, CommunityUpdateForm},
+    mod_action_notification::ModActionNotificationInsertForm,
     moderator::{
       ModRemoveComment,
       ModRemoveCommentForm,
@@ -26,6 +31,8 @@ use lemmy_db_schema::{
     post_report::PostReport,
   },
   traits::{Crud, Reportable},
+  ModActionNotificationType,
+  WebhookEventType,
 };
 use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
 use url::Url;
@@ -138,15 +145,25 @@ pub(in crate::activities) async fn receive_remove_action(
       .await?;
     }
     DeletableObjects::Post(post) => {
-      PostReport::resolve_all_for_object(&mut context.pool(), post.id, actor.id).await?;
+      let resolved_reports =
+        PostReport::resolve_all_for_object(&mut context.pool(), post.id, actor.id).await?;
+      let notification_form = ModActionNotificationInsertForm {
+        post_id: Some(post.id),
+        reason: reason.clone(),
+        ..ModActionNotificationInsertForm::new(
+          post.creator_id,
+          ModActionNotificationType::RemovePost,
+          Some(post.community_id),
+        )
+      };
       let form = ModRemovePostForm {
         mod_person_id: actor.id,
         post_id: post.id,
         removed: Some(true),
         reason,
       };
-      ModRemovePost::create(&mut context.pool(), &form).await?;
-      Post::update(
+      let entry = ModRemovePost::create(&mut context.pool(), &form).await?;
+      let post = Post::update(
         &mut context.pool(),
         post.id,
         &PostUpdateForm {
@@ -155,17 +172,44 @@ pub(in crate::activities) async fn receive_remove_action(
         },
       )
       .await?;
+      send_mod_action_notification(&notification_form, actor.id, &mut context.pool()).await?;
+      send_reports_resolved_by_removal_notifications(
+        resolved_reports.iter().map(|r| r.creator_id).collect(),
+        &post,
+        None,
+        true,
+        actor.id,
+        &mut context.pool(),
+      )
+      .await?;
+      let community_id = Some(post.community_id);
+      let action = WebhookModAction::RemovePost { entry, post };
+      send_webhook_event(WebhookEventType::ModAction, community_id, &action, context).await;
     }
     DeletableObjects::Comment(comment) => {
-      CommentReport::resolve_all_for_object(&mut context.pool(), comment.id, actor.id).await?;
+      let resolved_reports =
+        CommentReport::resolve_all_for_object(&mut context.pool(), comment.id, actor.id).await?;
+      let post = Post::read(&mut context.pool(), comment.post_id)
+        .await?
+        .ok_or(LemmyErrorType::CouldntFindPost)?;
+      let notification_form = ModActionNotificationInsertForm {
+        post_id: Some(post.id),
+        comment_id: Some(comment.id),
+        reason: reason.clone(),
+        ..ModActionNotificationInsertForm::new(
+          comment.creator_id,
+          ModActionNotificationType::RemoveComment,
+          Some(post.community_id),
+        )
+      };
       let form = ModRemoveCommentForm {
         mod_person_id: actor.id,
         comment_id: comment.id,
         removed: Some(true),
         reason,
       };
-      ModRemoveComment::create(&mut context.pool(), &form).await?;
-      Comment::update(
+      let entry = ModRemoveComment::create(&mut context.pool(), &form).await?;
+      let comment = Comment::update(
         &mut context.pool(),
         comment.id,
         &CommentUpdateForm {
@@ -174,6 +218,19 @@ pub(in crate::activities) async fn receive_remove_action(
         },
       )
       .await?;
+      send_mod_action_notification(&notification_form, actor.id, &mut context.pool()).await?;
+      send_reports_resolved_by_removal_notifications(
+        resolved_reports.iter().map(|r| r.creator_id).collect(),
+        &post,
+        Some(comment.id),
+        true,
+        actor.id,
+        &mut context.pool(),
+      )
+      .await?;
+      let action = WebhookModAction::RemoveComment { entry, comment };
+      let community_id = Some(post.community_id);
+      send_webhook_event(WebhookEventType::ModAction, community_id, &action, context).await;
     }
     // TODO these need to be implemented yet, for now, return errors
     DeletableObjects::PrivateMessage(_) => Err(LemmyErrorType::CouldntFindPrivateMessage)?,
@@ -0,0 +1,81 @@
+use crate::{
+  fetcher::resolve_actor_identifier,
+  objects::{community::ApubCommunity, person::ApubPerson},
+};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  saved_search::{CreateSavedSearch, SavedSearchResponse},
+  search_operators::SearchOperators,
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    person::Person,
+    saved_search::{SavedSearch, SavedSearchInsertForm},
+  },
+  traits::Crud,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_saved_search,
+};
+
+/// Maximum number of saved searches per user, as each of them is checked regularly.
+const MAX_SAVED_SEARCHES: usize = 25;
+
+/// The author and community operators are resolved when saving the search, so that the scheduled
+/// job doesn't have to fetch them.
+#[tracing::instrument(skip(context))]
+pub async fn create_saved_search(
+  data: Json<CreateSavedSearch>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SavedSearchResponse>> {
+  let q = data.q.trim().to_string();
+  is_valid_saved_search(&q)?;
+
+  let existing = SavedSearch::list(&mut context.pool(), local_user_view.local_user.id).await?;
+  if existing.len() >= MAX_SAVED_SEARCHES {
+    Err(LemmyErrorType::TooManySavedSearches)?
+  }
+
+  // Operators in the query take precedence over the separate parameters, like in a normal search
+  let operators = SearchOperators::parse(&q);
+  let user = Some(local_user_view.clone());
+  let community_id = if let Some(name) = &operators.community {
+    Some(resolve_actor_identifier::<ApubCommunity, Community>(name, &context, &user, false).await?)
+      .map(|c| c.id)
+  } else {
+    data.community_id
+  };
+  let creator_id = if let Some(name) = &operators.author {
+    Some(resolve_actor_identifier::<ApubPerson, Person>(name, &context, &user, false).await?)
+      .map(|p| p.id)
+  } else {
+    data.creator_id
+  };
+
+  // Without any search term or filter, every new post would match
+  if operators.search_term.is_none()
+    && operators.site.is_none()
+    && community_id.is_none()
+    && creator_id.is_none()
+  {
+    Err(LemmyErrorType::InvalidSavedSearch)?
+  }
+
+  let form = SavedSearchInsertForm::new(
+    local_user_view.local_user.id,
+    q,
+    community_id,
+    creator_id,
+    data.post_title_only.unwrap_or_default(),
+    data.send_email.unwrap_or_default(),
+  );
+  let saved_search = SavedSearch::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(SavedSearchResponse { saved_search }))
+}
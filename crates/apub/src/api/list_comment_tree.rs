@@ -0,0 +1,248 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  comment::{CommentTreeNode, GetCommentTree, GetCommentTreeResponse},
+  context::LemmyContext,
+  utils::check_private_instance,
+};
+use lemmy_db_schema::{
+  newtypes::CommentId,
+  source::{comment::Comment, local_site::LocalSite},
+  traits::Crud,
+  utils::limit_and_offset,
+};
+use lemmy_db_views::{
+  comment_view::{CommentQuery, CommentTreeEntry},
+  structs::{LocalUserView, PaginationCursor},
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// Used if the request doesn't specify max_depth.
+const DEFAULT_MAX_DEPTH: i32 = 8;
+/// Deeper branches have to be loaded with a separate request for their parent.
+const MAX_DEPTH_LIMIT: i32 = 16;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_comment_tree(
+  data: Query<GetCommentTree>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetCommentTreeResponse>> {
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  // If a parent_id is given, fetch the comment to get the path
+  let parent_path = if let Some(parent_id) = data.parent_id {
+    Some(
+      Comment::read(&mut context.pool(), parent_id)
+        .await?
+        .ok_or(LemmyErrorType::CouldntFindComment)?
+        .path,
+    )
+  } else {
+    None
+  };
+  if data.post_id.is_none() && parent_path.is_none() {
+    Err(LemmyErrorType::NoIdGiven)?
+  }
+
+  let (children_limit, _) = limit_and_offset(None, data.children_limit)?;
+  let page_after = if let Some(pa) = &data.page_cursor {
+    Some(pa.read_comment(&mut context.pool()).await?)
+  } else {
+    None
+  };
+
+  let (comments, selected) = CommentQuery {
+    sort: data.sort,
+    max_depth: Some(
+      data
+        .max_depth
+        .unwrap_or(DEFAULT_MAX_DEPTH)
+        .clamp(1, MAX_DEPTH_LIMIT),
+    ),
+    children_limit: Some(children_limit),
+    parent_path,
+    post_id: data.post_id,
+    local_user: local_user_view.as_ref().map(|l| &l.local_user),
+    page_after,
+    ..Default::default()
+  }
+  .list_tree(&mut context.pool())
+  .await
+  .with_lemmy_type(LemmyErrorType::CouldntGetComments)?;
+
+  // The cursors are built from the comments which were selected for the tree, including those
+  // which were filtered out afterwards, eg because their creator is blocked. Otherwise the
+  // branches after them could never be loaded.
+  //
+  // The first level are the replies to the parent, or the top level comments of the post
+  let first_level = selected_replies(&selected, data.parent_id);
+  let next_page = if first_level.len() as i64 >= children_limit {
+    first_level
+      .last()
+      .map(|c| PaginationCursor::after_comment_id(c.id))
+  } else {
+    None
+  };
+
+  let comments = comments
+    .iter()
+    .map(|c| {
+      let replies = selected_replies(&selected, Some(c.comment.id));
+      // Every selected reply accounts for itself and all of its own replies
+      let selected_count: i64 = replies.iter().map(|r| 1 + i64::from(r.child_count)).sum();
+      let missing_replies = (i64::from(c.counts.child_count) - selected_count).max(0);
+      let next_page = if missing_replies > 0 {
+        replies
+          .last()
+          .map(|r| PaginationCursor::after_comment_id(r.id))
+      } else {
+        None
+      };
+      CommentTreeNode {
+        comment_view: c.clone(),
+        missing_replies,
+        next_page,
+      }
+    })
+    .collect();
+
+  Ok(Json(GetCommentTreeResponse {
+    comments,
+    next_page,
+  }))
+}
+
+/// The direct replies to a comment which were selected for the tree, in their order, or the top
+/// level comments if `None`.
+fn selected_replies(
+  selected: &[CommentTreeEntry],
+  parent_id: Option<CommentId>,
+) -> Vec<&CommentTreeEntry> {
+  let mut replies: Vec<_> = selected
+    .iter()
+    .filter(|e| e.parent_id() == parent_id)
+    .collect();
+  replies.sort_by_key(|e| e.position);
+  replies
+}
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use super::*;
+  use lemmy_api_common::comment::CommentTreeNode;
+  use lemmy_db_schema::{
+    source::{
+      comment::CommentInsertForm,
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      local_site::LocalSiteInsertForm,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+      site::{Site, SiteInsertForm},
+    },
+    CommentSortType,
+  };
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  /// The comment ids of the nodes, with their missing replies and whether they have a cursor.
+  fn summary(nodes: &[CommentTreeNode]) -> Vec<(CommentId, i64, bool)> {
+    nodes
+      .iter()
+      .map(|n| (n.comment_view.comment.id, n.missing_replies, n.next_page.is_some()))
+      .collect()
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_comment_tree() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let site_form = SiteInsertForm::builder()
+      .name("test site".to_string())
+      .instance_id(instance.id)
+      .build();
+    let site = Site::create(pool, &site_form).await?;
+    let local_site_form = LocalSiteInsertForm::builder().site_id(site.id).build();
+    LocalSite::create(pool, &local_site_form).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "comment_tree_person");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::builder()
+      .name("comment_tree_community".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(instance.id)
+      .build();
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::builder()
+      .name("comment tree".to_string())
+      .creator_id(person.id)
+      .community_id(community.id)
+      .build();
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::builder()
+      .content("comment".to_string())
+      .creator_id(person.id)
+      .post_id(post.id)
+      .build();
+
+    // Three top level comments, the first one with three replies
+    let a = Comment::create(pool, &comment_form, None).await?;
+    let a1 = Comment::create(pool, &comment_form, Some(&a.path)).await?;
+    let a2 = Comment::create(pool, &comment_form, Some(&a.path)).await?;
+    let a3 = Comment::create(pool, &comment_form, Some(&a.path)).await?;
+    let b = Comment::create(pool, &comment_form, None).await?;
+    let c = Comment::create(pool, &comment_form, None).await?;
+
+    let form = GetCommentTree {
+      post_id: Some(post.id),
+      sort: Some(CommentSortType::Old),
+      children_limit: Some(2),
+      ..Default::default()
+    };
+    let tree = list_comment_tree(Query(form.clone()), context.reset_request_count(), None).await?;
+    // Ordered by parent first, the third reply is missing
+    let expected = vec![
+      (a.id, 1, true),
+      (b.id, 0, false),
+      (a1.id, 0, false),
+      (a2.id, 0, false),
+    ];
+    assert_eq!(expected, summary(&tree.comments));
+    assert_eq!(
+      Some(PaginationCursor::after_comment_id(b.id)),
+      tree.next_page
+    );
+
+    // The next page of the top level
+    let next_form = GetCommentTree {
+      page_cursor: tree.next_page.clone(),
+      ..form.clone()
+    };
+    let next = list_comment_tree(Query(next_form), context.reset_request_count(), None).await?;
+    assert_eq!(vec![(c.id, 0, false)], summary(&next.comments));
+    assert_eq!(None, next.next_page);
+
+    // The missing reply of the first comment
+    let replies_form = GetCommentTree {
+      post_id: None,
+      parent_id: Some(a.id),
+      page_cursor: tree.comments[0].next_page.clone(),
+      ..form
+    };
+    let replies =
+      list_comment_tree(Query(replies_form), context.reset_request_count(), None).await?;
+    assert_eq!(vec![(a3.id, 0, false)], summary(&replies.comments));
+    assert_eq!(None, replies.next_page);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
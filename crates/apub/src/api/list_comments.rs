@@ -14,7 +14,10 @@ use lemmy_db_schema::{
   source::{comment::Comment, community::Community, local_site::LocalSite},
   traits::Crud,
 };
-use lemmy_db_views::{comment_view::CommentQuery, structs::LocalUserView};
+use lemmy_db_views::{
+  comment_view::CommentQuery,
+  structs::{LocalUserView, PaginationCursor},
+};
 use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult};
 
 #[tracing::instrument(skip(context))]
@@ -68,6 +71,13 @@ pub async fn list_comments(
     None
   };
 
+  // parse pagination token
+  let page_after = if let Some(pa) = &data.page_cursor {
+    Some(pa.read_comment(&mut context.pool()).await?)
+  } else {
+    None
+  };
+
   let parent_path_cloned = parent_path.clone();
   let post_id = data.post_id;
   let local_user = local_user_view.as_ref().map(|l| &l.local_user);
@@ -85,11 +95,22 @@ pub async fn list_comments(
     local_user,
     page,
     limit,
+    page_after,
     ..Default::default()
   }
   .list(&mut context.pool())
   .await
   .with_lemmy_type(LemmyErrorType::CouldntGetComments)?;
 
-  Ok(Json(GetCommentsResponse { comments }))
+  // if this page wasn't empty, then there is a next page after the last comment on this page.
+  // comment trees can't be paginated.
+  let next_page = if max_depth.is_none() {
+    comments.last().map(PaginationCursor::after_comment)
+  } else {
+    None
+  };
+  Ok(Json(GetCommentsResponse {
+    comments,
+    next_page,
+  }))
 }
@@ -5,6 +5,8 @@ use lemmy_db_schema::{
   SortType,
 };
 
+pub mod create_saved_search;
+pub mod list_comment_tree;
 pub mod list_comments;
 pub mod list_posts;
 pub mod read_community;
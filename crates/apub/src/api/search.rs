@@ -1,12 +1,20 @@
-use crate::{fetcher::resolve_actor_identifier, objects::community::ApubCommunity};
+use crate::{
+  fetcher::resolve_actor_identifier,
+  objects::{community::ApubCommunity, person::ApubPerson},
+};
 use activitypub_federation::config::Data;
 use actix_web::web::{Json, Query};
 use lemmy_api_common::{
   context::LemmyContext,
+  search_operators::SearchOperators,
   site::{Search, SearchResponse},
   utils::{check_private_instance, is_admin},
 };
-use lemmy_db_schema::{source::community::Community, utils::post_to_comment_sort_type, SearchType};
+use lemmy_db_schema::{
+  source::{community::Community, person::Person},
+  utils::post_to_comment_sort_type,
+  SearchType,
+};
 use lemmy_db_views::{
   comment_view::CommentQuery,
   post_view::PostQuery,
@@ -40,12 +48,15 @@ pub async fn search(
   // TODO no clean / non-nsfw searching rn
 
   let q = data.q.clone();
+  let operators = SearchOperators::parse(&q);
   let page = data.page;
   let limit = data.limit;
   let sort = data.sort;
   let listing_type = data.listing_type;
   let search_type = data.type_.unwrap_or(SearchType::All);
-  let community_id = if let Some(name) = &data.community_name {
+  // Operators in the query take precedence over the separate parameters
+  let community_name = operators.community.as_ref().or(data.community_name.as_ref());
+  let community_id = if let Some(name) = community_name {
     Some(
       resolve_actor_identifier::<ApubCommunity, Community>(name, &context, &local_user_view, false)
         .await?,
@@ -54,7 +65,15 @@ pub async fn search(
   } else {
     data.community_id
   };
-  let creator_id = data.creator_id;
+  let creator_id = if let Some(name) = &operators.author {
+    Some(
+      resolve_actor_identifier::<ApubPerson, Person>(name, &context, &local_user_view, false)
+        .await?,
+    )
+    .map(|p| p.id)
+  } else {
+    data.creator_id
+  };
   let local_user = local_user_view.as_ref().map(|l| &l.local_user);
   let post_title_only = data.post_title_only;
 
@@ -64,7 +83,11 @@ pub async fn search(
     community_id: (community_id),
     creator_id: (creator_id),
     local_user,
-    search_term: (Some(q.clone())),
+    search_term: operators.search_term.clone(),
+    url_domain: operators.site.clone(),
+    published_after: operators.after,
+    published_before: operators.before,
+    exclude_terms: operators.exclude_terms.clone(),
     page: (page),
     limit: (limit),
     title_only: (post_title_only),
@@ -74,7 +97,10 @@ pub async fn search(
   let comment_query = CommentQuery {
     sort: (sort.map(post_to_comment_sort_type)),
     listing_type: (listing_type),
-    search_term: (Some(q.clone())),
+    search_term: operators.search_term.clone(),
+    published_after: operators.after,
+    published_before: operators.before,
+    exclude_terms: operators.exclude_terms.clone(),
     community_id: (community_id),
     creator_id: (creator_id),
     local_user,
@@ -82,11 +108,13 @@ pub async fn search(
     limit: (limit),
     ..Default::default()
   };
+  // Comments don't link anywhere, so none of them can match a site filter
+  let comments_match_site = operators.site.is_none();
 
   let community_query = CommunityQuery {
     sort: (sort),
     listing_type: (listing_type),
-    search_term: (Some(q.clone())),
+    search_term: operators.search_term.clone(),
     local_user,
     is_mod_or_admin: (is_admin),
     page: (page),
@@ -96,7 +124,7 @@ pub async fn search(
 
   let person_query = PersonQuery {
     sort,
-    search_term: (Some(q.clone())),
+    search_term: operators.search_term.clone(),
     listing_type: (listing_type),
     page: (page),
     limit: (limit),
@@ -109,7 +137,9 @@ pub async fn search(
         .await?;
     }
     SearchType::Comments => {
-      comments = comment_query.list(&mut context.pool()).await?;
+      if comments_match_site {
+        comments = comment_query.list(&mut context.pool()).await?;
+      }
     }
     SearchType::Communities => {
       communities = community_query
@@ -120,15 +150,18 @@ pub async fn search(
       users = person_query.list(&mut context.pool()).await?;
     }
     SearchType::All => {
-      // If the community or creator is included, dont search communities or users
+      // If the community or creator is included, dont search communities or users. The same
+      // applies to the other operators, which only filter posts and comments.
       let community_or_creator_included =
-        data.community_id.is_some() || data.community_name.is_some() || data.creator_id.is_some();
+        community_id.is_some() || creator_id.is_some() || operators.filters_content();
 
       posts = posts_query
         .list(&local_site.site, &mut context.pool())
         .await?;
 
-      comments = comment_query.list(&mut context.pool()).await?;
+      if comments_match_site {
+        comments = comment_query.list(&mut context.pool()).await?;
+      }
 
       communities = if community_or_creator_included {
         vec![]
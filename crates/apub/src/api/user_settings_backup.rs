@@ -130,6 +130,20 @@ pub async fn import_settings(
     auto_expand: data.settings.as_ref().map(|s| s.auto_expand),
     infinite_scroll_enabled: data.settings.as_ref().map(|s| s.infinite_scroll_enabled),
     post_listing_mode: data.settings.as_ref().map(|s| s.post_listing_mode),
+    email_notification_frequency: data
+      .settings
+      .as_ref()
+      .map(|s| s.email_notification_frequency),
+    send_reply_emails: data.settings.as_ref().map(|s| s.send_reply_emails),
+    send_mention_emails: data.settings.as_ref().map(|s| s.send_mention_emails),
+    send_private_message_emails: data
+      .settings
+      .as_ref()
+      .map(|s| s.send_private_message_emails),
+    report_resolved_notifications: data
+      .settings
+      .as_ref()
+      .map(|s| s.report_resolved_notifications),
     ..Default::default()
   };
   LocalUser::update(
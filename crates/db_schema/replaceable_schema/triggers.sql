@@ -519,6 +519,96 @@ CREATE TRIGGER aggregates
     FOR EACH ROW
     EXECUTE FUNCTION r.site_aggregates_from_site ();
 
+-- These triggers keep the full text search vectors up to date. Communities and persons don't have a language,
+-- so they aren't stemmed.
+CREATE FUNCTION r.post_search_from_post ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    INSERT INTO post_search (post_id, search_vector)
+    SELECT
+        NEW.id,
+        r.search_vector (r.text_search_config (language.code), NEW.name, NEW.body)
+    FROM
+        language
+    WHERE
+        language.id = NEW.language_id
+    ON CONFLICT (post_id)
+        DO UPDATE SET
+            search_vector = excluded.search_vector;
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER search
+    AFTER INSERT OR UPDATE OF name, body, language_id ON post
+    FOR EACH ROW
+    EXECUTE FUNCTION r.post_search_from_post ();
+
+CREATE FUNCTION r.comment_search_from_comment ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    INSERT INTO comment_search (comment_id, search_vector)
+    SELECT
+        NEW.id,
+        r.search_vector (r.text_search_config (language.code), NEW.content, NULL)
+    FROM
+        language
+    WHERE
+        language.id = NEW.language_id
+    ON CONFLICT (comment_id)
+        DO UPDATE SET
+            search_vector = excluded.search_vector;
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER search
+    AFTER INSERT OR UPDATE OF content, language_id ON comment
+    FOR EACH ROW
+    EXECUTE FUNCTION r.comment_search_from_comment ();
+
+CREATE FUNCTION r.community_search_from_community ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    INSERT INTO community_search (community_id, search_vector)
+        VALUES (NEW.id, r.search_vector ('simple', NEW.name || ' ' || NEW.title, NEW.description))
+    ON CONFLICT (community_id)
+        DO UPDATE SET
+            search_vector = excluded.search_vector;
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER search
+    AFTER INSERT OR UPDATE OF name, title, description ON community
+    FOR EACH ROW
+    EXECUTE FUNCTION r.community_search_from_community ();
+
+CREATE FUNCTION r.person_search_from_person ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    INSERT INTO person_search (person_id, search_vector)
+        VALUES (NEW.id, r.search_vector ('simple', NEW.name || ' ' || coalesce(NEW.display_name, ''), NEW.bio))
+    ON CONFLICT (person_id)
+        DO UPDATE SET
+            search_vector = excluded.search_vector;
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER search
+    AFTER INSERT OR UPDATE OF name, display_name, bio ON person
+    FOR EACH ROW
+    EXECUTE FUNCTION r.person_search_from_person ();
+
 -- Change the order of some cascading deletions to make deletion triggers run before the deletion of rows that the triggers need to read
 CREATE FUNCTION r.delete_comments_before_post ()
     RETURNS TRIGGER
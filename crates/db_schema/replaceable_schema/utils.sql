@@ -45,6 +45,89 @@ CREATE FUNCTION r.scaled_rank (score numeric, published timestamp with time zone
         r.hot_rank (score, published) / log(2 + users_active_month)
 );
 
+-- The full text search configuration for a language code. Languages without one use `simple`, which only
+-- lowercases the words.
+CREATE FUNCTION r.text_search_config (code text)
+    RETURNS regconfig
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN (CASE code
+    WHEN 'ar' THEN
+        'arabic'
+    WHEN 'da' THEN
+        'danish'
+    WHEN 'de' THEN
+        'german'
+    WHEN 'el' THEN
+        'greek'
+    WHEN 'en' THEN
+        'english'
+    WHEN 'es' THEN
+        'spanish'
+    WHEN 'fi' THEN
+        'finnish'
+    WHEN 'fr' THEN
+        'french'
+    WHEN 'ga' THEN
+        'irish'
+    WHEN 'hu' THEN
+        'hungarian'
+    WHEN 'id' THEN
+        'indonesian'
+    WHEN 'it' THEN
+        'italian'
+    WHEN 'lt' THEN
+        'lithuanian'
+    WHEN 'nb' THEN
+        'norwegian'
+    WHEN 'ne' THEN
+        'nepali'
+    WHEN 'nl' THEN
+        'dutch'
+    WHEN 'nn' THEN
+        'norwegian'
+    WHEN 'no' THEN
+        'norwegian'
+    WHEN 'pt' THEN
+        'portuguese'
+    WHEN 'ro' THEN
+        'romanian'
+    WHEN 'ru' THEN
+        'russian'
+    WHEN 'sv' THEN
+        'swedish'
+    WHEN 'ta' THEN
+        'tamil'
+    WHEN 'tr' THEN
+        'turkish'
+    ELSE
+        'simple'
+    END)::regconfig;
+
+-- The words of `content` stemmed in the given language, and unchanged unless the language is already `simple`
+CREATE FUNCTION r.search_words (config regconfig, content text)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN CASE WHEN content IS NULL THEN
+        ''::tsvector
+    WHEN config = 'simple'::regconfig THEN
+        to_tsvector('simple', content)
+    ELSE
+        to_tsvector(config, content) || to_tsvector('simple', content)
+    END;
+
+-- Words in `a` (like titles) rank higher than those in `b` (like bodies). Besides the words stemmed in the
+-- given language, the unchanged words are included so that searches in other languages can match too.
+CREATE FUNCTION r.search_vector (config regconfig, a text, b text)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN setweight(r.search_words (config, a), 'A') || setweight(r.search_words (config, b), 'B');
+
+-- Matches the unchanged words of the search term, or the words stemmed in the language of the user.
+CREATE FUNCTION r.search_query (search_term text, language_code text)
+    RETURNS tsquery
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN websearch_to_tsquery('simple', search_term) || websearch_to_tsquery(r.text_search_config (language_code), search_term);
+
 -- For tables with `deleted` and `removed` columns, this function determines which rows to include in a count.
 CREATE FUNCTION r.is_counted (item record)
     RETURNS bool
@@ -64,6 +147,12 @@ CREATE FUNCTION r.local_url (url_path text)
 current_setting('lemmy.protocol_and_hostname') || url_path
 );
 
+-- Converts webhook event names, because diesel-async can't bind arrays of enums
+CREATE FUNCTION r.webhook_events (events text[])
+    RETURNS webhook_event_type_enum[]
+    LANGUAGE sql
+    IMMUTABLE PARALLEL SAFE RETURN events::webhook_event_type_enum[];
+
 -- This function creates statement-level triggers for all operation types. It's designed this way
 -- because of these limitations:
 --   * A trigger that uses transition tables can only handle 1 operation type.
@@ -0,0 +1,172 @@
+use crate::{
+  diesel::OptionalExtension,
+  newtypes::{ApiTokenId, LocalUserId},
+  schema::api_token::dsl::{api_token, expires, id, last_used, local_user_id, token_hash},
+  source::api_token::{ApiToken, ApiTokenInsertForm},
+  utils::{get_conn, now, DbPool},
+};
+use diesel::{
+  delete,
+  insert_into,
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use sha2::{Digest, Sha256};
+
+/// All api tokens start with this prefix, so that they can be told apart from login jwts.
+const API_TOKEN_PREFIX: &str = "lemmy_pat_";
+
+impl ApiToken {
+  pub async fn create(pool: &mut DbPool<'_>, form: &ApiTokenInsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(api_token)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Returns the matching token if it exists and is not expired, and marks it as used.
+  pub async fn validate(pool: &mut DbPool<'_>, token: &str) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      api_token
+        .filter(token_hash.eq(Self::hash_token(token)))
+        .filter(expires.is_null().or(expires.gt(now().nullable()))),
+    )
+    .set(last_used.eq(now().nullable()))
+    .get_result::<Self>(conn)
+    .await
+    .optional()
+  }
+
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    local_user_id_: LocalUserId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    api_token
+      .filter(local_user_id.eq(local_user_id_))
+      .order_by(id)
+      .get_results(conn)
+      .await
+  }
+
+  /// Revoke a single token, only if it belongs to the given user.
+  pub async fn revoke(
+    pool: &mut DbPool<'_>,
+    local_user_id_: LocalUserId,
+    api_token_id: ApiTokenId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      api_token
+        .find(api_token_id)
+        .filter(local_user_id.eq(local_user_id_)),
+    )
+    .execute(conn)
+    .await
+  }
+
+  /// Generates a new random token. Only its hash should be stored.
+  pub fn generate_token() -> String {
+    format!(
+      "{API_TOKEN_PREFIX}{}{}",
+      uuid::Uuid::new_v4().simple(),
+      uuid::Uuid::new_v4().simple()
+    )
+  }
+
+  pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+  }
+
+  /// Check if the auth header or cookie holds an api token instead of a login jwt.
+  pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(API_TOKEN_PREFIX)
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{
+    source::{
+      api_token::{ApiToken, ApiTokenInsertForm},
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+    ApiTokenScope,
+  };
+  use chrono::{Duration, Utc};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_api_token() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "api_token_bot");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_local_user = LocalUserInsertForm::test_form(inserted_person.id);
+    let inserted_local_user = LocalUser::create(pool, &new_local_user, vec![]).await?;
+
+    let token = ApiToken::generate_token();
+    assert!(ApiToken::is_api_token(&token));
+    let form = ApiTokenInsertForm::new(
+      inserted_local_user.id,
+      "my bot".to_string(),
+      ApiToken::hash_token(&token),
+      vec![ApiTokenScope::Read, ApiTokenScope::Vote],
+    );
+    let inserted_token = ApiToken::create(pool, &form).await?;
+    assert_eq!(
+      vec![ApiTokenScope::Read, ApiTokenScope::Vote],
+      inserted_token.scopes
+    );
+    assert!(inserted_token.last_used.is_none());
+
+    // The token is found by its plain value, and marked as used
+    let validated = ApiToken::validate(pool, &token).await?.unwrap();
+    assert_eq!(inserted_token.id, validated.id);
+    assert!(validated.last_used.is_some());
+    assert!(ApiToken::validate(pool, "lemmy_pat_wrong").await?.is_none());
+
+    // Expired tokens are not valid anymore
+    let expired_token = ApiToken::generate_token();
+    let expired_form = ApiTokenInsertForm {
+      expires: Some(Utc::now() - Duration::days(1)),
+      ..ApiTokenInsertForm::new(
+        inserted_local_user.id,
+        "expired".to_string(),
+        ApiToken::hash_token(&expired_token),
+        vec![ApiTokenScope::Post],
+      )
+    };
+    ApiToken::create(pool, &expired_form).await?;
+    assert!(ApiToken::validate(pool, &expired_token).await?.is_none());
+
+    let list = ApiToken::list(pool, inserted_local_user.id).await?;
+    assert_eq!(2, list.len());
+
+    let revoked = ApiToken::revoke(pool, inserted_local_user.id, inserted_token.id).await?;
+    assert_eq!(1, revoked);
+    assert!(ApiToken::validate(pool, &token).await?.is_none());
+
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
@@ -61,16 +61,20 @@ impl Reportable for CommentReport {
     pool: &mut DbPool<'_>,
     comment_id_: CommentId,
     by_resolver_id: PersonId,
-  ) -> Result<usize, Error> {
+  ) -> Result<Vec<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
-    update(comment_report.filter(comment_id.eq(comment_id_)))
-      .set((
-        resolved.eq(true),
-        resolver_id.eq(by_resolver_id),
-        updated.eq(naive_now()),
-      ))
-      .execute(conn)
-      .await
+    update(
+      comment_report
+        .filter(comment_id.eq(comment_id_))
+        .filter(resolved.eq(false)),
+    )
+    .set((
+      resolved.eq(true),
+      resolver_id.eq(by_resolver_id),
+      updated.eq(naive_now()),
+    ))
+    .get_results::<Self>(conn)
+    .await
   }
 
   /// unresolve a comment report
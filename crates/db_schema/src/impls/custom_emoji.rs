@@ -1,16 +1,23 @@
 use crate::{
-  newtypes::CustomEmojiId,
+  newtypes::{CustomEmojiId, LocalSiteId},
   schema::{
-    custom_emoji::dsl::custom_emoji,
-    custom_emoji_keyword::dsl::{custom_emoji_id, custom_emoji_keyword},
+    custom_emoji::dsl::{custom_emoji, id, local_site_id, shortcode},
+    custom_emoji_keyword::dsl::{custom_emoji_id, custom_emoji_keyword, keyword},
   },
   source::{
     custom_emoji::{CustomEmoji, CustomEmojiInsertForm, CustomEmojiUpdateForm},
     custom_emoji_keyword::{CustomEmojiKeyword, CustomEmojiKeywordInsertForm},
   },
-  utils::{get_conn, DbPool},
+  utils::{functions::lower, get_conn, prefix_search, DbPool},
+};
+use diesel::{
+  dsl::{exists, insert_into},
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  QueryDsl,
+  TextExpressionMethods,
 };
-use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 
 impl CustomEmoji {
@@ -38,6 +45,32 @@ impl CustomEmoji {
       .execute(conn)
       .await
   }
+
+  /// Emojis whose shortcode or one of whose keywords starts with the prefix. An exact shortcode
+  /// match comes first, then other shortcode matches, then keyword matches.
+  pub async fn autocomplete(
+    pool: &mut DbPool<'_>,
+    for_local_site_id: LocalSiteId,
+    prefix: &str,
+    limit: i64,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let pattern = prefix_search(prefix);
+    let keyword_matches = exists(
+      custom_emoji_keyword
+        .filter(custom_emoji_id.eq(id))
+        .filter(lower(keyword).like(pattern.clone())),
+    );
+    custom_emoji
+      .filter(local_site_id.eq(for_local_site_id))
+      .filter(lower(shortcode).like(pattern.clone()).or(keyword_matches))
+      .order_by(lower(shortcode).eq(prefix.to_lowercase()).desc())
+      .then_order_by(lower(shortcode).like(pattern).desc())
+      .then_order_by(shortcode)
+      .limit(limit)
+      .load::<Self>(conn)
+      .await
+  }
 }
 
 impl CustomEmojiKeyword {
@@ -58,3 +91,94 @@ impl CustomEmojiKeyword {
       .await
   }
 }
+
+#[cfg(test)]
+mod tests {
+
+  use crate::{
+    source::{
+      custom_emoji::{CustomEmoji, CustomEmojiInsertForm},
+      custom_emoji_keyword::{CustomEmojiKeyword, CustomEmojiKeywordInsertForm},
+      instance::Instance,
+      local_site::{LocalSite, LocalSiteInsertForm},
+      site::{Site, SiteInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_autocomplete() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let site_form = SiteInsertForm::builder()
+      .name("test site".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let site = Site::create(pool, &site_form).await?;
+    let local_site_form = LocalSiteInsertForm::builder().site_id(site.id).build();
+    let local_site = LocalSite::create(pool, &local_site_form).await?;
+
+    for (name, keywords) in [
+      ("cake", vec!["party", "birthday"]),
+      ("partyparrot", vec![]),
+      ("party", vec![]),
+      ("dog", vec!["pet"]),
+    ] {
+      let emoji_form = CustomEmojiInsertForm::builder()
+        .local_site_id(local_site.id)
+        .shortcode(name.to_string())
+        .image_url(Url::parse(&format!("https://example.com/{name}.png"))?.into())
+        .alt_text(name.to_string())
+        .category("test".to_string())
+        .build();
+      let emoji = CustomEmoji::create(pool, &emoji_form).await?;
+      let keyword_forms = keywords
+        .into_iter()
+        .map(|k| {
+          CustomEmojiKeywordInsertForm::builder()
+            .custom_emoji_id(emoji.id)
+            .keyword(k.to_string())
+            .build()
+        })
+        .collect();
+      CustomEmojiKeyword::create(pool, keyword_forms).await?;
+    }
+
+    let shortcodes = |emojis: Vec<CustomEmoji>| {
+      emojis
+        .into_iter()
+        .map(|e| e.shortcode)
+        .collect::<Vec<_>>()
+    };
+    // The exact shortcode match first, then other shortcode matches, then keyword matches
+    assert_eq!(
+      vec!["party", "partyparrot", "cake"],
+      shortcodes(CustomEmoji::autocomplete(pool, local_site.id, "party", 10).await?)
+    );
+    assert_eq!(
+      vec!["party", "partyparrot", "cake"],
+      shortcodes(CustomEmoji::autocomplete(pool, local_site.id, "PART", 10).await?)
+    );
+    assert_eq!(
+      vec!["cake"],
+      shortcodes(CustomEmoji::autocomplete(pool, local_site.id, "birth", 10).await?)
+    );
+    assert_eq!(
+      vec!["party"],
+      shortcodes(CustomEmoji::autocomplete(pool, local_site.id, "party", 1).await?)
+    );
+
+    Site::delete(pool, site.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    LocalSite::delete(pool).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,191 @@
+use crate::{
+  schema::email_queue::dsl::{
+    attempts,
+    email_queue,
+    failed,
+    id,
+    last_error,
+    next_attempt,
+    published,
+  },
+  source::email_queue::{EmailQueue, EmailQueueInsertForm, FailedEmail},
+  utils::{get_conn, limit_and_offset, now, DbPool},
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  dsl::IntervalDsl,
+  insert_into,
+  result::Error,
+  ExpressionMethods,
+  QueryDsl,
+  SelectableHelper,
+};
+use diesel_async::RunQueryDsl;
+
+impl EmailQueue {
+  pub async fn create(pool: &mut DbPool<'_>, form: &EmailQueueInsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(email_queue)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Take the emails which are due for delivery, and count the attempt. They are not returned
+  /// again for a few minutes, so that other workers don't send them at the same time.
+  pub async fn claim_due(pool: &mut DbPool<'_>, limit: i64) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let due = email_queue
+            .select(id)
+            .filter(failed.eq(false))
+            .filter(next_attempt.le(now()))
+            .order_by(id)
+            .limit(limit)
+            .for_update()
+            .skip_locked()
+            .load::<i32>(conn)
+            .await?;
+          diesel::update(email_queue.filter(id.eq_any(due)))
+            .set((
+              attempts.eq(attempts + 1),
+              next_attempt.eq(now() + 10.minutes()),
+            ))
+            .get_results::<Self>(conn)
+            .await
+        }) as _
+      })
+      .await
+  }
+
+  /// Remove a successfully delivered email.
+  pub async fn delete(pool: &mut DbPool<'_>, email_id: i32) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(email_queue.find(email_id))
+      .execute(conn)
+      .await
+  }
+
+  /// Store the error of a failed attempt. Without a time for the next attempt, delivery is given
+  /// up.
+  pub async fn record_error(
+    pool: &mut DbPool<'_>,
+    email_id: i32,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let query = diesel::update(email_queue.find(email_id));
+    match retry_at {
+      Some(retry_at) => {
+        query
+          .set((last_error.eq(error), next_attempt.eq(retry_at)))
+          .get_result::<Self>(conn)
+          .await
+      }
+      None => {
+        query
+          .set((last_error.eq(error), failed.eq(true)))
+          .get_result::<Self>(conn)
+          .await
+      }
+    }
+  }
+
+  /// Emails which couldn't be delivered at least once, including those which are still retried.
+  pub async fn list_failed(
+    pool: &mut DbPool<'_>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<FailedEmail>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    email_queue
+      .filter(last_error.is_not_null())
+      .select(FailedEmail::as_select())
+      .order_by(id.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<FailedEmail>(conn)
+      .await
+  }
+
+  /// Failed emails are kept for some time, so that admins can check what went wrong.
+  pub async fn delete_old_failed(pool: &mut DbPool<'_>) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      email_queue
+        .filter(failed.eq(true))
+        .filter(published.lt(now() - 30.days())),
+    )
+    .execute(conn)
+    .await
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{
+    source::email_queue::{EmailQueue, EmailQueueInsertForm},
+    utils::build_db_pool_for_tests,
+  };
+  use chrono::{Duration, Utc};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_email_queue() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let form = |subject: &str| {
+      EmailQueueInsertForm::new(
+        "alice@example.com".to_string(),
+        "alice".to_string(),
+        subject.to_string(),
+        "<p>body</p>".to_string(),
+        None,
+      )
+    };
+    let first = EmailQueue::create(pool, &form("first")).await?;
+    let second = EmailQueue::create(pool, &form("second")).await?;
+
+    // Claimed emails aren't returned again until the retry time
+    let claimed = EmailQueue::claim_due(pool, 10).await?;
+    assert_eq!(2, claimed.len());
+    assert!(claimed.iter().all(|e| e.attempts == 1));
+    assert!(EmailQueue::claim_due(pool, 10).await?.is_empty());
+
+    // The first one is delivered, the second one fails and is retried
+    EmailQueue::delete(pool, first.id).await?;
+    let retry_at = Utc::now() - Duration::seconds(1);
+    EmailQueue::record_error(pool, second.id, "timeout", Some(retry_at)).await?;
+    let retried = EmailQueue::claim_due(pool, 10).await?;
+    assert_eq!(
+      vec![second.id],
+      retried.iter().map(|e| e.id).collect::<Vec<_>>()
+    );
+    assert_eq!(2, retried[0].attempts);
+
+    // Gave up on it, so it's not retried anymore but still listed for admins
+    let gave_up = EmailQueue::record_error(pool, second.id, "timeout", None).await?;
+    assert!(gave_up.failed);
+    assert!(EmailQueue::claim_due(pool, 10).await?.is_empty());
+    let failed = EmailQueue::list_failed(pool, None, None).await?;
+    assert_eq!(1, failed.len());
+    assert_eq!(Some("timeout".to_string()), failed[0].last_error);
+
+    // Only removed after some time
+    assert_eq!(0, EmailQueue::delete_old_failed(pool).await?);
+    EmailQueue::delete(pool, second.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,154 @@
+use crate::{
+  newtypes::EmailTemplateId,
+  schema::email_template::dsl::{email_template, language, template_type},
+  source::email_template::{EmailTemplate, EmailTemplateInsertForm, EmailTemplateUpdateForm},
+  utils::{get_conn, DbPool},
+  EmailTemplateType,
+};
+use diesel::{
+  dsl::insert_into,
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl EmailTemplate {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &EmailTemplateInsertForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(email_template)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn update(
+    pool: &mut DbPool<'_>,
+    template_id: EmailTemplateId,
+    form: &EmailTemplateUpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(email_template.find(template_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn delete(pool: &mut DbPool<'_>, template_id: EmailTemplateId) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(email_template.find(template_id))
+      .execute(conn)
+      .await
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    email_template
+      .order_by((template_type, language))
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// The template to use for the given interface language. A template for this exact language is
+  /// preferred over one for all languages.
+  pub async fn read_for_language(
+    pool: &mut DbPool<'_>,
+    template_type_: EmailTemplateType,
+    language_: &str,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    email_template
+      .into_boxed()
+      .filter(template_type.eq(template_type_))
+      .filter(language.eq(language_).or(language.is_null()))
+      // Postgres sorts null last in ascending order
+      .order_by(language.asc())
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{
+    source::email_template::{EmailTemplate, EmailTemplateInsertForm, EmailTemplateUpdateForm},
+    utils::build_db_pool_for_tests,
+    EmailTemplateType,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_email_template() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let all_languages = EmailTemplate::create(
+      pool,
+      &EmailTemplateInsertForm::new(
+        EmailTemplateType::PasswordReset,
+        None,
+        "Reset your password".to_string(),
+        "{link}".to_string(),
+      ),
+    )
+    .await?;
+    let german = EmailTemplate::create(
+      pool,
+      &EmailTemplateInsertForm::new(
+        EmailTemplateType::PasswordReset,
+        Some("de".to_string()),
+        "Passwort zurücksetzen".to_string(),
+        "{link}".to_string(),
+      ),
+    )
+    .await?;
+
+    // Only one template per type and language
+    let duplicate = EmailTemplate::create(
+      pool,
+      &EmailTemplateInsertForm::new(
+        EmailTemplateType::PasswordReset,
+        None,
+        "Duplicate".to_string(),
+        String::new(),
+      ),
+    )
+    .await;
+    assert!(duplicate.is_err());
+
+    let read_german =
+      EmailTemplate::read_for_language(pool, EmailTemplateType::PasswordReset, "de").await?;
+    assert_eq!(Some(german.id), read_german.map(|t| t.id));
+    let read_french =
+      EmailTemplate::read_for_language(pool, EmailTemplateType::PasswordReset, "fr").await?;
+    assert_eq!(Some(all_languages.id), read_french.map(|t| t.id));
+    let verification =
+      EmailTemplate::read_for_language(pool, EmailTemplateType::EmailVerification, "de").await?;
+    assert!(verification.is_none());
+
+    let form = EmailTemplateUpdateForm {
+      subject: Some("Neues Passwort".to_string()),
+      ..Default::default()
+    };
+    let updated = EmailTemplate::update(pool, german.id, &form).await?;
+    assert_eq!("Neues Passwort", updated.subject);
+    assert_eq!(2, EmailTemplate::list(pool).await?.len());
+
+    EmailTemplate::delete(pool, german.id).await?;
+    EmailTemplate::delete(pool, all_languages.id).await?;
+    assert!(EmailTemplate::list(pool).await?.is_empty());
+    Ok(())
+  }
+}
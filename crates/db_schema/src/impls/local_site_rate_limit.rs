@@ -58,6 +58,16 @@ impl LocalSiteRateLimitUpdateForm {
       && self.comment_per_second.is_none()
       && self.search.is_none()
       && self.search_per_second.is_none()
+      && self.account_factor.is_none()
+      && self.trusted_account_factor.is_none()
+      && self.vote.is_none()
+      && self.vote_per_second.is_none()
+      && self.report.is_none()
+      && self.report_per_second.is_none()
+      && self.follow.is_none()
+      && self.follow_per_second.is_none()
+      && self.federation_fetch.is_none()
+      && self.federation_fetch_per_second.is_none()
       && self.updated.is_none()
   }
 }
@@ -14,14 +14,18 @@ use crate::{
     DbPool,
   },
   CommunityVisibility,
+  EmailNotificationFrequency,
 };
 use bcrypt::{hash, DEFAULT_COST};
+use chrono::{DateTime, Duration, Utc};
 use diesel::{
-  dsl::{insert_into, not, IntervalDsl},
+  dsl::{insert_into, not, sql, IntervalDsl},
   result::Error,
+  sql_types::{Integer, Nullable, Timestamptz},
   CombineDsl,
   ExpressionMethods,
   JoinOnDsl,
+  NullableExpressionMethods,
   QueryDsl,
 };
 use diesel_async::RunQueryDsl;
@@ -93,6 +97,51 @@ impl LocalUser {
       .await
   }
 
+  /// Increase the failed login count, and return the updated user. The count starts over if the
+  /// last failed login was more than a day ago. From `max_failed_logins` on, logging in is locked
+  /// for one minute, which doubles with each further failed login up to `max_lockout_minutes`.
+  pub async fn record_failed_login(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+    max_failed_logins: i32,
+    max_lockout_minutes: i32,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    // A single statement, so that concurrent failed logins are all counted. The values in it are
+    // those from before the update.
+    let count = "CASE WHEN last_failed_login < now() - interval '1 day' THEN 1 \
+                 ELSE failed_login_count + 1 END";
+    let locked_until = format!(
+      "CASE WHEN {count} >= {max_failed_logins} \
+       THEN now() + least(2 ^ least({count} - {max_failed_logins}, 16), {max_lockout_minutes}) \
+         * interval '1 minute' \
+       ELSE login_locked_until END"
+    );
+    diesel::update(local_user::table.find(local_user_id))
+      .set((
+        local_user::failed_login_count.eq(sql::<Integer>(count)),
+        local_user::login_locked_until.eq(sql::<Nullable<Timestamptz>>(&locked_until)),
+        local_user::last_failed_login.eq(now().nullable()),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Reset the failed login count and any lockout, after a successful login.
+  pub async fn reset_failed_logins(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(local_user::table.find(local_user_id))
+      .set((
+        local_user::failed_login_count.eq(0),
+        local_user::login_locked_until.eq(None::<DateTime<Utc>>),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+
   pub async fn set_all_users_email_verified(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
     diesel::update(local_user::table)
@@ -344,6 +393,18 @@ impl LocalUserOptionHelper for Option<&LocalUser> {
   }
 }
 
+impl EmailNotificationFrequency {
+  /// Time between two notification digests, or `None` if emails are sent instantly.
+  pub fn digest_interval(&self) -> Option<Duration> {
+    match self {
+      EmailNotificationFrequency::Instant => None,
+      EmailNotificationFrequency::Hourly => Some(Duration::hours(1)),
+      EmailNotificationFrequency::Daily => Some(Duration::days(1)),
+      EmailNotificationFrequency::Weekly => Some(Duration::weeks(1)),
+    }
+  }
+}
+
 impl LocalUserInsertForm {
   pub fn test_form(person_id: PersonId) -> Self {
     Self::new(person_id, String::new())
@@ -372,13 +433,15 @@ mod tests {
   use crate::{
     source::{
       instance::Instance,
-      local_user::{LocalUser, LocalUserInsertForm},
+      local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
       person::{Person, PersonInsertForm},
     },
     traits::Crud,
     utils::build_db_pool_for_tests,
   };
+  use chrono::{Duration, Utc};
   use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
   use serial_test::serial;
 
   #[tokio::test]
@@ -419,4 +482,67 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_failed_logins() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "failed_login_user");
+    let inserted_person = Person::create(pool, &person_form).await?;
+    let local_user_form = LocalUserInsertForm::test_form(inserted_person.id);
+    let inserted_local_user = LocalUser::create(pool, &local_user_form, vec![]).await?;
+    assert_eq!(0, inserted_local_user.failed_login_count);
+
+    let id = inserted_local_user.id;
+    LocalUser::record_failed_login(pool, id, 3, 60).await?;
+    let updated = LocalUser::record_failed_login(pool, id, 3, 60).await?;
+    assert_eq!(2, updated.failed_login_count);
+    assert!(updated.last_failed_login.is_some());
+    assert!(updated.login_locked_until.is_none());
+
+    // From the maximum on, the lockout starts at one minute and doubles up to the limit
+    let lockout_minutes = |user: &LocalUser| {
+      user
+        .login_locked_until
+        .map(|until| (until - Utc::now() + Duration::seconds(1)).num_minutes())
+    };
+    let mut lockouts = vec![];
+    for _ in 0..6 {
+      let updated = LocalUser::record_failed_login(pool, id, 3, 60).await?;
+      lockouts.push(lockout_minutes(&updated));
+    }
+    assert_eq!(
+      vec![Some(1), Some(2), Some(4), Some(8), Some(16), Some(32)],
+      lockouts
+    );
+    let updated = LocalUser::record_failed_login(pool, id, 3, 60).await?;
+    assert_eq!(Some(60), lockout_minutes(&updated));
+
+    // Failed logins from a long time ago are not counted
+    let form = LocalUserUpdateForm {
+      last_failed_login: Some(Some(Utc::now() - Duration::days(2))),
+      login_locked_until: Some(None),
+      ..Default::default()
+    };
+    LocalUser::update(pool, inserted_local_user.id, &form).await?;
+    let updated = LocalUser::record_failed_login(pool, id, 3, 60).await?;
+    assert_eq!(1, updated.failed_login_count);
+    assert!(updated.login_locked_until.is_none());
+
+    let form = LocalUserUpdateForm {
+      login_locked_until: Some(Some(Utc::now() + Duration::minutes(5))),
+      ..Default::default()
+    };
+    LocalUser::update(pool, inserted_local_user.id, &form).await?;
+    let reset = LocalUser::reset_failed_logins(pool, inserted_local_user.id).await?;
+    assert_eq!(0, reset.failed_login_count);
+    assert!(reset.login_locked_until.is_none());
+
+    Instance::delete(pool, inserted_instance.id).await?;
+
+    Ok(())
+  }
 }
@@ -1,11 +1,11 @@
 use crate::{
   diesel::{ExpressionMethods, QueryDsl},
-  newtypes::LocalUserId,
-  schema::login_token::{dsl::login_token, user_id},
+  newtypes::{LocalUserId, LoginTokenId},
+  schema::login_token::{dsl::login_token, id, ip, token, user_agent, user_id},
   source::login_token::{LoginToken, LoginTokenCreateForm},
   utils::{get_conn, DbPool},
 };
-use diesel::{delete, dsl::exists, insert_into, result::Error, select};
+use diesel::{delete, dsl::exists, insert_into, result::Error, select, PgExpressionMethods};
 use diesel_async::RunQueryDsl;
 
 impl LoginToken {
@@ -31,6 +31,24 @@ impl LoginToken {
     .await
   }
 
+  /// Check if the user already has a login from the same ip address and with the same user agent.
+  pub async fn is_known_device(
+    pool: &mut DbPool<'_>,
+    user_id_: LocalUserId,
+    ip_: Option<&str>,
+    user_agent_: Option<&str>,
+  ) -> Result<bool, Error> {
+    let conn = &mut get_conn(pool).await?;
+    select(exists(
+      login_token
+        .filter(user_id.eq(user_id_))
+        .filter(ip.is_not_distinct_from(ip_))
+        .filter(user_agent.is_not_distinct_from(user_agent_)),
+    ))
+    .get_result(conn)
+    .await
+  }
+
   pub async fn list(
     pool: &mut DbPool<'_>,
     user_id_: LocalUserId,
@@ -49,6 +67,34 @@ impl LoginToken {
     delete(login_token.find(token_)).execute(conn).await
   }
 
+  /// Invalidate a single login of the given user, identified by its id.
+  pub async fn invalidate_by_id(
+    pool: &mut DbPool<'_>,
+    user_id_: LocalUserId,
+    id_: LoginTokenId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(login_token.filter(user_id.eq(user_id_)).filter(id.eq(id_)))
+      .execute(conn)
+      .await
+  }
+
+  /// Invalidate all logins of given user, except for the one which is currently used.
+  pub async fn invalidate_all_except(
+    pool: &mut DbPool<'_>,
+    user_id_: LocalUserId,
+    token_: &str,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      login_token
+        .filter(user_id.eq(user_id_))
+        .filter(token.ne(token_)),
+    )
+    .execute(conn)
+    .await
+  }
+
   /// Invalidate all logins of given user on password reset/change, account deletion or site ban.
   pub async fn invalidate_all(
     pool: &mut DbPool<'_>,
@@ -60,3 +106,73 @@ impl LoginToken {
       .await
   }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      login_token::{LoginToken, LoginTokenCreateForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_revoke_logins() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "login_token_user");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_local_user = LocalUserInsertForm::test_form(inserted_person.id);
+    let user_id = LocalUser::create(pool, &new_local_user, vec![]).await?.id;
+
+    for (token, ip, user_agent) in [
+      ("token_1", Some("1.2.3.4"), Some("Firefox")),
+      ("token_2", Some("5.6.7.8"), Some("Chrome")),
+      ("token_3", None, None),
+    ] {
+      let form = LoginTokenCreateForm {
+        token: token.to_string().into(),
+        user_id,
+        ip: ip.map(ToString::to_string),
+        user_agent: user_agent.map(ToString::to_string),
+      };
+      LoginToken::create(pool, form).await?;
+    }
+    let logins = LoginToken::list(pool, user_id).await?;
+    assert_eq!(3, logins.len());
+    assert!(LoginToken::is_known_device(pool, user_id, None, None).await?);
+    assert!(LoginToken::is_known_device(pool, user_id, Some("1.2.3.4"), Some("Firefox")).await?);
+    assert!(!LoginToken::is_known_device(pool, user_id, Some("1.2.3.4"), None).await?);
+    // The ip address and user agent need to be known from the same login
+    assert!(!LoginToken::is_known_device(pool, user_id, Some("1.2.3.4"), Some("Chrome")).await?);
+
+    // Revoke a single login by its id
+    let first_login = logins.iter().find(|l| &*l.token == "token_1").unwrap();
+    let revoked = LoginToken::invalidate_by_id(pool, user_id, first_login.id).await?;
+    assert_eq!(1, revoked);
+    assert!(!LoginToken::validate(pool, user_id, "token_1").await?);
+
+    // Log out everywhere else
+    let revoked = LoginToken::invalidate_all_except(pool, user_id, "token_3").await?;
+    assert_eq!(1, revoked);
+    assert!(!LoginToken::validate(pool, user_id, "token_2").await?);
+    assert!(LoginToken::validate(pool, user_id, "token_3").await?);
+
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
@@ -1,5 +1,6 @@
 pub mod activity;
 pub mod actor_language;
+pub mod api_token;
 pub mod captcha_answer;
 pub mod comment;
 pub mod comment_reply;
@@ -7,6 +8,8 @@ pub mod comment_report;
 pub mod community;
 pub mod community_block;
 pub mod custom_emoji;
+pub mod email_queue;
+pub mod email_template;
 pub mod email_verification;
 pub mod federation_allowlist;
 pub mod federation_blocklist;
@@ -21,6 +24,7 @@ pub mod local_site_url_blocklist;
 pub mod local_user;
 pub mod local_user_vote_display_mode;
 pub mod login_token;
+pub mod mod_action_notification;
 pub mod moderator;
 pub mod password_reset_request;
 pub mod person;
@@ -30,7 +34,12 @@ pub mod post;
 pub mod post_report;
 pub mod private_message;
 pub mod private_message_report;
+pub mod push_subscription;
+pub mod rate_limit_bucket;
 pub mod registration_application;
+pub mod saved_search;
 pub mod secret;
 pub mod site;
 pub mod tagline;
+pub mod totp_recovery_code;
+pub mod webhook;
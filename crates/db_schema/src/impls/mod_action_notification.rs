@@ -0,0 +1,73 @@
+use crate::{
+  newtypes::{ModActionNotificationId, PersonId},
+  schema::mod_action_notification,
+  source::mod_action_notification::{
+    ModActionNotification,
+    ModActionNotificationInsertForm,
+    ModActionNotificationUpdateForm,
+  },
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for ModActionNotification {
+  type InsertForm = ModActionNotificationInsertForm;
+  type UpdateForm = ModActionNotificationUpdateForm;
+  type IdType = ModActionNotificationId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(mod_action_notification::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    notification_id: ModActionNotificationId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(mod_action_notification::table.find(notification_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl ModActionNotification {
+  pub async fn mark_all_as_read(
+    pool: &mut DbPool<'_>,
+    for_recipient_id: PersonId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      mod_action_notification::table
+        .filter(mod_action_notification::recipient_id.eq(for_recipient_id))
+        .filter(mod_action_notification::read.eq(false)),
+    )
+    .set(mod_action_notification::read.eq(true))
+    .get_results::<Self>(conn)
+    .await
+  }
+
+  /// Gets the number of unread mod action notifications
+  pub async fn get_unread_count(
+    pool: &mut DbPool<'_>,
+    for_recipient_id: PersonId,
+  ) -> Result<i64, Error> {
+    use diesel::dsl::count;
+
+    let conn = &mut get_conn(pool).await?;
+    mod_action_notification::table
+      .filter(mod_action_notification::recipient_id.eq(for_recipient_id))
+      .filter(mod_action_notification::read.eq(false))
+      .select(count(mod_action_notification::id))
+      .first::<i64>(conn)
+      .await
+  }
+}
@@ -50,16 +50,20 @@ impl Reportable for PostReport {
     pool: &mut DbPool<'_>,
     post_id_: PostId,
     by_resolver_id: PersonId,
-  ) -> Result<usize, Error> {
+  ) -> Result<Vec<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
-    update(post_report.filter(post_id.eq(post_id_)))
-      .set((
-        resolved.eq(true),
-        resolver_id.eq(by_resolver_id),
-        updated.eq(naive_now()),
-      ))
-      .execute(conn)
-      .await
+    update(
+      post_report
+        .filter(post_id.eq(post_id_))
+        .filter(resolved.eq(false)),
+    )
+    .set((
+      resolved.eq(true),
+      resolver_id.eq(by_resolver_id),
+      updated.eq(naive_now()),
+    ))
+    .get_results::<Self>(conn)
+    .await
   }
 
   async fn unresolve(
@@ -159,10 +163,16 @@ mod tests {
 
     let (person, report) = init(pool).await;
 
-    let resolved_count = PostReport::resolve_all_for_object(pool, report.post_id, person.id)
+    let resolved_reports = PostReport::resolve_all_for_object(pool, report.post_id, person.id)
       .await
       .unwrap();
-    assert_eq!(resolved_count, 1);
+    assert_eq!(vec![report.id], resolved_reports.iter().map(|r| r.id).collect::<Vec<_>>());
+
+    // Already resolved reports are left alone
+    let resolved_reports = PostReport::resolve_all_for_object(pool, report.post_id, person.id)
+      .await
+      .unwrap();
+    assert!(resolved_reports.is_empty());
 
     Person::delete(pool, person.id).await.unwrap();
     Post::delete(pool, report.post_id).await.unwrap();
@@ -51,7 +51,7 @@ impl Reportable for PrivateMessageReport {
     _pool: &mut DbPool<'_>,
     _pm_id_: PrivateMessageId,
     _by_resolver_id: PersonId,
-  ) -> Result<usize, Error> {
+  ) -> Result<Vec<Self>, Error> {
     Err(Error::NotFound)
   }
 
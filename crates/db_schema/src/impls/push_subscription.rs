@@ -0,0 +1,130 @@
+use crate::{
+  newtypes::LocalUserId,
+  schema::push_subscription::dsl::{endpoint, local_user_id, push_subscription},
+  source::push_subscription::{PushSubscription, PushSubscriptionInsertForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl PushSubscription {
+  /// Store the subscription. If the user already registered the endpoint, its keys are replaced.
+  /// Subscriptions of other users for the same endpoint are left alone.
+  pub async fn upsert(
+    pool: &mut DbPool<'_>,
+    form: &PushSubscriptionInsertForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(push_subscription)
+      .values(form)
+      .on_conflict((local_user_id, endpoint))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn list_for_local_user(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    push_subscription
+      .filter(local_user_id.eq(for_local_user_id))
+      .load::<Self>(conn)
+      .await
+  }
+
+  pub async fn delete_for_local_user(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+    endpoint_: &str,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      push_subscription
+        .filter(local_user_id.eq(for_local_user_id))
+        .filter(endpoint.eq(endpoint_)),
+    )
+    .execute(conn)
+    .await
+  }
+
+  /// Remove a subscription which the push service reported as expired.
+  pub async fn delete(pool: &mut DbPool<'_>, subscription_id: i32) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(push_subscription.find(subscription_id))
+      .execute(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+      push_subscription::{PushSubscription, PushSubscriptionInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_push_subscription() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let alice_form = PersonInsertForm::test_form(inserted_instance.id, "push_alice");
+    let alice = Person::create(pool, &alice_form).await?;
+    let alice_local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(alice.id), vec![]).await?;
+    let bob_form = PersonInsertForm::test_form(inserted_instance.id, "push_bob");
+    let bob = Person::create(pool, &bob_form).await?;
+    let bob_local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(bob.id), vec![]).await?;
+
+    let endpoint = "https://push.example.com/device1";
+    let form = |local_user_id| {
+      PushSubscriptionInsertForm::new(
+        local_user_id,
+        endpoint.to_string(),
+        "p256dh".to_string(),
+        "auth".to_string(),
+      )
+    };
+    PushSubscription::upsert(pool, &form(alice_local_user.id)).await?;
+    let list = PushSubscription::list_for_local_user(pool, alice_local_user.id).await?;
+    assert_eq!(1, list.len());
+
+    // Bob registers the same endpoint, which doesn't take over alice's subscription
+    PushSubscription::upsert(pool, &form(bob_local_user.id)).await?;
+    PushSubscription::upsert(pool, &form(bob_local_user.id)).await?;
+    let list = PushSubscription::list_for_local_user(pool, alice_local_user.id).await?;
+    assert_eq!(1, list.len());
+    let list = PushSubscription::list_for_local_user(pool, bob_local_user.id).await?;
+    assert_eq!(1, list.len());
+
+    // Alice only deletes her own subscription
+    let deleted =
+      PushSubscription::delete_for_local_user(pool, alice_local_user.id, endpoint).await?;
+    assert_eq!(1, deleted);
+    let list = PushSubscription::list_for_local_user(pool, bob_local_user.id).await?;
+    assert_eq!(1, list.len());
+
+    Person::delete(pool, alice.id).await?;
+    Person::delete(pool, bob.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,208 @@
+use crate::{
+  schema::rate_limit_bucket::dsl::{last_checked, rate_limit_bucket},
+  source::rate_limit_bucket::RateLimitBucket,
+  utils::{get_conn, now, ActualDbPool, DbPool},
+};
+use diesel::{
+  dsl::IntervalDsl,
+  result::Error,
+  sql_query,
+  sql_types::{Double, Text},
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+  QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use futures_util::{future::BoxFuture, FutureExt};
+use lemmy_utils::{
+  error::LemmyResult,
+  rate_limit::{ActionType, BucketConfig, RateLimitInfo, SharedRateLimitBackend},
+};
+
+#[derive(QueryableByName)]
+struct BucketTokens {
+  #[diesel(sql_type = Double)]
+  tokens: f64,
+}
+
+impl RateLimitBucket {
+  /// Takes one token from the bucket, after refilling it for the time since the last check. New
+  /// buckets start out full. Returns the remaining tokens, or `None` if the bucket is empty, in
+  /// which case it is left unchanged.
+  ///
+  /// This is a single statement, so concurrent requests from multiple processes can't take the
+  /// same token.
+  pub async fn check(
+    pool: &mut DbPool<'_>,
+    key: &str,
+    action_type: &str,
+    capacity: f64,
+    secs_to_refill: f64,
+  ) -> Result<Option<f64>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let remaining = sql_query(
+      "INSERT INTO rate_limit_bucket AS b (key, action_type, tokens, last_checked)
+        VALUES ($1, $2, $3 - 1, now())
+        ON CONFLICT (key, action_type) DO UPDATE
+        SET tokens = least($3, b.tokens + $3 / $4 * extract(epoch FROM now() - b.last_checked)::float8) - 1,
+          last_checked = now()
+        WHERE least($3, b.tokens + $3 / $4 * extract(epoch FROM now() - b.last_checked)::float8) >= 1
+        RETURNING b.tokens",
+    )
+    .bind::<Text, _>(key)
+    .bind::<Text, _>(action_type)
+    .bind::<Double, _>(capacity)
+    .bind::<Double, _>(secs_to_refill)
+    .get_result::<BucketTokens>(conn)
+    .await
+    .optional()?;
+    Ok(remaining.map(|r| r.tokens))
+  }
+
+  /// The fractional amount of tokens which are currently in the bucket, without taking any.
+  pub async fn current_tokens(
+    pool: &mut DbPool<'_>,
+    key: &str,
+    action_type: &str,
+    capacity: f64,
+    secs_to_refill: f64,
+  ) -> Result<f64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let current = sql_query(
+      "SELECT least($3, tokens + $3 / $4 * extract(epoch FROM now() - last_checked)::float8) AS tokens
+        FROM rate_limit_bucket
+        WHERE key = $1 AND action_type = $2",
+    )
+    .bind::<Text, _>(key)
+    .bind::<Text, _>(action_type)
+    .bind::<Double, _>(capacity)
+    .bind::<Double, _>(secs_to_refill)
+    .get_result::<BucketTokens>(conn)
+    .await
+    .optional()?;
+    // The bucket may have been removed in the meantime, which means that it's full
+    Ok(current.map_or(capacity, |c| c.tokens))
+  }
+
+  /// Buckets which weren't checked for longer than the refill time are full again, so they can
+  /// be removed.
+  pub async fn remove_full(pool: &mut DbPool<'_>, max_secs_to_refill: i32) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(rate_limit_bucket.filter(last_checked.lt(now() - max_secs_to_refill.seconds())))
+      .execute(conn)
+      .await
+  }
+}
+
+/// Shares rate limits between multiple lemmy processes which use the same database.
+#[derive(Clone)]
+pub struct PostgresRateLimitBackend {
+  pool: ActualDbPool,
+}
+
+impl PostgresRateLimitBackend {
+  pub fn new(pool: ActualDbPool) -> Self {
+    PostgresRateLimitBackend { pool }
+  }
+}
+
+impl SharedRateLimitBackend for PostgresRateLimitBackend {
+  fn check<'a>(
+    &'a self,
+    action_type: ActionType,
+    key: &'a str,
+    config: BucketConfig,
+  ) -> BoxFuture<'a, LemmyResult<RateLimitInfo>> {
+    async move {
+      // Same as the in-memory limiter, which rejects everything for a capacity of 0
+      if config.capacity == 0 {
+        return Ok(RateLimitInfo::new(false, 0.0, config));
+      }
+      let pool = &mut (&self.pool).into();
+      let (capacity, secs_to_refill) = (config.capacity.into(), config.secs_to_refill.into());
+      let action_type = action_type.as_ref();
+      let info =
+        match RateLimitBucket::check(pool, key, action_type, capacity, secs_to_refill).await? {
+          Some(remaining) => RateLimitInfo::new(true, remaining, config),
+          None => {
+            let current =
+              RateLimitBucket::current_tokens(pool, key, action_type, capacity, secs_to_refill)
+                .await?;
+            RateLimitInfo::new(false, current, config)
+          }
+        };
+      Ok(info)
+    }
+    .boxed()
+  }
+
+  fn remove_full_buckets(&self, max_secs_to_refill: u32) -> BoxFuture<'_, LemmyResult<()>> {
+    async move {
+      let max_secs_to_refill = i32::try_from(max_secs_to_refill).unwrap_or(i32::MAX);
+      RateLimitBucket::remove_full(&mut (&self.pool).into(), max_secs_to_refill).await?;
+      Ok(())
+    }
+    .boxed()
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{
+    schema::rate_limit_bucket,
+    source::rate_limit_bucket::RateLimitBucket,
+    utils::{build_db_pool_for_tests, get_conn},
+  };
+  use diesel::{ExpressionMethods, QueryDsl};
+  use diesel_async::RunQueryDsl;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_rate_limit_bucket() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    // A bucket with capacity 2 which takes an hour to refill
+    for expected_to_pass in [true, true, false, false] {
+      let passed = RateLimitBucket::check(pool, "ip:1.2.3.4", "Post", 2.0, 3600.0).await?;
+      assert_eq!(expected_to_pass, passed.is_some());
+    }
+    let current = RateLimitBucket::current_tokens(pool, "ip:1.2.3.4", "Post", 2.0, 3600.0).await?;
+    assert!(current < 1.0);
+
+    // Other keys and action types have their own buckets
+    let other_key = RateLimitBucket::check(pool, "ip:1.2.3.5", "Post", 2.0, 3600.0).await?;
+    assert_eq!(Some(1.0), other_key);
+    let other_action = RateLimitBucket::check(pool, "ip:1.2.3.4", "Comment", 2.0, 3600.0).await?;
+    assert_eq!(Some(1.0), other_action);
+
+    // Pretend that the bucket was last checked an hour ago, so it is full again
+    diesel::update(rate_limit_bucket::table.find(("ip:1.2.3.4", "Post")))
+      .set(rate_limit_bucket::last_checked.eq(chrono::Utc::now() - chrono::Duration::hours(1)))
+      .execute(&mut get_conn(pool).await?)
+      .await?;
+    assert!(
+      RateLimitBucket::check(pool, "ip:1.2.3.4", "Post", 2.0, 3600.0)
+        .await?
+        .is_some()
+    );
+    let bucket: RateLimitBucket = rate_limit_bucket::table
+      .find(("ip:1.2.3.4", "Post"))
+      .first(&mut get_conn(pool).await?)
+      .await?;
+    assert_eq!(1.0, bucket.tokens.round());
+
+    // Nothing is older than a minute, so nothing is removed
+    assert_eq!(0, RateLimitBucket::remove_full(pool, 60).await?);
+    assert_eq!(3, RateLimitBucket::remove_full(pool, 0).await?);
+
+    Ok(())
+  }
+}
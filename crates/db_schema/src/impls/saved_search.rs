@@ -0,0 +1,182 @@
+use crate::{
+  newtypes::{CommentId, LocalUserId, PostId, SavedSearchId, SavedSearchMatchId},
+  schema::{comment, local_user, person, post, saved_search, saved_search_match},
+  source::saved_search::{
+    SavedSearch,
+    SavedSearchInsertForm,
+    SavedSearchMatch,
+    SavedSearchMatchInsertForm,
+    SavedSearchMatchUpdateForm,
+    SavedSearchUpdateForm,
+  },
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{
+  dsl::{count, insert_into},
+  result::Error,
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for SavedSearch {
+  type InsertForm = SavedSearchInsertForm;
+  type UpdateForm = SavedSearchUpdateForm;
+  type IdType = SavedSearchId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(saved_search::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    saved_search_id: SavedSearchId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(saved_search::table.find(saved_search_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl SavedSearch {
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    saved_search::table
+      .filter(saved_search::local_user_id.eq(for_local_user_id))
+      .order_by(saved_search::id)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// The saved searches of all users which aren't banned or deleted, for the scheduled check.
+  pub async fn list_all(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    saved_search::table
+      .inner_join(local_user::table.inner_join(person::table))
+      .filter(person::banned.eq(false))
+      .filter(person::deleted.eq(false))
+      .select(saved_search::all_columns)
+      .order_by(saved_search::id)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Stop sending emails for all saved searches of the user.
+  pub async fn disable_emails(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(saved_search::table.filter(saved_search::local_user_id.eq(for_local_user_id)))
+      .set(saved_search::send_email.eq(false))
+      .execute(conn)
+      .await
+  }
+
+  /// The ids of the newest post and comment. Content up to these ids is checked in one run of the
+  /// scheduled job.
+  pub async fn latest_content_ids(pool: &mut DbPool<'_>) -> Result<(PostId, CommentId), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let post_id = post::table
+      .select(post::id)
+      .order_by(post::id.desc())
+      .first::<PostId>(conn)
+      .await
+      .optional()?;
+    let comment_id = comment::table
+      .select(comment::id)
+      .order_by(comment::id.desc())
+      .first::<CommentId>(conn)
+      .await
+      .optional()?;
+    Ok((post_id.unwrap_or_default(), comment_id.unwrap_or_default()))
+  }
+}
+
+#[async_trait]
+impl Crud for SavedSearchMatch {
+  type InsertForm = SavedSearchMatchInsertForm;
+  type UpdateForm = SavedSearchMatchUpdateForm;
+  type IdType = SavedSearchMatchId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(saved_search_match::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    saved_search_match_id: SavedSearchMatchId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(saved_search_match::table.find(saved_search_match_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl SavedSearchMatch {
+  /// Returns only the new matches, content which already matched the search is skipped.
+  pub async fn create_many(
+    pool: &mut DbPool<'_>,
+    forms: &[SavedSearchMatchInsertForm],
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(saved_search_match::table)
+      .values(forms)
+      .on_conflict_do_nothing()
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  pub async fn mark_all_as_read(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let saved_search_ids = saved_search::table
+      .filter(saved_search::local_user_id.eq(for_local_user_id))
+      .select(saved_search::id);
+    diesel::update(
+      saved_search_match::table
+        .filter(saved_search_match::saved_search_id.eq_any(saved_search_ids))
+        .filter(saved_search_match::read.eq(false)),
+    )
+    .set(saved_search_match::read.eq(true))
+    .execute(conn)
+    .await
+  }
+
+  /// Gets the number of unread saved search matches
+  pub async fn get_unread_count(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    saved_search_match::table
+      .inner_join(saved_search::table)
+      .filter(saved_search::local_user_id.eq(for_local_user_id))
+      .filter(saved_search_match::read.eq(false))
+      .select(count(saved_search_match::id))
+      .first::<i64>(conn)
+      .await
+  }
+}
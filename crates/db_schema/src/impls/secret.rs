@@ -1,10 +1,10 @@
 use crate::{
   diesel::OptionalExtension,
-  schema::secret::dsl::secret,
+  schema::secret::dsl::{secret, vapid_private_key, vapid_public_key},
   source::secret::Secret,
   utils::{get_conn, DbPool},
 };
-use diesel::result::Error;
+use diesel::{result::Error, ExpressionMethods};
 use diesel_async::RunQueryDsl;
 
 impl Secret {
@@ -14,6 +14,22 @@ impl Secret {
     Self::read_secrets(pool).await
   }
 
+  /// Store the generated keys for Web Push.
+  pub async fn update_vapid_keys(
+    pool: &mut DbPool<'_>,
+    private_key: &str,
+    public_key: &str,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(secret)
+      .set((
+        vapid_private_key.eq(private_key),
+        vapid_public_key.eq(public_key),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+
   async fn read_secrets(pool: &mut DbPool<'_>) -> Result<Option<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
     secret.first(conn).await.optional()
@@ -0,0 +1,148 @@
+use crate::{
+  newtypes::LocalUserId,
+  schema::totp_recovery_code::dsl::{code_hash, local_user_id, totp_recovery_code},
+  sensitive::SensitiveString,
+  source::totp_recovery_code::{TotpRecoveryCode, TotpRecoveryCodeForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{delete, insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use sha2::{Digest, Sha256};
+
+/// How many recovery codes are generated at once.
+const RECOVERY_CODE_COUNT: usize = 10;
+
+impl TotpRecoveryCode {
+  /// Replace all existing recovery codes of the user with new ones. Returns the plain codes, which
+  /// need to be shown to the user as they can't be retrieved later.
+  pub async fn regenerate(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<Vec<SensitiveString>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+      .map(|_| Self::generate_code())
+      .collect();
+    let forms: Vec<_> = codes
+      .iter()
+      .map(|c| TotpRecoveryCodeForm {
+        local_user_id: for_local_user_id,
+        code_hash: Self::hash_code(c),
+      })
+      .collect();
+
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          delete(totp_recovery_code.filter(local_user_id.eq(for_local_user_id)))
+            .execute(conn)
+            .await?;
+          insert_into(totp_recovery_code)
+            .values(forms)
+            .execute(conn)
+            .await?;
+          Ok::<_, Error>(())
+        }) as _
+      })
+      .await?;
+
+    Ok(codes.into_iter().map(Into::into).collect())
+  }
+
+  /// Check if the code is valid for the user, and delete it so that it can't be used again.
+  pub async fn use_code(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+    code: &str,
+  ) -> Result<bool, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let deleted = delete(
+      totp_recovery_code
+        .filter(local_user_id.eq(for_local_user_id))
+        .filter(code_hash.eq(Self::hash_code(code))),
+    )
+    .execute(conn)
+    .await?;
+    Ok(deleted > 0)
+  }
+
+  /// Remove all recovery codes, when 2fa gets disabled.
+  pub async fn delete_all(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    delete(totp_recovery_code.filter(local_user_id.eq(for_local_user_id)))
+      .execute(conn)
+      .await
+  }
+
+  /// Generates a code like `3f9a1-c04b7`.
+  fn generate_code() -> String {
+    let random = uuid::Uuid::new_v4().simple().to_string();
+    let first = random.get(0..5).unwrap_or_default();
+    let second = random.get(5..10).unwrap_or_default();
+    format!("{first}-{second}")
+  }
+
+  /// Codes are normalized before hashing, so that copy-paste mistakes like surrounding whitespace
+  /// or uppercase letters don't matter.
+  fn hash_code(code: &str) -> String {
+    let normalized = code.trim().to_lowercase();
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+      totp_recovery_code::TotpRecoveryCode,
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_totp_recovery_codes() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "totp_recovery_user");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_local_user = LocalUserInsertForm::test_form(inserted_person.id);
+    let local_user_id = LocalUser::create(pool, &new_local_user, vec![]).await?.id;
+
+    let codes = TotpRecoveryCode::regenerate(pool, local_user_id).await?;
+    assert_eq!(10, codes.len());
+    assert_eq!(11, codes[0].len());
+
+    // Each code works exactly once, and is case insensitive
+    assert!(TotpRecoveryCode::use_code(pool, local_user_id, &codes[0].to_uppercase()).await?);
+    assert!(!TotpRecoveryCode::use_code(pool, local_user_id, &codes[0]).await?);
+    assert!(!TotpRecoveryCode::use_code(pool, local_user_id, "wrong-code").await?);
+
+    // Old codes are invalid after regenerating
+    let new_codes = TotpRecoveryCode::regenerate(pool, local_user_id).await?;
+    assert!(!TotpRecoveryCode::use_code(pool, local_user_id, &codes[1]).await?);
+    assert!(TotpRecoveryCode::use_code(pool, local_user_id, &new_codes[1]).await?);
+
+    assert_eq!(9, TotpRecoveryCode::delete_all(pool, local_user_id).await?);
+
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
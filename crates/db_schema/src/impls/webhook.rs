@@ -0,0 +1,400 @@
+use crate::{
+  newtypes::{CommunityId, WebhookId},
+  schema::{sql_types::WebhookEventTypeEnum, webhook, webhook_delivery},
+  source::webhook::{
+    Webhook,
+    WebhookDelivery,
+    WebhookDeliveryInsertForm,
+    WebhookInsertForm,
+    WebhookUpdateForm,
+  },
+  traits::Crud,
+  utils::{get_conn, limit_and_offset, now, DbPool},
+  WebhookEventType,
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  dsl::{insert_into, IntervalDsl},
+  result::Error,
+  sql_types::{Array, Text},
+  BoolExpressionMethods,
+  ExpressionMethods,
+  PgArrayExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+sql_function! {
+  #[sql_name = "r.webhook_events"]
+  fn webhook_events(events: Array<Text>) -> Array<WebhookEventTypeEnum>;
+}
+
+/// Events are bound by name, because diesel-async can't bind arrays of enums.
+fn event_names(events: &[WebhookEventType]) -> Vec<String> {
+  events.iter().map(ToString::to_string).collect()
+}
+
+#[async_trait]
+impl Crud for Webhook {
+  type InsertForm = WebhookInsertForm;
+  type UpdateForm = WebhookUpdateForm;
+  type IdType = WebhookId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook::table)
+      .values((
+        webhook::creator_id.eq(form.creator_id),
+        webhook::community_id.eq(form.community_id),
+        webhook::url.eq(&form.url),
+        webhook::secret.eq(&form.secret),
+        webhook::events.eq(webhook_events(event_names(&form.events))),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(webhook::table.find(webhook_id))
+      .set((
+        form.url.as_ref().map(|url| webhook::url.eq(url)),
+        form.secret.as_ref().map(|secret| webhook::secret.eq(secret)),
+        form
+          .events
+          .as_ref()
+          .map(|events| webhook::events.eq(webhook_events(event_names(events)))),
+        form.enabled.map(|enabled| webhook::enabled.eq(enabled)),
+        form.updated.map(|updated| webhook::updated.eq(updated)),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl Webhook {
+  /// The webhooks of a community, or the site webhooks if no community is given.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    for_community_id: Option<CommunityId>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let query = webhook::table.into_boxed();
+    let query = match for_community_id {
+      Some(for_community_id) => query.filter(webhook::community_id.eq(for_community_id)),
+      None => query.filter(webhook::community_id.is_null()),
+    };
+    query.order_by(webhook::id).load::<Self>(conn).await
+  }
+
+  /// The enabled webhooks which receive the event, that is all site webhooks and those of the
+  /// community where it happened.
+  pub async fn list_for_event(
+    pool: &mut DbPool<'_>,
+    event: WebhookEventType,
+    for_community_id: Option<CommunityId>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = webhook::table
+      .filter(webhook::enabled.eq(true))
+      .filter(webhook::events.contains(webhook_events(event_names(&[event]))))
+      .into_boxed();
+    query = match for_community_id {
+      Some(for_community_id) => query.filter(
+        webhook::community_id
+          .is_null()
+          .or(webhook::community_id.eq(for_community_id)),
+      ),
+      None => query.filter(webhook::community_id.is_null()),
+    };
+    query.load::<Self>(conn).await
+  }
+}
+
+impl WebhookDelivery {
+  pub async fn create_many(
+    pool: &mut DbPool<'_>,
+    forms: &[WebhookDeliveryInsertForm],
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook_delivery::table)
+      .values(forms)
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  /// Take the deliveries which are due, and count the attempt. They are not returned again for a
+  /// few minutes, so that other workers don't send them at the same time.
+  pub async fn claim_due(pool: &mut DbPool<'_>, limit: i64) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let due = webhook_delivery::table
+            .select(webhook_delivery::id)
+            .filter(webhook_delivery::delivered.eq(false))
+            .filter(webhook_delivery::failed.eq(false))
+            .filter(webhook_delivery::next_attempt.le(now()))
+            .order_by(webhook_delivery::id)
+            .limit(limit)
+            .for_update()
+            .skip_locked()
+            .load::<i32>(conn)
+            .await?;
+          diesel::update(webhook_delivery::table.filter(webhook_delivery::id.eq_any(due)))
+            .set((
+              webhook_delivery::attempts.eq(webhook_delivery::attempts + 1),
+              webhook_delivery::next_attempt.eq(now() + 10.minutes()),
+            ))
+            .get_results::<Self>(conn)
+            .await
+        }) as _
+      })
+      .await
+  }
+
+  pub async fn mark_delivered(
+    pool: &mut DbPool<'_>,
+    delivery_id: i32,
+    status: i32,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(webhook_delivery::table.find(delivery_id))
+      .set((
+        webhook_delivery::delivered.eq(true),
+        webhook_delivery::response_status.eq(status),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Store the result of a failed attempt. Without a time for the next attempt, delivery is given
+  /// up.
+  pub async fn record_error(
+    pool: &mut DbPool<'_>,
+    delivery_id: i32,
+    status: Option<i32>,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let query = diesel::update(webhook_delivery::table.find(delivery_id));
+    let result = (
+      webhook_delivery::response_status.eq(status),
+      webhook_delivery::last_error.eq(error),
+    );
+    match retry_at {
+      Some(retry_at) => {
+        query
+          .set((result, webhook_delivery::next_attempt.eq(retry_at)))
+          .get_result::<Self>(conn)
+          .await
+      }
+      None => {
+        query
+          .set((result, webhook_delivery::failed.eq(true)))
+          .get_result::<Self>(conn)
+          .await
+      }
+    }
+  }
+
+  /// The delivery log of a webhook, most recent first.
+  pub async fn list_for_webhook(
+    pool: &mut DbPool<'_>,
+    for_webhook_id: WebhookId,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    webhook_delivery::table
+      .filter(webhook_delivery::webhook_id.eq(for_webhook_id))
+      .order_by(webhook_delivery::id.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Finished deliveries are kept for some time as a log.
+  pub async fn delete_old(pool: &mut DbPool<'_>) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      webhook_delivery::table
+        .filter(
+          webhook_delivery::delivered
+            .eq(true)
+            .or(webhook_delivery::failed.eq(true)),
+        )
+        .filter(webhook_delivery::published.lt(now() - 30.days())),
+    )
+    .execute(conn)
+    .await
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      webhook::{
+        Webhook,
+        WebhookDelivery,
+        WebhookDeliveryInsertForm,
+        WebhookInsertForm,
+        WebhookUpdateForm,
+      },
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+    WebhookEventType,
+  };
+  use chrono::{Duration, Utc};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_webhook() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "webhook_creator");
+    let inserted_person = Person::create(pool, &person_form).await?;
+    let new_community = CommunityInsertForm::builder()
+      .name("test community webhook".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await?;
+
+    let site_webhook = Webhook::create(
+      pool,
+      &WebhookInsertForm::new(
+        inserted_person.id,
+        None,
+        "https://example.com/site".to_string(),
+        "secret".to_string(),
+        vec![
+          WebhookEventType::ReportCreated,
+          WebhookEventType::RegistrationApplicationCreated,
+        ],
+      ),
+    )
+    .await?;
+    let community_webhook = Webhook::create(
+      pool,
+      &WebhookInsertForm::new(
+        inserted_person.id,
+        Some(inserted_community.id),
+        "https://example.com/community".to_string(),
+        "secret".to_string(),
+        vec![WebhookEventType::ReportCreated, WebhookEventType::PostCreated],
+      ),
+    )
+    .await?;
+
+    assert_eq!(vec![site_webhook.clone()], Webhook::list(pool, None).await?);
+    assert_eq!(
+      vec![community_webhook.clone()],
+      Webhook::list(pool, Some(inserted_community.id)).await?
+    );
+
+    // Site webhooks get the events of all communities, community webhooks only their own
+    let report = WebhookEventType::ReportCreated;
+    let in_community = Webhook::list_for_event(pool, report, Some(inserted_community.id)).await?;
+    assert_eq!(2, in_community.len());
+    let without_community = Webhook::list_for_event(pool, report, None).await?;
+    assert_eq!(vec![site_webhook.clone()], without_community);
+    let for_post =
+      Webhook::list_for_event(pool, WebhookEventType::PostCreated, Some(inserted_community.id))
+        .await?;
+    assert_eq!(vec![community_webhook.clone()], for_post);
+
+    let events_form = WebhookUpdateForm {
+      events: Some(vec![WebhookEventType::ModAction]),
+      ..Default::default()
+    };
+    let updated = Webhook::update(pool, site_webhook.id, &events_form).await?;
+    assert_eq!(vec![WebhookEventType::ModAction], updated.events);
+    assert!(Webhook::list_for_event(pool, report, None).await?.is_empty());
+
+    // Disabled webhooks don't get any events
+    let disable_form = WebhookUpdateForm {
+      enabled: Some(false),
+      ..Default::default()
+    };
+    Webhook::update(pool, community_webhook.id, &disable_form).await?;
+    assert!(
+      Webhook::list_for_event(pool, WebhookEventType::PostCreated, Some(inserted_community.id))
+        .await?
+        .is_empty()
+    );
+
+    let form = |event| WebhookDeliveryInsertForm::new(site_webhook.id, event, "{}".to_string());
+    let deliveries = WebhookDelivery::create_many(
+      pool,
+      &[
+        form(WebhookEventType::ReportCreated),
+        form(WebhookEventType::RegistrationApplicationCreated),
+      ],
+    )
+    .await?;
+    let (first, second) = (&deliveries[0], &deliveries[1]);
+
+    // Claimed deliveries aren't returned again until the retry time
+    let claimed = WebhookDelivery::claim_due(pool, 10).await?;
+    assert_eq!(2, claimed.len());
+    assert!(claimed.iter().all(|d| d.attempts == 1));
+    assert!(WebhookDelivery::claim_due(pool, 10).await?.is_empty());
+
+    // The first one is delivered, the second one fails and is retried
+    let delivered = WebhookDelivery::mark_delivered(pool, first.id, 200).await?;
+    assert!(delivered.delivered);
+    let retry_at = Utc::now() - Duration::seconds(1);
+    WebhookDelivery::record_error(pool, second.id, Some(500), "status 500", Some(retry_at))
+      .await?;
+    let retried = WebhookDelivery::claim_due(pool, 10).await?;
+    assert_eq!(
+      vec![second.id],
+      retried.iter().map(|d| d.id).collect::<Vec<_>>()
+    );
+    assert_eq!(2, retried[0].attempts);
+
+    // Gave up on it, but it stays in the log
+    let gave_up = WebhookDelivery::record_error(pool, second.id, None, "timeout", None).await?;
+    assert!(gave_up.failed);
+    assert_eq!(None, gave_up.response_status);
+    assert!(WebhookDelivery::claim_due(pool, 10).await?.is_empty());
+    let log = WebhookDelivery::list_for_webhook(pool, site_webhook.id, None, None).await?;
+    assert_eq!(
+      vec![second.id, first.id],
+      log.iter().map(|d| d.id).collect::<Vec<_>>()
+    );
+
+    // Only removed after some time
+    assert_eq!(0, WebhookDelivery::delete_old(pool).await?);
+
+    Webhook::delete(pool, site_webhook.id).await?;
+    Webhook::delete(pool, community_webhook.id).await?;
+    Community::delete(pool, inserted_community.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
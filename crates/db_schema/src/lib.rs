@@ -47,6 +47,13 @@ pub mod utils;
 #[cfg(feature = "full")]
 mod schema_setup;
 
+#[cfg(feature = "full")]
+use diesel::{
+  deserialize::FromSql,
+  pg::{Pg, PgValue},
+  serialize::{Output, ToSql},
+  sql_types::Text,
+};
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 #[cfg(feature = "full")]
@@ -85,6 +92,9 @@ pub enum SortType {
   TopNineMonths,
   Controversial,
   Scaled,
+  /// Sorts search results by how well they match the search term. Same as `Hot` without a search
+  /// term.
+  Relevance,
 }
 
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
@@ -97,6 +107,9 @@ pub enum CommentSortType {
   New,
   Old,
   Controversial,
+  /// Sorts search results by how well they match the search term. Same as `Hot` without a search
+  /// term.
+  Relevance,
 }
 
 #[derive(
@@ -164,6 +177,129 @@ pub enum PostListingMode {
   SmallCard,
 }
 
+#[derive(
+  EnumString, Display, Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Hash,
+)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::EmailNotificationFrequencyEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// How often emails about new replies, mentions and private messages are sent.
+pub enum EmailNotificationFrequency {
+  /// A separate email for each notification.
+  #[default]
+  Instant,
+  /// A digest of unread notifications, at most once per hour.
+  Hourly,
+  /// A digest of unread notifications, at most once per day.
+  Daily,
+  /// A digest of unread notifications, at most once per week.
+  Weekly,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::EmailTemplateTypeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// The emails which can be customized by admins.
+///
+/// All templates can use the placeholders `{username}` for the recipient, `{hostname}` and
+/// `{link}`. Notifications can also use `{sender}` and `{content}`.
+pub enum EmailTemplateType {
+  /// Confirm a new email address. `{link}` verifies it.
+  EmailVerification,
+  /// `{link}` leads to the page for choosing a new password.
+  PasswordReset,
+  /// The registration application was approved. `{link}` is the user profile.
+  ApplicationApproved,
+  /// Sent to admins. `{sender}` is the applicant, `{link}` the list of applications.
+  NewApplication,
+  /// Sent to admins. `{sender}` is the reporter, `{content}` the name of the reported user, and
+  /// `{link}` the list of reports.
+  NewReport,
+  /// `{sender}` mentioned the recipient in a comment, `{link}` is the inbox.
+  Mention,
+  /// `{sender}` replied to a comment, `{link}` is the inbox.
+  CommentReply,
+  /// `{sender}` replied to a post, `{link}` is the inbox.
+  PostReply,
+  /// `{sender}` sent a private message, `{link}` is the inbox.
+  PrivateMessage,
+  /// New posts and comments matched a saved search. `{content}` is the list of matches, `{link}`
+  /// is the inbox.
+  SavedSearchMatch,
+  /// Logging in was blocked after too many failed attempts. `{content}` is the time until which
+  /// it is blocked, `{link}` the page for resetting the password.
+  LoginLocked,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::ModActionNotificationTypeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// A moderator action which the affected user is notified about.
+pub enum ModActionNotificationType {
+  /// One of the user's posts was removed.
+  RemovePost,
+  /// One of the user's comments was removed.
+  RemoveComment,
+  /// One of the user's posts was locked.
+  LockPost,
+  /// The user was banned from a community.
+  BanFromCommunity,
+  /// A report which the user filed was resolved.
+  ReportResolved,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::ReportOutcomeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// What happened after a report was resolved.
+pub enum ReportOutcome {
+  /// The reported content was removed.
+  ContentRemoved,
+  /// The creator of the reported content was banned from the site or community.
+  UserBanned,
+  /// The report was resolved without taking any action.
+  NoAction,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::WebhookEventTypeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// The events which a webhook can subscribe to.
+pub enum WebhookEventType {
+  /// A post, comment or private message was reported.
+  ReportCreated,
+  /// A new registration application is waiting for approval. Only sent to site webhooks.
+  RegistrationApplicationCreated,
+  /// A moderator or admin removed, locked, featured or banned something.
+  ModAction,
+  /// A new post was created.
+  PostCreated,
+}
+
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -177,6 +313,16 @@ pub enum SearchType {
   Url,
 }
 
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The type of name which is completed by autocomplete.
+pub enum AutocompleteType {
+  Users,
+  Communities,
+  Emojis,
+}
+
 #[derive(EnumString, Display, Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Hash)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -245,6 +391,55 @@ pub enum CommunityVisibility {
   LocalOnly,
 }
 
+#[derive(
+  EnumString,
+  Display,
+  Debug,
+  Serialize,
+  Deserialize,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+  Hash,
+)]
+#[cfg_attr(feature = "full", derive(AsExpression, FromSqlRow, TS))]
+#[cfg_attr(feature = "full", diesel(sql_type = Text))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A permission which can be granted to an api token.
+///
+/// Stored as text, because diesel-async can't bind arrays of custom postgres enums.
+pub enum ApiTokenScope {
+  /// Read content and your own account data, such as notifications.
+  Read,
+  /// Create, edit and delete posts, comments and private messages, follow communities and
+  /// other non-voting actions.
+  Post,
+  /// Vote on posts and comments.
+  Vote,
+  /// Moderation actions in communities which you moderate.
+  Moderate,
+  /// Site administration actions. Only available for admins.
+  Admin,
+}
+
+#[cfg(feature = "full")]
+impl ToSql<Text, Pg> for ApiTokenScope {
+  fn to_sql(&self, out: &mut Output<Pg>) -> diesel::serialize::Result {
+    <String as ToSql<Text, Pg>>::to_sql(&self.to_string(), &mut out.reborrow())
+  }
+}
+
+#[cfg(feature = "full")]
+impl FromSql<Text, Pg> for ApiTokenScope {
+  fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+    let str = String::from_sql(value)?;
+    Ok(str.parse()?)
+  }
+}
+
 /// Wrapper for assert_eq! macro. Checks that vec matches the given length, and prints the
 /// vec on failure.
 #[macro_export]
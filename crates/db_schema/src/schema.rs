@@ -9,6 +9,14 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "community_visibility"))]
     pub struct CommunityVisibility;
 
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "email_notification_frequency_enum"))]
+    pub struct EmailNotificationFrequencyEnum;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "email_template_type_enum"))]
+    pub struct EmailTemplateTypeEnum;
+
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "listing_type_enum"))]
     pub struct ListingTypeEnum;
@@ -17,6 +25,10 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "ltree"))]
     pub struct Ltree;
 
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "mod_action_notification_type_enum"))]
+    pub struct ModActionNotificationTypeEnum;
+
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "post_listing_mode_enum"))]
     pub struct PostListingModeEnum;
@@ -25,9 +37,21 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "registration_mode_enum"))]
     pub struct RegistrationModeEnum;
 
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "report_outcome_enum"))]
+    pub struct ReportOutcomeEnum;
+
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "sort_type_enum"))]
     pub struct SortTypeEnum;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "tsvector", schema = "pg_catalog"))]
+    pub struct Tsvector;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "webhook_event_type_enum"))]
+    pub struct WebhookEventTypeEnum;
 }
 
 diesel::table! {
@@ -68,6 +92,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    api_token (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        token_hash -> Text,
+        scopes -> Array<Text>,
+        published -> Timestamptz,
+        expires -> Nullable<Timestamptz>,
+        last_used -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     captcha_answer (uuid) {
         uuid -> Uuid,
@@ -153,6 +190,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
+    comment_search (comment_id) {
+        comment_id -> Int4,
+        search_vector -> Tsvector,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::CommunityVisibility;
@@ -251,6 +298,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
+    community_search (community_id) {
+        community_id -> Int4,
+        search_vector -> Tsvector,
+    }
+}
+
 diesel::table! {
     custom_emoji (id) {
         id -> Int4,
@@ -273,6 +330,38 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    email_queue (id) {
+        id -> Int4,
+        to_email -> Text,
+        to_username -> Text,
+        subject -> Text,
+        body -> Text,
+        unsubscribe_link -> Nullable<Text>,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        failed -> Bool,
+        next_attempt -> Timestamptz,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::EmailTemplateTypeEnum;
+
+    email_template (id) {
+        id -> Int4,
+        template_type -> EmailTemplateTypeEnum,
+        #[max_length = 20]
+        language -> Nullable<Varchar>,
+        subject -> Text,
+        body -> Text,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     email_verification (id) {
         id -> Int4,
@@ -415,6 +504,16 @@ diesel::table! {
         updated -> Nullable<Timestamptz>,
         import_user_settings -> Int4,
         import_user_settings_per_second -> Int4,
+        account_factor -> Int4,
+        trusted_account_factor -> Int4,
+        vote -> Int4,
+        vote_per_second -> Int4,
+        report -> Int4,
+        report_per_second -> Int4,
+        follow -> Int4,
+        follow_per_second -> Int4,
+        federation_fetch -> Int4,
+        federation_fetch_per_second -> Int4,
     }
 }
 
@@ -432,6 +531,7 @@ diesel::table! {
     use super::sql_types::SortTypeEnum;
     use super::sql_types::ListingTypeEnum;
     use super::sql_types::PostListingModeEnum;
+    use super::sql_types::EmailNotificationFrequencyEnum;
 
     local_user (id) {
         id -> Int4,
@@ -462,6 +562,17 @@ diesel::table! {
         enable_keyboard_navigation -> Bool,
         enable_animated_images -> Bool,
         collapse_bot_comments -> Bool,
+        send_login_alerts -> Bool,
+        failed_login_count -> Int4,
+        last_failed_login -> Nullable<Timestamptz>,
+        login_locked_until -> Nullable<Timestamptz>,
+        trusted_bot -> Bool,
+        email_notification_frequency -> EmailNotificationFrequencyEnum,
+        last_email_digest -> Nullable<Timestamptz>,
+        send_reply_emails -> Bool,
+        send_mention_emails -> Bool,
+        send_private_message_emails -> Bool,
+        report_resolved_notifications -> Bool,
     }
 }
 
@@ -489,6 +600,27 @@ diesel::table! {
         published -> Timestamptz,
         ip -> Nullable<Text>,
         user_agent -> Nullable<Text>,
+        id -> Int4,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ModActionNotificationTypeEnum;
+    use super::sql_types::ReportOutcomeEnum;
+
+    mod_action_notification (id) {
+        id -> Int4,
+        recipient_id -> Int4,
+        kind -> ModActionNotificationTypeEnum,
+        community_id -> Nullable<Int4>,
+        post_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+        reason -> Nullable<Text>,
+        read -> Bool,
+        published -> Timestamptz,
+        private_message_id -> Nullable<Int4>,
+        report_outcome -> Nullable<ReportOutcomeEnum>,
     }
 }
 
@@ -706,6 +838,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
+    person_search (person_id) {
+        person_id -> Int4,
+        search_vector -> Tsvector,
+    }
+}
+
 diesel::table! {
     post (id) {
         id -> Int4,
@@ -809,6 +951,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
+    post_search (post_id) {
+        post_id -> Int4,
+        search_vector -> Tsvector,
+    }
+}
+
 diesel::table! {
     private_message (id) {
         id -> Int4,
@@ -839,6 +991,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    push_subscription (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        endpoint -> Text,
+        p256dh -> Text,
+        auth -> Text,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    rate_limit_bucket (key, action_type) {
+        key -> Text,
+        action_type -> Text,
+        tokens -> Float8,
+        last_checked -> Timestamptz,
+    }
+}
+
 diesel::table! {
     received_activity (ap_id) {
         ap_id -> Text,
@@ -864,10 +1036,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    saved_search (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        query -> Text,
+        community_id -> Nullable<Int4>,
+        creator_id -> Nullable<Int4>,
+        post_title_only -> Bool,
+        send_email -> Bool,
+        last_post_id -> Nullable<Int4>,
+        last_comment_id -> Nullable<Int4>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    saved_search_match (id) {
+        id -> Int4,
+        saved_search_id -> Int4,
+        post_id -> Int4,
+        comment_id -> Nullable<Int4>,
+        read -> Bool,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    search_vector_backfill (table_name) {
+        table_name -> Text,
+        last_id -> Int4,
+    }
+}
+
 diesel::table! {
     secret (id) {
         id -> Int4,
         jwt_secret -> Varchar,
+        vapid_private_key -> Nullable<Text>,
+        vapid_public_key -> Nullable<Text>,
     }
 }
 
@@ -944,12 +1151,58 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    totp_recovery_code (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        code_hash -> Text,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventTypeEnum;
+
+    webhook (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        community_id -> Nullable<Int4>,
+        url -> Text,
+        secret -> Text,
+        events -> Array<WebhookEventTypeEnum>,
+        enabled -> Bool,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventTypeEnum;
+
+    webhook_delivery (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        event -> WebhookEventTypeEnum,
+        payload -> Text,
+        attempts -> Int4,
+        response_status -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        delivered -> Bool,
+        failed -> Bool,
+        next_attempt -> Timestamptz,
+        published -> Timestamptz,
+    }
+}
+
 diesel::joinable!(admin_purge_comment -> person (admin_person_id));
 diesel::joinable!(admin_purge_comment -> post (post_id));
 diesel::joinable!(admin_purge_community -> person (admin_person_id));
 diesel::joinable!(admin_purge_person -> person (admin_person_id));
 diesel::joinable!(admin_purge_post -> community (community_id));
 diesel::joinable!(admin_purge_post -> person (admin_person_id));
+diesel::joinable!(api_token -> local_user (local_user_id));
 diesel::joinable!(comment -> language (language_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(comment -> post (post_id));
@@ -962,6 +1215,7 @@ diesel::joinable!(comment_reply -> person (recipient_id));
 diesel::joinable!(comment_report -> comment (comment_id));
 diesel::joinable!(comment_saved -> comment (comment_id));
 diesel::joinable!(comment_saved -> person (person_id));
+diesel::joinable!(comment_search -> comment (comment_id));
 diesel::joinable!(community -> instance (instance_id));
 diesel::joinable!(community_aggregates -> community (community_id));
 diesel::joinable!(community_block -> community (community_id));
@@ -974,6 +1228,7 @@ diesel::joinable!(community_moderator -> community (community_id));
 diesel::joinable!(community_moderator -> person (person_id));
 diesel::joinable!(community_person_ban -> community (community_id));
 diesel::joinable!(community_person_ban -> person (person_id));
+diesel::joinable!(community_search -> community (community_id));
 diesel::joinable!(custom_emoji -> local_site (local_site_id));
 diesel::joinable!(custom_emoji_keyword -> custom_emoji (custom_emoji_id));
 diesel::joinable!(email_verification -> local_user (local_user_id));
@@ -990,6 +1245,11 @@ diesel::joinable!(local_user_language -> language (language_id));
 diesel::joinable!(local_user_language -> local_user (local_user_id));
 diesel::joinable!(local_user_vote_display_mode -> local_user (local_user_id));
 diesel::joinable!(login_token -> local_user (user_id));
+diesel::joinable!(mod_action_notification -> comment (comment_id));
+diesel::joinable!(mod_action_notification -> community (community_id));
+diesel::joinable!(mod_action_notification -> person (recipient_id));
+diesel::joinable!(mod_action_notification -> post (post_id));
+diesel::joinable!(mod_action_notification -> private_message (private_message_id));
 diesel::joinable!(mod_add_community -> community (community_id));
 diesel::joinable!(mod_ban_from_community -> community (community_id));
 diesel::joinable!(mod_feature_post -> person (mod_person_id));
@@ -1013,6 +1273,7 @@ diesel::joinable!(person_mention -> comment (comment_id));
 diesel::joinable!(person_mention -> person (recipient_id));
 diesel::joinable!(person_post_aggregates -> person (person_id));
 diesel::joinable!(person_post_aggregates -> post (post_id));
+diesel::joinable!(person_search -> person (person_id));
 diesel::joinable!(post -> community (community_id));
 diesel::joinable!(post -> language (language_id));
 diesel::joinable!(post -> person (creator_id));
@@ -1029,20 +1290,33 @@ diesel::joinable!(post_read -> post (post_id));
 diesel::joinable!(post_report -> post (post_id));
 diesel::joinable!(post_saved -> person (person_id));
 diesel::joinable!(post_saved -> post (post_id));
+diesel::joinable!(post_search -> post (post_id));
 diesel::joinable!(private_message_report -> private_message (private_message_id));
+diesel::joinable!(push_subscription -> local_user (local_user_id));
 diesel::joinable!(registration_application -> local_user (local_user_id));
 diesel::joinable!(registration_application -> person (admin_id));
+diesel::joinable!(saved_search -> community (community_id));
+diesel::joinable!(saved_search -> local_user (local_user_id));
+diesel::joinable!(saved_search -> person (creator_id));
+diesel::joinable!(saved_search_match -> comment (comment_id));
+diesel::joinable!(saved_search_match -> post (post_id));
+diesel::joinable!(saved_search_match -> saved_search (saved_search_id));
 diesel::joinable!(site -> instance (instance_id));
 diesel::joinable!(site_aggregates -> site (site_id));
 diesel::joinable!(site_language -> language (language_id));
 diesel::joinable!(site_language -> site (site_id));
 diesel::joinable!(tagline -> local_site (local_site_id));
+diesel::joinable!(totp_recovery_code -> local_user (local_user_id));
+diesel::joinable!(webhook -> community (community_id));
+diesel::joinable!(webhook -> person (creator_id));
+diesel::joinable!(webhook_delivery -> webhook (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     admin_purge_comment,
     admin_purge_community,
     admin_purge_person,
     admin_purge_post,
+    api_token,
     captcha_answer,
     comment,
     comment_aggregates,
@@ -1050,6 +1324,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     comment_reply,
     comment_report,
     comment_saved,
+    comment_search,
     community,
     community_aggregates,
     community_block,
@@ -1057,8 +1332,11 @@ diesel::allow_tables_to_appear_in_same_query!(
     community_language,
     community_moderator,
     community_person_ban,
+    community_search,
     custom_emoji,
     custom_emoji_keyword,
+    email_queue,
+    email_template,
     email_verification,
     federation_allowlist,
     federation_blocklist,
@@ -1075,6 +1353,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     local_user_language,
     local_user_vote_display_mode,
     login_token,
+    mod_action_notification,
     mod_add,
     mod_add_community,
     mod_ban,
@@ -1094,6 +1373,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     person_follower,
     person_mention,
     person_post_aggregates,
+    person_search,
     post,
     post_aggregates,
     post_hide,
@@ -1101,15 +1381,24 @@ diesel::allow_tables_to_appear_in_same_query!(
     post_read,
     post_report,
     post_saved,
+    post_search,
     private_message,
     private_message_report,
+    push_subscription,
+    rate_limit_bucket,
     received_activity,
     registration_application,
     remote_image,
+    saved_search,
+    saved_search_match,
+    search_vector_backfill,
     secret,
     sent_activity,
     site,
     site_aggregates,
     site_language,
     tagline,
+    totp_recovery_code,
+    webhook,
+    webhook_delivery,
 );
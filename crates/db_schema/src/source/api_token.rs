@@ -0,0 +1,48 @@
+#[cfg(feature = "full")]
+use crate::schema::api_token;
+use crate::{
+  newtypes::{ApiTokenId, LocalUserId},
+  sensitive::SensitiveString,
+  ApiTokenScope,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+/// A long-lived token which lets bots and integrations use the api with limited permissions.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+pub struct ApiToken {
+  pub id: ApiTokenId,
+  pub local_user_id: LocalUserId,
+  /// A name chosen by the user to tell tokens apart.
+  pub name: String,
+  /// Sha256 hash of the token. The token itself is only shown once after creation.
+  #[serde(skip)]
+  pub token_hash: SensitiveString,
+  /// The actions which may be performed with this token.
+  pub scopes: Vec<ApiTokenScope>,
+  pub published: DateTime<Utc>,
+  /// The token stops working after this time.
+  pub expires: Option<DateTime<Utc>>,
+  /// The last time this token was used for an api call.
+  pub last_used: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+pub struct ApiTokenInsertForm {
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  pub token_hash: String,
+  pub scopes: Vec<ApiTokenScope>,
+  #[new(default)]
+  pub expires: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,61 @@
+#[cfg(feature = "full")]
+use crate::schema::email_queue;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = email_queue))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// An outgoing email which wasn't delivered yet.
+pub struct EmailQueue {
+  pub id: i32,
+  pub to_email: String,
+  pub to_username: String,
+  pub subject: String,
+  pub body: String,
+  pub unsubscribe_link: Option<String>,
+  /// Number of delivery attempts so far.
+  pub attempts: i32,
+  /// The error from the last failed delivery attempt.
+  pub last_error: Option<String>,
+  /// Delivery was given up after too many attempts.
+  pub failed: bool,
+  pub next_attempt: DateTime<Utc>,
+  pub published: DateTime<Utc>,
+}
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = email_queue))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An email which couldn't be delivered, for admins. The body and unsubscribe link are left out,
+/// because they can contain secrets like password reset links.
+pub struct FailedEmail {
+  pub id: i32,
+  pub to_email: String,
+  pub to_username: String,
+  pub subject: String,
+  pub attempts: i32,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub last_error: Option<String>,
+  pub failed: bool,
+  pub next_attempt: DateTime<Utc>,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = email_queue))]
+pub struct EmailQueueInsertForm {
+  pub to_email: String,
+  pub to_username: String,
+  pub subject: String,
+  pub body: String,
+  pub unsubscribe_link: Option<String>,
+}
@@ -0,0 +1,45 @@
+#[cfg(feature = "full")]
+use crate::schema::email_template;
+use crate::{newtypes::EmailTemplateId, EmailTemplateType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = email_template))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An admin defined email, which is used instead of the built-in translation.
+pub struct EmailTemplate {
+  pub id: EmailTemplateId,
+  pub template_type: EmailTemplateType,
+  /// The interface language which this template is used for, or all languages if empty.
+  pub language: Option<String>,
+  pub subject: String,
+  pub body: String,
+  pub published: DateTime<Utc>,
+  pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = email_template))]
+pub struct EmailTemplateInsertForm {
+  pub template_type: EmailTemplateType,
+  pub language: Option<String>,
+  pub subject: String,
+  pub body: String,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = email_template))]
+pub struct EmailTemplateUpdateForm {
+  pub subject: Option<String>,
+  pub body: Option<String>,
+  pub updated: Option<Option<DateTime<Utc>>>,
+}
@@ -38,6 +38,18 @@ pub struct LocalSiteRateLimit {
   pub updated: Option<DateTime<Utc>>,
   pub import_user_settings: i32,
   pub import_user_settings_per_second: i32,
+  /// Per-account limits, as a multiple of the per-ip limits. 0 disables them.
+  pub account_factor: i32,
+  /// Per-account limits for admins and trusted bots, which are not limited per ip.
+  pub trusted_account_factor: i32,
+  pub vote: i32,
+  pub vote_per_second: i32,
+  pub report: i32,
+  pub report_per_second: i32,
+  pub follow: i32,
+  pub follow_per_second: i32,
+  pub federation_fetch: i32,
+  pub federation_fetch_per_second: i32,
 }
 
 #[derive(Clone, TypedBuilder)]
@@ -61,6 +73,16 @@ pub struct LocalSiteRateLimitInsertForm {
   pub search_per_second: Option<i32>,
   pub import_user_settings: Option<i32>,
   pub import_user_settings_per_second: Option<i32>,
+  pub account_factor: Option<i32>,
+  pub trusted_account_factor: Option<i32>,
+  pub vote: Option<i32>,
+  pub vote_per_second: Option<i32>,
+  pub report: Option<i32>,
+  pub report_per_second: Option<i32>,
+  pub follow: Option<i32>,
+  pub follow_per_second: Option<i32>,
+  pub federation_fetch: Option<i32>,
+  pub federation_fetch_per_second: Option<i32>,
 }
 
 #[derive(Clone, Default)]
@@ -81,5 +103,15 @@ pub struct LocalSiteRateLimitUpdateForm {
   pub search_per_second: Option<i32>,
   pub import_user_settings: Option<i32>,
   pub import_user_settings_per_second: Option<i32>,
+  pub account_factor: Option<i32>,
+  pub trusted_account_factor: Option<i32>,
+  pub vote: Option<i32>,
+  pub vote_per_second: Option<i32>,
+  pub report: Option<i32>,
+  pub report_per_second: Option<i32>,
+  pub follow: Option<i32>,
+  pub follow_per_second: Option<i32>,
+  pub federation_fetch: Option<i32>,
+  pub federation_fetch_per_second: Option<i32>,
   pub updated: Option<Option<DateTime<Utc>>>,
 }
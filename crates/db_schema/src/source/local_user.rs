@@ -3,10 +3,12 @@ use crate::schema::local_user;
 use crate::{
   newtypes::{LocalUserId, PersonId},
   sensitive::SensitiveString,
+  EmailNotificationFrequency,
   ListingType,
   PostListingMode,
   SortType,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
@@ -66,6 +68,27 @@ pub struct LocalUser {
   pub enable_animated_images: bool,
   /// Whether to auto-collapse bot comments.
   pub collapse_bot_comments: bool,
+  /// Whether to send an email when logging in from a new ip address or device.
+  pub send_login_alerts: bool,
+  /// Number of failed login attempts since the last successful login.
+  pub failed_login_count: i32,
+  pub last_failed_login: Option<DateTime<Utc>>,
+  /// Login is not possible until this time, because of too many failed attempts.
+  pub login_locked_until: Option<DateTime<Utc>>,
+  /// A bot account which was marked as trusted by an admin, and gets higher rate limits.
+  pub trusted_bot: bool,
+  /// How often to send emails about new notifications, either instantly or as a digest.
+  pub email_notification_frequency: EmailNotificationFrequency,
+  /// When the last notification digest was sent.
+  pub last_email_digest: Option<DateTime<Utc>>,
+  /// Whether to send emails about replies to your posts and comments.
+  pub send_reply_emails: bool,
+  /// Whether to send emails when you are mentioned.
+  pub send_mention_emails: bool,
+  /// Whether to send emails about new private messages.
+  pub send_private_message_emails: bool,
+  /// Whether to be notified when a report you filed is resolved.
+  pub report_resolved_notifications: bool,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -122,6 +145,28 @@ pub struct LocalUserInsertForm {
   pub enable_animated_images: Option<bool>,
   #[new(default)]
   pub collapse_bot_comments: Option<bool>,
+  #[new(default)]
+  pub send_login_alerts: Option<bool>,
+  #[new(default)]
+  pub failed_login_count: Option<i32>,
+  #[new(default)]
+  pub last_failed_login: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub login_locked_until: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub trusted_bot: Option<bool>,
+  #[new(default)]
+  pub email_notification_frequency: Option<EmailNotificationFrequency>,
+  #[new(default)]
+  pub last_email_digest: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub send_reply_emails: Option<bool>,
+  #[new(default)]
+  pub send_mention_emails: Option<bool>,
+  #[new(default)]
+  pub send_private_message_emails: Option<bool>,
+  #[new(default)]
+  pub report_resolved_notifications: Option<bool>,
 }
 
 #[derive(Clone, Default)]
@@ -153,4 +198,15 @@ pub struct LocalUserUpdateForm {
   pub enable_keyboard_navigation: Option<bool>,
   pub enable_animated_images: Option<bool>,
   pub collapse_bot_comments: Option<bool>,
+  pub send_login_alerts: Option<bool>,
+  pub failed_login_count: Option<i32>,
+  pub last_failed_login: Option<Option<DateTime<Utc>>>,
+  pub login_locked_until: Option<Option<DateTime<Utc>>>,
+  pub trusted_bot: Option<bool>,
+  pub email_notification_frequency: Option<EmailNotificationFrequency>,
+  pub last_email_digest: Option<Option<DateTime<Utc>>>,
+  pub send_reply_emails: Option<bool>,
+  pub send_mention_emails: Option<bool>,
+  pub send_private_message_emails: Option<bool>,
+  pub report_resolved_notifications: Option<bool>,
 }
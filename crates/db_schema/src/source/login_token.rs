@@ -1,6 +1,9 @@
 #[cfg(feature = "full")]
 use crate::schema::login_token;
-use crate::{newtypes::LocalUserId, sensitive::SensitiveString};
+use crate::{
+  newtypes::{LocalUserId, LoginTokenId},
+  sensitive::SensitiveString,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -26,6 +29,8 @@ pub struct LoginToken {
   /// Could be stored in truncated format, or store derived information for better privacy.
   pub ip: Option<String>,
   pub user_agent: Option<String>,
+  /// Used to revoke this specific login.
+  pub id: LoginTokenId,
 }
 
 #[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
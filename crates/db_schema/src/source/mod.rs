@@ -4,6 +4,7 @@ use url::Url;
 #[cfg(feature = "full")]
 pub mod activity;
 pub mod actor_language;
+pub mod api_token;
 pub mod captcha_answer;
 pub mod comment;
 pub mod comment_reply;
@@ -12,6 +13,8 @@ pub mod community;
 pub mod community_block;
 pub mod custom_emoji;
 pub mod custom_emoji_keyword;
+pub mod email_queue;
+pub mod email_template;
 pub mod email_verification;
 pub mod federation_allowlist;
 pub mod federation_blocklist;
@@ -26,6 +29,7 @@ pub mod local_site_url_blocklist;
 pub mod local_user;
 pub mod local_user_vote_display_mode;
 pub mod login_token;
+pub mod mod_action_notification;
 pub mod moderator;
 pub mod password_reset_request;
 pub mod person;
@@ -35,10 +39,14 @@ pub mod post;
 pub mod post_report;
 pub mod private_message;
 pub mod private_message_report;
+pub mod push_subscription;
+pub mod rate_limit_bucket;
 pub mod registration_application;
+pub mod saved_search;
 pub mod secret;
 pub mod site;
 pub mod tagline;
+pub mod totp_recovery_code;
 
 /// Default value for columns like [community::Community.inbox_url] which are marked as serde(skip).
 ///
@@ -50,3 +58,4 @@ fn placeholder_apub_url() -> DbUrl {
     Url::parse("http://example.com").expect("parse placeholder url"),
   ))
 }
+pub mod webhook;
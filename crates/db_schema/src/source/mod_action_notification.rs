@@ -0,0 +1,67 @@
+#[cfg(feature = "full")]
+use crate::schema::mod_action_notification;
+use crate::{
+  newtypes::{CommentId, CommunityId, ModActionNotificationId, PersonId, PostId, PrivateMessageId},
+  ModActionNotificationType,
+  ReportOutcome,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_action_notification))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Tells a user that a moderator removed or locked their content, banned them from a community,
+/// or resolved one of their reports.
+pub struct ModActionNotification {
+  pub id: ModActionNotificationId,
+  pub recipient_id: PersonId,
+  pub kind: ModActionNotificationType,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub post_id: Option<PostId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment_id: Option<CommentId>,
+  /// The reason given in the modlog.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub reason: Option<String>,
+  pub read: bool,
+  pub published: DateTime<Utc>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub private_message_id: Option<PrivateMessageId>,
+  /// For resolved reports, what happened to the reported content.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub report_outcome: Option<ReportOutcome>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_action_notification))]
+pub struct ModActionNotificationInsertForm {
+  pub recipient_id: PersonId,
+  pub kind: ModActionNotificationType,
+  pub community_id: Option<CommunityId>,
+  #[new(default)]
+  pub post_id: Option<PostId>,
+  #[new(default)]
+  pub comment_id: Option<CommentId>,
+  #[new(default)]
+  pub reason: Option<String>,
+  #[new(default)]
+  pub private_message_id: Option<PrivateMessageId>,
+  #[new(default)]
+  pub report_outcome: Option<ReportOutcome>,
+}
+
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_action_notification))]
+pub struct ModActionNotificationUpdateForm {
+  pub read: Option<bool>,
+}
@@ -0,0 +1,32 @@
+use crate::newtypes::LocalUserId;
+#[cfg(feature = "full")]
+use crate::schema::push_subscription;
+use chrono::{DateTime, Utc};
+
+/// A Web Push subscription of a single device, as returned by `PushManager.subscribe()` in the
+/// browser.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = push_subscription))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PushSubscription {
+  pub id: i32,
+  pub local_user_id: LocalUserId,
+  /// Url of the push service, where notifications for this device are sent to
+  pub endpoint: String,
+  /// Public key of the device, used to encrypt notifications
+  pub p256dh: String,
+  /// Authentication secret of the device
+  pub auth: String,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = push_subscription))]
+pub struct PushSubscriptionInsertForm {
+  pub local_user_id: LocalUserId,
+  pub endpoint: String,
+  pub p256dh: String,
+  pub auth: String,
+}
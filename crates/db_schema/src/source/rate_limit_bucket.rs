@@ -0,0 +1,16 @@
+#[cfg(feature = "full")]
+use crate::schema::rate_limit_bucket;
+use chrono::{DateTime, Utc};
+
+/// A rate limit bucket which is shared between multiple lemmy processes.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(table_name = rate_limit_bucket))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct RateLimitBucket {
+  /// The ip address group or account which is limited, eg `ip:1.2.3.4` or `user:5`.
+  pub key: String,
+  pub action_type: String,
+  pub tokens: f64,
+  pub last_checked: DateTime<Utc>,
+}
@@ -0,0 +1,97 @@
+#[cfg(feature = "full")]
+use crate::schema::{saved_search, saved_search_match};
+use crate::newtypes::{
+  CommentId,
+  CommunityId,
+  LocalUserId,
+  PersonId,
+  PostId,
+  SavedSearchId,
+  SavedSearchMatchId,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = saved_search))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A search which is regularly checked for new posts and comments.
+pub struct SavedSearch {
+  pub id: SavedSearchId,
+  pub local_user_id: LocalUserId,
+  /// The search query, which may contain search operators.
+  pub query: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub creator_id: Option<PersonId>,
+  pub post_title_only: bool,
+  /// Send an email for each new match.
+  pub send_email: bool,
+  /// The newest post which was already checked.
+  #[serde(skip)]
+  pub last_post_id: Option<PostId>,
+  /// The newest comment which was already checked.
+  #[serde(skip)]
+  pub last_comment_id: Option<CommentId>,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = saved_search))]
+pub struct SavedSearchInsertForm {
+  pub local_user_id: LocalUserId,
+  pub query: String,
+  pub community_id: Option<CommunityId>,
+  pub creator_id: Option<PersonId>,
+  pub post_title_only: bool,
+  pub send_email: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = saved_search))]
+pub struct SavedSearchUpdateForm {
+  pub send_email: Option<bool>,
+  pub last_post_id: Option<Option<PostId>>,
+  pub last_comment_id: Option<Option<CommentId>>,
+}
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = saved_search_match))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A new post or comment which matched a saved search. For comments, the post is also set.
+pub struct SavedSearchMatch {
+  pub id: SavedSearchMatchId,
+  pub saved_search_id: SavedSearchId,
+  pub post_id: PostId,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment_id: Option<CommentId>,
+  pub read: bool,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = saved_search_match))]
+pub struct SavedSearchMatchInsertForm {
+  pub saved_search_id: SavedSearchId,
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+}
+
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = saved_search_match))]
+pub struct SavedSearchMatchUpdateForm {
+  pub read: Option<bool>,
+}
@@ -9,4 +9,9 @@ use crate::sensitive::SensitiveString;
 pub struct Secret {
   pub id: i32,
   pub jwt_secret: SensitiveString,
+  /// Private key for signing Web Push requests, as base64 encoded PKCS#8
+  pub vapid_private_key: Option<SensitiveString>,
+  /// Public key for Web Push, as base64url encoded uncompressed P-256 point. Browsers need it to
+  /// subscribe.
+  pub vapid_public_key: Option<String>,
 }
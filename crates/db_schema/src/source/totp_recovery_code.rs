@@ -0,0 +1,24 @@
+#[cfg(feature = "full")]
+use crate::schema::totp_recovery_code;
+use crate::{newtypes::LocalUserId, sensitive::SensitiveString};
+use chrono::{DateTime, Utc};
+
+/// A one-time code which can be used to login instead of a totp token.
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = totp_recovery_code))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct TotpRecoveryCode {
+  pub id: i32,
+  pub local_user_id: LocalUserId,
+  /// Sha256 hash of the code.
+  pub code_hash: SensitiveString,
+  pub published: DateTime<Utc>,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = totp_recovery_code))]
+pub struct TotpRecoveryCodeForm {
+  pub local_user_id: LocalUserId,
+  pub code_hash: String,
+}
@@ -0,0 +1,90 @@
+#[cfg(feature = "full")]
+use crate::schema::{webhook, webhook_delivery};
+use crate::{
+  newtypes::{CommunityId, PersonId, WebhookId},
+  WebhookEventType,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An url which receives events as signed json requests.
+pub struct Webhook {
+  pub id: WebhookId,
+  pub creator_id: PersonId,
+  /// Only events from this community are sent. Webhooks without community are managed by admins
+  /// and receive the events of the whole site.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community_id: Option<CommunityId>,
+  pub url: String,
+  /// Key for the HMAC-SHA256 signature in the `X-Lemmy-Signature` header.
+  pub secret: String,
+  pub events: Vec<WebhookEventType>,
+  pub enabled: bool,
+  pub published: DateTime<Utc>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+pub struct WebhookInsertForm {
+  pub creator_id: PersonId,
+  pub community_id: Option<CommunityId>,
+  pub url: String,
+  pub secret: String,
+  pub events: Vec<WebhookEventType>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WebhookUpdateForm {
+  pub url: Option<String>,
+  pub secret: Option<String>,
+  pub events: Option<Vec<WebhookEventType>>,
+  pub enabled: Option<bool>,
+  pub updated: Option<Option<DateTime<Utc>>>,
+}
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A single event which is sent to a webhook.
+pub struct WebhookDelivery {
+  pub id: i32,
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+  /// The json request body.
+  pub payload: String,
+  /// Number of delivery attempts so far.
+  pub attempts: i32,
+  /// The http status of the last response.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub response_status: Option<i32>,
+  /// The error from the last failed delivery attempt.
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub last_error: Option<String>,
+  pub delivered: bool,
+  /// Delivery was given up after too many attempts.
+  pub failed: bool,
+  pub next_attempt: DateTime<Utc>,
+  pub published: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+pub struct WebhookDeliveryInsertForm {
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+  pub payload: String,
+}
@@ -157,11 +157,12 @@ pub trait Reportable {
   ) -> Result<usize, Error>
   where
     Self: Sized;
+  /// Resolves all unresolved reports for the object, and returns them.
   async fn resolve_all_for_object(
     pool: &mut DbPool<'_>,
     comment_id_: Self::ObjectIdType,
     by_resolver_id: PersonId,
-  ) -> Result<usize, Error>
+  ) -> Result<Vec<Self>, Error>
   where
     Self: Sized;
   async fn unresolve(
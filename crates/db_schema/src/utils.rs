@@ -250,6 +250,33 @@ pub fn fuzzy_search(q: &str) -> String {
   format!("%{replaced}%")
 }
 
+/// Regular expression which matches http(s) urls on `domain`, including its subdomains and any
+/// explicit port. All characters besides letters and digits are escaped, so the domain is matched
+/// literally.
+pub fn url_domain_regex(domain: &str) -> String {
+  let escaped = domain
+    .chars()
+    .map(|c| {
+      if c.is_alphanumeric() {
+        c.to_string()
+      } else {
+        format!("\\{c}")
+      }
+    })
+    .collect::<String>();
+  format!("^https?://([^/?#@]*\\.)?{escaped}(:[0-9]+)?([/?#]|$)")
+}
+
+/// Pattern which matches lowercase text starting with `q`, for use with `lower(column) LIKE`.
+pub fn prefix_search(q: &str) -> String {
+  let replaced = q
+    .to_lowercase()
+    .replace('\\', "\\\\")
+    .replace('%', "\\%")
+    .replace('_', "\\_");
+  format!("{replaced}%")
+}
+
 pub fn limit_and_offset(
   page: Option<i64>,
   limit: Option<i64>,
@@ -463,6 +490,7 @@ pub fn post_to_comment_sort_type(sort: SortType) -> CommentSortType {
     SortType::New | SortType::NewComments | SortType::MostComments => CommentSortType::New,
     SortType::Old => CommentSortType::Old,
     SortType::Controversial => CommentSortType::Controversial,
+    SortType::Relevance => CommentSortType::Relevance,
     SortType::TopHour
     | SortType::TopSixHour
     | SortType::TopTwelveHour
@@ -483,7 +511,12 @@ static EMAIL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 });
 
 pub mod functions {
-  use diesel::sql_types::{BigInt, Bool, Text, Timestamptz};
+  use crate::schema::sql_types::Tsvector;
+  use diesel::{
+    expression::Expression,
+    sql_types::{BigInt, Bool, Text, Timestamptz},
+    IntoSql,
+  };
 
   sql_function! {
     #[sql_name = "r.hot_rank"]
@@ -508,6 +541,41 @@ pub mod functions {
   sql_function!(fn coalesce<T: diesel::sql_types::SqlType + diesel::sql_types::SingleValue>(x: diesel::sql_types::Nullable<T>, y: T) -> T);
 
   sql_function!(fn set_config(setting_name: Text, new_value: Text, is_local: Bool) -> Text);
+
+  #[derive(diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "tsquery", schema = "pg_catalog"))]
+  pub struct Tsquery;
+
+  sql_function! {
+    #[sql_name = "r.search_query"]
+    fn search_query(search_term: Text, language_code: Text) -> Tsquery;
+  }
+
+  sql_function!(fn ts_rank_cd(vector: Tsvector, query: Tsquery) -> Float);
+
+  diesel::infix_operator!(TextSearchMatches, " @@ ", backend: diesel::pg::Pg);
+
+  /// Checks if a `search_vector` column matches the query built by `search_query`
+  pub fn text_search_matches<V, Q>(vector: V, query: Q) -> TextSearchMatches<V, Q>
+  where
+    V: Expression<SqlType = Tsvector>,
+    Q: Expression<SqlType = Tsquery>,
+  {
+    TextSearchMatches::new(vector, query)
+  }
+
+  diesel::infix_operator!(RegexMatchesCi, " ~* ", backend: diesel::pg::Pg);
+
+  /// Case insensitive match of a text column against a postgres regular expression
+  pub fn regex_matches_ci<T>(
+    text: T,
+    pattern: String,
+  ) -> RegexMatchesCi<T, diesel::dsl::AsExprOf<String, Text>>
+  where
+    T: Expression,
+  {
+    RegexMatchesCi::new(text, pattern.into_sql::<Text>())
+  }
 }
 
 pub const DELETED_REPLACEMENT_TEXT: &str = "*Permanently Deleted*";
@@ -595,6 +663,20 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_prefix_search() {
+    assert_eq!(prefix_search("Rust_Lang"), "rust\\_lang%".to_string());
+    assert_eq!(prefix_search("100%"), "100\\%%".to_string());
+  }
+
+  #[test]
+  fn test_url_domain_regex() {
+    assert_eq!(
+      url_domain_regex("example.com"),
+      "^https?://([^/?#@]*\\.)?example\\.com(:[0-9]+)?([/?#]|$)".to_string()
+    );
+  }
+
   #[test]
   fn test_email() {
     assert!(is_email_regex("gush@gmail.com"));
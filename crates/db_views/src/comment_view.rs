@@ -1,8 +1,10 @@
-use crate::structs::CommentView;
+use crate::structs::{CommentView, PaginationCursor};
+use chrono::{DateTime, Utc};
 use diesel::{
   dsl::{exists, not},
   pg::Pg,
   result::Error,
+  sql_query,
   sql_types,
   BoolExpressionMethods,
   BoxableExpression,
@@ -10,12 +12,14 @@ use diesel::{
   IntoSql,
   JoinOnDsl,
   NullableExpressionMethods,
-  PgTextExpressionMethods,
+  OptionalExtension,
   QueryDsl,
+  QueryableByName,
 };
 use diesel_async::RunQueryDsl;
-use diesel_ltree::{nlevel, subpath, Ltree, LtreeExtensions};
+use diesel_ltree::{nlevel, sql_types::Ltree as LtreeType, subpath, Ltree, LtreeExtensions};
 use lemmy_db_schema::{
+  aggregates::structs::CommentAggregates,
   impls::local_user::LocalUserOptionHelper,
   newtypes::{CommentId, CommunityId, LocalUserId, PersonId, PostId},
   schema::{
@@ -23,6 +27,7 @@ use lemmy_db_schema::{
     comment_aggregates,
     comment_like,
     comment_saved,
+    comment_search,
     community,
     community_block,
     community_follower,
@@ -36,7 +41,16 @@ use lemmy_db_schema::{
     post,
   },
   source::local_user::LocalUser,
-  utils::{fuzzy_search, limit_and_offset, DbConn, DbPool, ListFn, Queries, ReadFn},
+  utils::{
+    functions::{search_query, text_search_matches, ts_rank_cd},
+    get_conn,
+    limit_and_offset,
+    DbConn,
+    DbPool,
+    ListFn,
+    Queries,
+    ReadFn,
+  },
   CommentSortType,
   ListingType,
 };
@@ -203,15 +217,64 @@ fn queries<'a>() -> Queries<
     if let Some(parent_path) = options.parent_path.as_ref() {
       query = query.filter(comment::path.contained_by(parent_path));
     };
+
+    if let Some(comment_ids) = options.comment_ids.clone() {
+      query = query.filter(comment::id.eq_any(comment_ids));
+    };
+    // The search term is stemmed with the interface language of the user, in addition to the
+    // language independent `simple` config
+    let search_language = options
+      .local_user
+      .map(|l| l.interface_language.clone())
+      .unwrap_or_default();
+
     //filtering out removed and deleted comments from search
-    if let Some(search_term) = options.search_term {
+    if let Some(search_term) = &options.search_term {
       query = query.filter(
-        comment::content
-          .ilike(fuzzy_search(&search_term))
+        comment::id
+          .eq_any(
+            comment_search::table
+              .select(comment_search::comment_id)
+              .filter(text_search_matches(
+                comment_search::search_vector,
+                search_query(search_term.clone(), search_language.clone()),
+              )),
+          )
           .and(not(comment::removed.or(comment::deleted))),
       );
     };
 
+    if !options.exclude_terms.is_empty() {
+      // Hide comments which match any of the terms, using the `or` of the websearch syntax
+      let exclude_term = options.exclude_terms.join(" or ");
+      query = query.filter(not(
+        comment::id.eq_any(
+          comment_search::table
+            .select(comment_search::comment_id)
+            .filter(text_search_matches(
+              comment_search::search_vector,
+              search_query(exclude_term, search_language.clone()),
+            )),
+        ),
+      ));
+    }
+
+    if let Some(id_after) = options.id_after {
+      query = query.filter(comment::id.gt(id_after));
+    }
+
+    if let Some(id_before_or_equal) = options.id_before_or_equal {
+      query = query.filter(comment::id.le(id_before_or_equal));
+    }
+
+    if let Some(published_after) = options.published_after {
+      query = query.filter(comment::published.ge(published_after));
+    }
+
+    if let Some(published_before) = options.published_before {
+      query = query.filter(comment::published.lt(published_before));
+    }
+
     if let Some(community_id) = options.community_id {
       query = query.filter(post::community_id.eq(community_id));
     }
@@ -297,14 +360,15 @@ fn queries<'a>() -> Queries<
 
     query = options.local_user.visible_communities_only(query);
 
+    let sort = match options.sort.unwrap_or(CommentSortType::Hot) {
+      // Without a search term there is nothing to rank by
+      CommentSortType::Relevance if options.search_term.is_none() => CommentSortType::Hot,
+      sort => sort,
+    };
+
     // A Max depth given means its a tree fetch
     let (limit, offset) = if let Some(max_depth) = options.max_depth {
-      let depth_limit = if let Some(parent_path) = options.parent_path.as_ref() {
-        parent_path.0.split('.').count() as i32 + max_depth
-        // Add one because of root "0"
-      } else {
-        max_depth + 1
-      };
+      let depth_limit = depth_limit(options.parent_path.as_ref(), max_depth);
 
       query = query.filter(nlevel(comment::path).le(depth_limit));
 
@@ -323,12 +387,87 @@ fn queries<'a>() -> Queries<
       // This does not work for comment trees, and the limit should be manually set to a high number
       //
       // If a max depth is given, then you know its a tree fetch, and limits should be ignored
-      // TODO a kludge to prevent attacks. Limit comments to 300 for now.
-      // (i64::MAX, 0)
-      (300, 0)
+      if let Some(comment_ids) = &options.comment_ids {
+        // The selected tree of a fetch with children_limit is bounded already
+        (i64::try_from(comment_ids.len()).unwrap_or(i64::MAX), 0)
+      } else {
+        // TODO a kludge to prevent attacks. Limit comments to 300 for now.
+        // (i64::MAX, 0)
+        (COMMENT_TREE_SIZE_LIMIT, 0)
+      }
     } else {
       // limit_and_offset_unlimited(options.page, options.limit)
-      limit_and_offset(options.page, options.limit)?
+      let (limit, offset) = limit_and_offset(options.page, options.limit)?;
+      if let Some(page_after) = options.page_after {
+        if offset != 0 {
+          return Err(Error::QueryBuilderError(
+            "legacy pagination cannot be combined with v2 pagination".into(),
+          ));
+        }
+        if options.saved_only.unwrap_or_default() {
+          return Err(Error::QueryBuilderError(
+            "saved comments can't be paginated with a cursor".into(),
+          ));
+        }
+        // Only return comments which come after the cursor, using the same columns as the
+        // ordering below
+        let counts = &page_after.counts;
+        let after_id = comment::id.lt(counts.comment_id);
+        let after: Box<dyn BoxableExpression<_, Pg, SqlType = sql_types::Bool>> = match sort {
+          CommentSortType::Hot => Box::new(
+            comment_aggregates::hot_rank.lt(counts.hot_rank).or(
+              comment_aggregates::hot_rank.eq(counts.hot_rank).and(
+                comment_aggregates::score
+                  .lt(counts.score)
+                  .or(comment_aggregates::score.eq(counts.score).and(after_id)),
+              ),
+            ),
+          ),
+          CommentSortType::Controversial => Box::new(
+            comment_aggregates::controversy_rank
+              .lt(counts.controversy_rank)
+              .or(
+                comment_aggregates::controversy_rank
+                  .eq(counts.controversy_rank)
+                  .and(after_id),
+              ),
+          ),
+          CommentSortType::New => Box::new(
+            comment::published
+              .lt(counts.published)
+              .or(comment::published.eq(counts.published).and(after_id)),
+          ),
+          CommentSortType::Old => Box::new(
+            comment::published.gt(counts.published).or(
+              comment::published
+                .eq(counts.published)
+                .and(comment::id.gt(counts.comment_id)),
+            ),
+          ),
+          CommentSortType::Top => Box::new(
+            comment_aggregates::score
+              .lt(counts.score)
+              .or(comment_aggregates::score.eq(counts.score).and(after_id)),
+          ),
+          // The relevance depends on the search term, so it can't be stored in the cursor
+          CommentSortType::Relevance => {
+            return Err(Error::QueryBuilderError(
+              "relevance sort can't be paginated with a cursor".into(),
+            ))
+          }
+        };
+        if options.post_id.is_some() || options.parent_path.is_some() {
+          let distinguished = page_after.distinguished;
+          query = query.filter(
+            comment::distinguished
+              .lt(distinguished)
+              .or(comment::distinguished.eq(distinguished).and(after)),
+          );
+        } else {
+          query = query.filter(after);
+        }
+      }
+      (limit, offset)
     };
 
     // distinguished comments should go first when viewing post
@@ -336,7 +475,7 @@ fn queries<'a>() -> Queries<
       query = query.then_order_by(comment::distinguished.desc());
     }
 
-    query = match options.sort.unwrap_or(CommentSortType::Hot) {
+    query = match sort {
       CommentSortType::Hot => query
         .then_order_by(comment_aggregates::hot_rank.desc())
         .then_order_by(comment_aggregates::score.desc()),
@@ -346,6 +485,22 @@ fn queries<'a>() -> Queries<
       CommentSortType::New => query.then_order_by(comment::published.desc()),
       CommentSortType::Old => query.then_order_by(comment::published.asc()),
       CommentSortType::Top => query.then_order_by(comment_aggregates::score.desc()),
+      CommentSortType::Relevance => query.then_order_by(
+        comment_search::table
+          .filter(comment_search::comment_id.eq(comment::id))
+          .select(ts_rank_cd(
+            comment_search::search_vector,
+            search_query(options.search_term.unwrap_or_default(), search_language),
+          ))
+          .single_value()
+          .desc(),
+      ),
+    };
+
+    // finally use unique comment id as tie breaker, so that cursor pagination is stable
+    query = match sort {
+      CommentSortType::Old => query.then_order_by(comment::id.asc()),
+      _ => query.then_order_by(comment::id.desc()),
     };
 
     // Note: deleted and removed comments are done on the front side
@@ -383,6 +538,175 @@ impl CommentView {
   }
 }
 
+/// The maximum `nlevel` of the comment paths in a tree fetch.
+fn depth_limit(parent_path: Option<&Ltree>, max_depth: i32) -> i32 {
+  if let Some(parent_path) = parent_path {
+    parent_path.0.split('.').count() as i32 + max_depth
+    // Add one because of root "0"
+  } else {
+    max_depth + 1
+  }
+}
+
+/// The maximum number of comments in a tree fetch.
+const COMMENT_TREE_SIZE_LIMIT: i64 = 300;
+
+/// A comment which was selected for a tree fetch with `children_limit`, before the other filters
+/// of the list query were applied.
+#[derive(QueryableByName, Debug, Clone)]
+pub struct CommentTreeEntry {
+  #[diesel(sql_type = sql_types::Integer)]
+  pub id: CommentId,
+  #[diesel(sql_type = LtreeType)]
+  pub path: Ltree,
+  #[diesel(sql_type = sql_types::Integer)]
+  pub child_count: i32,
+  /// The position among the selected children of the same parent, starting at 1.
+  #[diesel(sql_type = sql_types::BigInt)]
+  pub position: i64,
+}
+
+impl CommentTreeEntry {
+  /// Selects the comment tree below `root`, with at most `children_limit` children for each
+  /// comment. The children are chosen with the same ordering as the list query, and the first
+  /// level starts after the `page_after` comment if given.
+  ///
+  /// The tree is selected level by level, and stops after `COMMENT_TREE_SIZE_LIMIT` comments. The
+  /// branches which are cut off this way can be loaded like those below `depth_limit`.
+  async fn select(
+    pool: &mut DbPool<'_>,
+    root: Ltree,
+    post_id: Option<PostId>,
+    depth_limit: i32,
+    children_limit: i64,
+    sort: CommentSortType,
+    page_after: Option<CommentId>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    // Old is the only ascending sort, so there distinguished is negated to compare the whole row
+    // in one direction
+    let (order, row, cmp) = match sort {
+      // Tree fetches have no search term, so relevance is the same as hot
+      CommentSortType::Hot | CommentSortType::Relevance => (
+        "c.distinguished DESC, ca.hot_rank DESC, ca.score DESC, c.id DESC",
+        "c.distinguished, ca.hot_rank, ca.score, c.id",
+        "<",
+      ),
+      CommentSortType::Controversial => (
+        "c.distinguished DESC, ca.controversy_rank DESC, c.id DESC",
+        "c.distinguished, ca.controversy_rank, c.id",
+        "<",
+      ),
+      CommentSortType::New => (
+        "c.distinguished DESC, c.published DESC, c.id DESC",
+        "c.distinguished, c.published, c.id",
+        "<",
+      ),
+      CommentSortType::Old => (
+        "c.distinguished DESC, c.published ASC, c.id ASC",
+        "NOT c.distinguished, c.published, c.id",
+        ">",
+      ),
+      CommentSortType::Top => (
+        "c.distinguished DESC, ca.score DESC, c.id DESC",
+        "c.distinguished, ca.score, c.id",
+        "<",
+      ),
+    };
+    // Only the constant ordering fragments are inserted into the query, the values are bound
+    sql_query(format!(
+      "WITH RECURSIVE tree AS (
+        (SELECT c.id, c.path, ca.child_count, row_number() OVER (ORDER BY {order}) AS position
+          FROM comment c
+          JOIN comment_aggregates ca ON ca.comment_id = c.id
+          WHERE c.path <@ $1
+            AND nlevel(c.path) = nlevel($1) + 1
+            AND ($2 IS NULL OR c.post_id = $2)
+            AND ($3 IS NULL OR ({row}) {cmp} (
+              SELECT {row}
+                FROM comment c
+                JOIN comment_aggregates ca ON ca.comment_id = c.id
+                WHERE c.id = $3))
+          ORDER BY {order}
+          LIMIT $4)
+        UNION ALL
+        SELECT child.id, child.path, child.child_count, child.position
+          FROM tree
+          CROSS JOIN LATERAL (
+            SELECT c.id, c.path, ca.child_count, row_number() OVER (ORDER BY {order}) AS position
+              FROM comment c
+              JOIN comment_aggregates ca ON ca.comment_id = c.id
+              WHERE c.path <@ tree.path
+                AND nlevel(c.path) = nlevel(tree.path) + 1
+              ORDER BY {order}
+              LIMIT $4) child
+          WHERE nlevel(tree.path) < $5)
+      SELECT id, path, child_count, position FROM tree LIMIT $6"
+    ))
+    .bind::<LtreeType, _>(root)
+    .bind::<sql_types::Nullable<sql_types::Integer>, _>(post_id)
+    .bind::<sql_types::Nullable<sql_types::Integer>, _>(page_after)
+    .bind::<sql_types::BigInt, _>(children_limit)
+    .bind::<sql_types::Integer, _>(depth_limit)
+    .bind::<sql_types::BigInt, _>(COMMENT_TREE_SIZE_LIMIT)
+    .load::<Self>(conn)
+    .await
+  }
+
+  /// The parent comment, or `None` for top level comments.
+  pub fn parent_id(&self) -> Option<CommentId> {
+    let mut ids = self.path.0.split('.').rev().skip(1);
+    ids
+      .next()
+      .and_then(|id| id.parse().ok())
+      .filter(|id| *id != 0)
+      .map(CommentId)
+  }
+}
+
+impl PaginationCursor {
+  // get cursor for page that starts immediately after the given comment
+  pub fn after_comment(view: &CommentView) -> PaginationCursor {
+    PaginationCursor::after_comment_id(view.comment.id)
+  }
+  pub fn after_comment_id(comment_id: CommentId) -> PaginationCursor {
+    // hex encoding to prevent ossification
+    PaginationCursor(format!("C{:x}", comment_id.0))
+  }
+  pub async fn read_comment(
+    &self,
+    pool: &mut DbPool<'_>,
+  ) -> Result<CommentPaginationCursorData, Error> {
+    let err_msg = || Error::QueryBuilderError("Could not parse pagination token".into());
+    let comment_id = self
+      .0
+      .strip_prefix('C')
+      .and_then(|e| i32::from_str_radix(e, 16).ok())
+      .ok_or_else(err_msg)?;
+    let conn = &mut get_conn(pool).await?;
+    let (counts, distinguished) = comment_aggregates::table
+      .inner_join(comment::table)
+      .filter(comment_aggregates::comment_id.eq(CommentId(comment_id)))
+      .select((comment_aggregates::all_columns, comment::distinguished))
+      .first::<(CommentAggregates, bool)>(conn)
+      .await
+      .optional()?
+      .ok_or_else(err_msg)?;
+    Ok(CommentPaginationCursorData {
+      counts,
+      distinguished,
+    })
+  }
+}
+
+/// The values of the comment which a page starts after. Only some of them are used, depending on
+/// the sort type.
+#[derive(Clone)]
+pub struct CommentPaginationCursorData {
+  counts: CommentAggregates,
+  distinguished: bool,
+}
+
 #[derive(Default)]
 pub struct CommentQuery<'a> {
   pub listing_type: Option<ListingType>,
@@ -393,16 +717,76 @@ pub struct CommentQuery<'a> {
   pub creator_id: Option<PersonId>,
   pub local_user: Option<&'a LocalUser>,
   pub search_term: Option<String>,
+  pub published_after: Option<DateTime<Utc>>,
+  pub published_before: Option<DateTime<Utc>>,
+  /// Hide comments which match any of these search terms.
+  pub exclude_terms: Vec<String>,
+  /// Only comments with a higher id, used to check new comments against saved searches.
+  pub id_after: Option<CommentId>,
+  pub id_before_or_equal: Option<CommentId>,
   pub saved_only: Option<bool>,
   pub liked_only: Option<bool>,
   pub disliked_only: Option<bool>,
   pub page: Option<i64>,
   pub limit: Option<i64>,
+  /// For tree fetches with max_depth, this only applies to the first level, and only together
+  /// with children_limit.
+  pub page_after: Option<CommentPaginationCursorData>,
   pub max_depth: Option<i32>,
+  /// For tree fetches, the maximum number of children to return for each comment. The comment at
+  /// parent_path itself is not included then.
+  pub children_limit: Option<i64>,
+  /// Only these comments, used for the selected tree of a fetch with children_limit.
+  pub comment_ids: Option<Vec<CommentId>>,
 }
 
 impl<'a> CommentQuery<'a> {
   pub async fn list(self, pool: &mut DbPool<'_>) -> Result<Vec<CommentView>, Error> {
+    if self.max_depth.is_some() && self.children_limit.is_some() {
+      Ok(self.list_tree(pool).await?.0)
+    } else {
+      self.list_filtered(pool).await
+    }
+  }
+
+  /// A tree fetch with max_depth and children_limit. The children are selected before the other
+  /// filters are applied, so a comment can have fewer children than the limit. The selected
+  /// comments are also returned, so that the cursors for loading the remaining children can be
+  /// built from them.
+  pub async fn list_tree(
+    self,
+    pool: &mut DbPool<'_>,
+  ) -> Result<(Vec<CommentView>, Vec<CommentTreeEntry>), Error> {
+    let (Some(max_depth), Some(children_limit)) = (self.max_depth, self.children_limit) else {
+      return Err(Error::QueryBuilderError(
+        "comment trees need max_depth and children_limit".into(),
+      ));
+    };
+    if self.post_id.is_none() && self.parent_path.is_none() {
+      return Err(Error::QueryBuilderError(
+        "comment trees need a post_id or parent_path".into(),
+      ));
+    }
+    let selected = CommentTreeEntry::select(
+      pool,
+      self.parent_path.clone().unwrap_or(Ltree("0".into())),
+      self.post_id,
+      depth_limit(self.parent_path.as_ref(), max_depth),
+      children_limit,
+      self.sort.unwrap_or(CommentSortType::Hot),
+      self.page_after.as_ref().map(|c| c.counts.comment_id),
+    )
+    .await?;
+    let comments = CommentQuery {
+      comment_ids: Some(selected.iter().map(|e| e.id).collect()),
+      ..self
+    }
+    .list_filtered(pool)
+    .await?;
+    Ok((comments, selected))
+  }
+
+  async fn list_filtered(self, pool: &mut DbPool<'_>) -> Result<Vec<CommentView>, Error> {
     let is_admin = self.local_user.map(|u| u.admin).unwrap_or(false);
     Ok(
       queries()
@@ -428,8 +812,9 @@ mod tests {
 
   use crate::{
     comment_view::{CommentQuery, CommentSortType, CommentView, DbPool},
-    structs::LocalUserView,
+    structs::{LocalUserView, PaginationCursor},
   };
+  use chrono::{TimeDelta, Utc};
   use lemmy_db_schema::{
     aggregates::structs::CommentAggregates,
     assert_length,
@@ -993,6 +1378,239 @@ mod tests {
     cleanup(data, pool).await
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_cursor_pagination() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let form = CommentUpdateForm {
+      distinguished: Some(true),
+      ..Default::default()
+    };
+    Comment::update(pool, data.inserted_comment_2.id, &form).await?;
+
+    for sort in [
+      CommentSortType::Hot,
+      CommentSortType::Top,
+      CommentSortType::New,
+      CommentSortType::Old,
+      CommentSortType::Controversial,
+    ] {
+      let all_ids = CommentQuery {
+        sort: Some(sort),
+        post_id: Some(data.inserted_post.id),
+        ..Default::default()
+      }
+      .list(pool)
+      .await?
+      .into_iter()
+      .map(|c| c.comment.id)
+      .collect::<Vec<_>>();
+      assert_length!(6, all_ids);
+
+      // Fetch two comments at a time, until there are no more
+      let mut paged_ids = vec![];
+      let mut page_after = None;
+      loop {
+        let comments = CommentQuery {
+          sort: Some(sort),
+          post_id: Some(data.inserted_post.id),
+          limit: Some(2),
+          page_after,
+          ..Default::default()
+        }
+        .list(pool)
+        .await?;
+        let Some(last) = comments.last() else {
+          break;
+        };
+        page_after = Some(PaginationCursor::after_comment(last).read_comment(pool).await?);
+        paged_ids.extend(comments.iter().map(|c| c.comment.id));
+      }
+      assert_eq!(all_ids, paged_ids);
+    }
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_children_limit() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Only the first child of each comment
+    let comments = CommentQuery {
+      sort: Some(CommentSortType::Old),
+      post_id: Some(data.inserted_post.id),
+      max_depth: Some(8),
+      children_limit: Some(1),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    let ids: Vec<_> = comments.iter().map(|c| c.comment.id).collect();
+    assert_eq!(ids.len(), 3);
+    assert_eq!(ids[0], data.inserted_comment_0.id);
+    assert_eq!(ids[1], data.inserted_comment_1.id);
+
+    // Load the remaining children of comment 0
+    let page_after = Some(
+      PaginationCursor::after_comment(&comments[1])
+        .read_comment(pool)
+        .await?,
+    );
+    let comments = CommentQuery {
+      sort: Some(CommentSortType::Old),
+      parent_path: Some(data.inserted_comment_0.path.clone()),
+      max_depth: Some(8),
+      children_limit: Some(1),
+      page_after,
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_length!(1, comments);
+    assert_eq!(comments[0].comment.id, data.inserted_comment_2.id);
+
+    // Comment 1 is selected for the tree, but hidden because timmy blocked its creator
+    let (comments, selected) = CommentQuery {
+      sort: Some(CommentSortType::Old),
+      post_id: Some(data.inserted_post.id),
+      local_user: Some(&data.timmy_local_user_view.local_user),
+      max_depth: Some(8),
+      children_limit: Some(1),
+      ..Default::default()
+    }
+    .list_tree(pool)
+    .await?;
+    assert!(!comments
+      .iter()
+      .any(|c| c.comment.id == data.inserted_comment_1.id));
+    let selected_1 = selected
+      .iter()
+      .find(|e| e.id == data.inserted_comment_1.id)
+      .expect("comment 1 is selected");
+    assert_eq!(Some(data.inserted_comment_0.id), selected_1.parent_id());
+    assert_eq!(1, selected_1.position);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_search_operators() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let week_ago = Utc::now() - TimeDelta::days(7);
+    let old_comment_form = CommentInsertForm::builder()
+      .content("The weather was nice".into())
+      .creator_id(data.timmy_local_user_view.person.id)
+      .post_id(data.inserted_post.id)
+      .published(Some(Utc::now() - TimeDelta::days(30)))
+      .build();
+    let old_comment = Comment::create(pool, &old_comment_form, None).await?;
+
+    let comments_before = CommentQuery {
+      post_id: Some(data.inserted_post.id),
+      published_before: Some(week_ago),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_length!(1, comments_before);
+    assert_eq!(old_comment.id, comments_before[0].comment.id);
+
+    let comments_after = CommentQuery {
+      post_id: Some(data.inserted_post.id),
+      published_after: Some(week_ago),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_length!(6, comments_after);
+    assert!(!comments_after.iter().any(|c| c.comment.id == old_comment.id));
+
+    // Comments matching any of the terms are hidden
+    let comments_exclude = CommentQuery {
+      post_id: Some(data.inserted_post.id),
+      exclude_terms: vec!["weather".to_string(), "blocked".to_string()],
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_length!(5, comments_exclude);
+    assert!(!comments_exclude.iter().any(|c| c.comment.id == old_comment.id
+      || c.comment.id == data.inserted_comment_1.id));
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_search() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let english_id = Language::read_id_from_code(pool, Some("en")).await?;
+    let mut search_comments = vec![];
+    for content in [
+      "I was running late",
+      "Walking home",
+      "Running, running and running again",
+    ] {
+      let form = CommentInsertForm::builder()
+        .content(content.into())
+        .creator_id(data.timmy_local_user_view.person.id)
+        .post_id(data.inserted_post.id)
+        .language_id(english_id)
+        .build();
+      search_comments.push(Comment::create(pool, &form, None).await?);
+    }
+    let [running_late, _, running_again] = [0, 1, 2].map(|i| search_comments[i].id);
+
+    // Without a language, only the unchanged words match
+    let comments_simple = CommentQuery {
+      search_term: Some("runs".to_string()),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_length!(0, comments_simple);
+
+    // "runs" and "running" have the same english stem, more matches rank higher
+    let local_user = LocalUser {
+      interface_language: "en".to_string(),
+      ..data.timmy_local_user_view.local_user.clone()
+    };
+    let comments_relevance = CommentQuery {
+      sort: Some(CommentSortType::Relevance),
+      search_term: Some("runs".to_string()),
+      local_user: Some(&local_user),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_eq!(
+      vec![running_again, running_late],
+      comments_relevance
+        .iter()
+        .map(|c| c.comment.id)
+        .collect::<Vec<_>>()
+    );
+
+    for comment in search_comments {
+      Comment::delete(pool, comment.id).await?;
+    }
+    cleanup(data, pool).await
+  }
+
   async fn cleanup(data: Data, pool: &mut DbPool<'_>) -> LemmyResult<()> {
     CommentLike::remove(
       pool,
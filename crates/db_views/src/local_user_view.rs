@@ -1,5 +1,6 @@
 use crate::structs::LocalUserView;
 use actix_web::{dev::Payload, FromRequest, HttpMessage, HttpRequest};
+use chrono::{DateTime, Duration, Utc};
 use diesel::{result::Error, BoolExpressionMethods, ExpressionMethods, JoinOnDsl, QueryDsl};
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
@@ -7,12 +8,14 @@ use lemmy_db_schema::{
   schema::{local_user, local_user_vote_display_mode, person, person_aggregates},
   utils::{
     functions::{coalesce, lower},
+    limit_and_offset,
     DbConn,
     DbPool,
     ListFn,
     Queries,
     ReadFn,
   },
+  EmailNotificationFrequency,
 };
 use lemmy_utils::error::{LemmyError, LemmyErrorType};
 use std::future::{ready, Ready};
@@ -27,6 +30,8 @@ enum ReadBy<'a> {
 
 enum ListMode {
   AdminsWithEmails,
+  FailedLogins(Option<i64>, Option<i64>),
+  DueEmailDigests(DateTime<Utc>),
 }
 
 fn queries<'a>(
@@ -79,6 +84,49 @@ fn queries<'a>(
           .load::<LocalUserView>(&mut conn)
           .await
       }
+      ListMode::FailedLogins(page, limit) => {
+        let (limit, offset) = limit_and_offset(page, limit)?;
+        local_user::table
+          .inner_join(local_user_vote_display_mode::table)
+          .inner_join(person::table)
+          .inner_join(person_aggregates::table.on(person::id.eq(person_aggregates::person_id)))
+          .filter(local_user::failed_login_count.gt(0))
+          .order_by(local_user::last_failed_login.desc())
+          .limit(limit)
+          .offset(offset)
+          .select(selection)
+          .load::<LocalUserView>(&mut conn)
+          .await
+      }
+      ListMode::DueEmailDigests(now) => {
+        // The digest job doesn't run at exactly the same time every hour, so allow digests to be
+        // sent a few minutes early. Otherwise they would be delayed by an hour.
+        let tolerance = Duration::minutes(5);
+        let due = |frequency: EmailNotificationFrequency| {
+          let interval = frequency.digest_interval().unwrap_or_default();
+          local_user::email_notification_frequency.eq(frequency).and(
+            local_user::last_email_digest
+              .is_null()
+              .or(local_user::last_email_digest.lt(now - interval + tolerance)),
+          )
+        };
+        local_user::table
+          .inner_join(local_user_vote_display_mode::table)
+          .inner_join(person::table)
+          .inner_join(person_aggregates::table.on(person::id.eq(person_aggregates::person_id)))
+          .into_boxed()
+          .filter(local_user::email.is_not_null())
+          .filter(local_user::send_notifications_to_email.eq(true))
+          .filter(person::banned.eq(false))
+          .filter(
+            due(EmailNotificationFrequency::Hourly)
+              .or(due(EmailNotificationFrequency::Daily))
+              .or(due(EmailNotificationFrequency::Weekly)),
+          )
+          .select(selection)
+          .load::<LocalUserView>(&mut conn)
+          .await
+      }
     }
   };
 
@@ -123,6 +171,24 @@ impl LocalUserView {
   pub async fn list_admins_with_emails(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
     queries().list(pool, ListMode::AdminsWithEmails).await
   }
+
+  /// Users with recent failed logins, for admins to spot password guessing.
+  pub async fn list_failed_logins(
+    pool: &mut DbPool<'_>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    queries()
+      .list(pool, ListMode::FailedLogins(page, limit))
+      .await
+  }
+
+  /// Users who receive notifications as a digest, and whose next digest email is due.
+  pub async fn list_due_email_digests(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    queries()
+      .list(pool, ListMode::DueEmailDigests(Utc::now()))
+      .await
+  }
 }
 
 impl FromRequest for LocalUserView {
@@ -136,3 +202,72 @@ impl FromRequest for LocalUserView {
     })
   }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::structs::LocalUserView;
+  use chrono::{Duration, Utc};
+  use lemmy_db_schema::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+    EmailNotificationFrequency,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_due_email_digests() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+    let users = [
+      ("digest_instant", EmailNotificationFrequency::Instant, None),
+      ("digest_daily", EmailNotificationFrequency::Daily, None),
+      (
+        "digest_hourly",
+        EmailNotificationFrequency::Hourly,
+        Some(Utc::now() - Duration::minutes(10)),
+      ),
+      (
+        "digest_weekly",
+        EmailNotificationFrequency::Weekly,
+        Some(Utc::now() - Duration::days(8)),
+      ),
+    ];
+    for (name, frequency, last_email_digest) in users {
+      let person_form = PersonInsertForm::test_form(inserted_instance.id, name);
+      let person = Person::create(pool, &person_form).await?;
+      let local_user_form = LocalUserInsertForm {
+        email: Some(format!("{name}@example.com")),
+        send_notifications_to_email: Some(true),
+        email_notification_frequency: Some(frequency),
+        last_email_digest,
+        ..LocalUserInsertForm::test_form(person.id)
+      };
+      LocalUser::create(pool, &local_user_form, vec![]).await?;
+    }
+
+    // Instant notifications don't have a digest, and the hourly one was sent recently
+    let mut due: Vec<_> = LocalUserView::list_due_email_digests(pool)
+      .await?
+      .into_iter()
+      .map(|u| u.person.name)
+      .collect();
+    due.sort();
+    assert_eq!(vec!["digest_daily", "digest_weekly"], due);
+
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
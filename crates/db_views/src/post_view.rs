@@ -1,4 +1,5 @@
 use crate::structs::{PaginationCursor, PostView};
+use chrono::{DateTime, Utc};
 use diesel::{
   debug_query,
   dsl::{exists, not, IntervalDsl},
@@ -41,14 +42,16 @@ use lemmy_db_schema::{
     post_like,
     post_read,
     post_saved,
+    post_search,
   },
   source::{local_user::LocalUser, site::Site},
   utils::{
-    functions::coalesce,
+    functions::{coalesce, regex_matches_ci, search_query, text_search_matches, ts_rank_cd},
     fuzzy_search,
     get_conn,
     limit_and_offset,
     now,
+    url_domain_regex,
     Commented,
     DbConn,
     DbPool,
@@ -385,20 +388,62 @@ fn queries<'a>() -> Queries<
       query = query.filter(post::url.eq(url_search));
     }
 
+    if let Some(url_domain) = &options.url_domain {
+      query = query.filter(regex_matches_ci(post::url, url_domain_regex(url_domain)));
+    }
+
+    if let Some(id_after) = options.id_after {
+      query = query.filter(post_aggregates::post_id.gt(id_after));
+    }
+
+    if let Some(id_before_or_equal) = options.id_before_or_equal {
+      query = query.filter(post_aggregates::post_id.le(id_before_or_equal));
+    }
+
+    if let Some(published_after) = options.published_after {
+      query = query.filter(post_aggregates::published.ge(published_after));
+    }
+
+    if let Some(published_before) = options.published_before {
+      query = query.filter(post_aggregates::published.lt(published_before));
+    }
+
+    // The search term is stemmed with the interface language of the user, in addition to the
+    // language independent `simple` config
+    let search_language = options
+      .local_user
+      .map(|l| l.interface_language.clone())
+      .unwrap_or_default();
+
     if let Some(search_term) = &options.search_term {
-      let searcher = fuzzy_search(search_term);
       query = if options.title_only.unwrap_or_default() {
-        query.filter(post::name.ilike(searcher))
+        query.filter(post::name.ilike(fuzzy_search(search_term)))
       } else {
         query.filter(
-          post::name
-            .ilike(searcher.clone())
-            .or(post::body.ilike(searcher)),
+          post::id.eq_any(post_search::table.select(post_search::post_id).filter(
+            text_search_matches(
+              post_search::search_vector,
+              search_query(search_term.clone(), search_language.clone()),
+            ),
+          )),
         )
       }
       .filter(not(post::removed.or(post::deleted)));
     }
 
+    if !options.exclude_terms.is_empty() {
+      // Hide posts which match any of the terms, using the `or` of the websearch syntax
+      let exclude_term = options.exclude_terms.join(" or ");
+      query = query.filter(not(
+        post::id.eq_any(post_search::table.select(post_search::post_id).filter(
+          text_search_matches(
+            post_search::search_vector,
+            search_query(exclude_term, search_language.clone()),
+          ),
+        )),
+      ));
+    }
+
     if !options
       .show_nsfw
       .unwrap_or(options.local_user.show_nsfw(site))
@@ -484,6 +529,33 @@ fn queries<'a>() -> Queries<
     let (limit, offset) = limit_and_offset(options.page, options.limit)?;
     query = query.limit(limit).offset(offset);
 
+    // The relevance of a post depends on the search term, so it can't be used as a cursor key.
+    // These results can only be paginated with page numbers.
+    if options.sorts_by_relevance() {
+      if options.page_after.is_some() || options.page_before_or_equal.is_some() {
+        return Err(Error::QueryBuilderError(
+          "relevance sort can't be paginated with a cursor".into(),
+        ));
+      }
+      let relevance = post_search::table
+        .filter(post_search::post_id.eq(post_aggregates::post_id))
+        .select(ts_rank_cd(
+          post_search::search_vector,
+          search_query(options.search_term.unwrap_or_default(), search_language),
+        ))
+        .single_value();
+      let query = query
+        .then_order_by(relevance.desc())
+        .then_order_by(post_aggregates::post_id.desc());
+
+      debug!("Post View Query: {:?}", debug_query::<Pg, _>(&query));
+
+      return Commented::new(query)
+        .text("PostQuery::list")
+        .load::<PostView>(&mut conn)
+        .await;
+    }
+
     let mut query = PaginatedQueryBuilder::new(query);
 
     let page_after = options.page_after.map(|c| c.0);
@@ -512,7 +584,7 @@ fn queries<'a>() -> Queries<
     // then use the main sort
     query = match options.sort.unwrap_or(SortType::Hot) {
       SortType::Active => query.then_desc(key::hot_rank_active),
-      SortType::Hot => query.then_desc(key::hot_rank),
+      SortType::Hot | SortType::Relevance => query.then_desc(key::hot_rank),
       SortType::Scaled => query.then_desc(key::scaled_rank),
       SortType::Controversial => query.then_desc(key::controversy_rank),
       SortType::New => query.then_desc(key::published),
@@ -617,6 +689,15 @@ pub struct PostQuery<'a> {
   pub local_user: Option<&'a LocalUser>,
   pub search_term: Option<String>,
   pub url_search: Option<String>,
+  /// Only posts which link to this domain.
+  pub url_domain: Option<String>,
+  pub published_after: Option<DateTime<Utc>>,
+  pub published_before: Option<DateTime<Utc>>,
+  /// Hide posts which match any of these search terms.
+  pub exclude_terms: Vec<String>,
+  /// Only posts with a higher id, used to check new posts against saved searches.
+  pub id_after: Option<PostId>,
+  pub id_before_or_equal: Option<PostId>,
   pub saved_only: Option<bool>,
   pub liked_only: Option<bool>,
   pub disliked_only: Option<bool>,
@@ -632,6 +713,10 @@ pub struct PostQuery<'a> {
 }
 
 impl<'a> PostQuery<'a> {
+  fn sorts_by_relevance(&self) -> bool {
+    self.sort == Some(SortType::Relevance) && self.search_term.is_some()
+  }
+
   async fn prefetch_upper_bound_for_page_before(
     &self,
     site: &Site,
@@ -718,6 +803,7 @@ impl<'a> PostQuery<'a> {
       && self.community_id.is_none()
       && self.local_user.is_some()
       && self.page_before_or_equal.is_none()
+      && !self.sorts_by_relevance()
     {
       if let Some(query) = self
         .prefetch_upper_bound_for_page_before(site, pool)
@@ -739,7 +825,7 @@ mod tests {
     post_view::{PaginationCursorData, PostQuery, PostView},
     structs::LocalUserView,
   };
-  use chrono::Utc;
+  use chrono::{TimeDelta, Utc};
   use lemmy_db_schema::{
     aggregates::structs::PostAggregates,
     impls::actor_language::UNDETERMINED_ID,
@@ -1639,6 +1725,128 @@ mod tests {
     cleanup(data, pool).await
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn post_listing_search() -> LemmyResult<()> {
+    const RUNNING: &str = "Running in the rain";
+    const WEEKEND: &str = "Weekend plans";
+
+    let pool = &build_db_pool().await?;
+    let pool = &mut pool.into();
+    let mut data = init_data(pool).await?;
+
+    let english_id = Language::read_id_from_code(pool, Some("en"))
+      .await?
+      .expect("english should exist");
+
+    for (name, body) in [(RUNNING, None), (WEEKEND, Some("We will go running on sunday"))] {
+      let form = PostInsertForm::builder()
+        .name(name.to_string())
+        .body(body.map(ToString::to_string))
+        .creator_id(data.local_user_view.person.id)
+        .community_id(data.inserted_community.id)
+        .language_id(Some(english_id))
+        .build();
+      Post::create(pool, &form).await?;
+    }
+
+    // "runs" and "running" have the same english stem, matches in the title rank higher
+    data.local_user_view.local_user.interface_language = "en".to_string();
+    let post_listings_relevance = PostQuery {
+      sort: Some(SortType::Relevance),
+      search_term: Some("runs".to_string()),
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(vec![RUNNING, WEEKEND], names(&post_listings_relevance));
+
+    // Relevance can't be combined with cursor pagination
+    let first = post_listings_relevance
+      .first()
+      .ok_or(LemmyErrorType::CouldntFindPost)?;
+    let page_after = Some(PaginationCursorData(first.counts.clone()));
+    let post_listings_cursor = PostQuery {
+      sort: Some(SortType::Relevance),
+      search_term: Some("runs".to_string()),
+      page_after,
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await;
+    assert!(post_listings_cursor.is_err());
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn post_listing_search_operators() -> LemmyResult<()> {
+    const ON_DOMAIN: &str = "On the domain";
+    const ON_SUBDOMAIN: &str = "On a subdomain with a port";
+    const LOOKALIKE: &str = "Lookalike weather report";
+
+    let pool = &build_db_pool().await?;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let month_ago = Utc::now() - TimeDelta::days(30);
+    let week_ago = Utc::now() - TimeDelta::days(7);
+    for (name, url, published) in [
+      (ON_DOMAIN, "https://example.com/a", Some(month_ago)),
+      (ON_SUBDOMAIN, "http://sub.example.com:8080/b?c=d", None),
+      (LOOKALIKE, "https://example.com.other.org/c", None),
+    ] {
+      let form = PostInsertForm::builder()
+        .name(name.to_string())
+        .url(Some(Url::parse(url)?.into()))
+        .published(published)
+        .creator_id(data.local_user_view.person.id)
+        .community_id(data.inserted_community.id)
+        .build();
+      Post::create(pool, &form).await?;
+    }
+
+    // Subdomains and explicit ports match, other domains which contain the domain don't
+    let post_listings_domain = PostQuery {
+      url_domain: Some("example.com".to_string()),
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(vec![ON_SUBDOMAIN, ON_DOMAIN], names(&post_listings_domain));
+
+    let post_listings_before = PostQuery {
+      url_domain: Some("example.com".to_string()),
+      published_before: Some(week_ago),
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(vec![ON_DOMAIN], names(&post_listings_before));
+
+    let post_listings_after = PostQuery {
+      url_domain: Some("example.com".to_string()),
+      published_after: Some(week_ago),
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(vec![ON_SUBDOMAIN], names(&post_listings_after));
+
+    let post_listings_exclude = PostQuery {
+      exclude_terms: vec!["weather".to_string()],
+      ..data.default_post_query()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let names_exclude = names(&post_listings_exclude);
+    assert!(!names_exclude.contains(&LOOKALIKE));
+    assert!(names_exclude.contains(&ON_SUBDOMAIN));
+
+    cleanup(data, pool).await
+  }
+
   async fn cleanup(data: Data, pool: &mut DbPool<'_>) -> LemmyResult<()> {
     let num_deleted = Post::delete(pool, data.inserted_post.id).await?;
     Community::delete(pool, data.inserted_community.id).await?;
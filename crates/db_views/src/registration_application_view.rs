@@ -266,6 +266,17 @@ mod tests {
         enable_keyboard_navigation: inserted_sara_local_user.enable_keyboard_navigation,
         enable_animated_images: inserted_sara_local_user.enable_animated_images,
         collapse_bot_comments: inserted_sara_local_user.collapse_bot_comments,
+        send_login_alerts: inserted_sara_local_user.send_login_alerts,
+        failed_login_count: 0,
+        last_failed_login: None,
+        login_locked_until: None,
+        trusted_bot: false,
+        email_notification_frequency: inserted_sara_local_user.email_notification_frequency,
+        last_email_digest: None,
+        send_reply_emails: true,
+        send_mention_emails: true,
+        send_private_message_emails: true,
+        report_resolved_notifications: true,
       },
       creator: Person {
         id: inserted_sara_person.id,
@@ -0,0 +1,314 @@
+use crate::structs::{CommunityAutocompleteView, PersonAutocompleteView};
+use diesel::{
+  dsl::{exists, not},
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  QueryDsl,
+  TextExpressionMethods,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use lemmy_db_schema::{
+  impls::local_user::LocalUserOptionHelper,
+  newtypes::PersonId,
+  schema::{
+    comment,
+    comment_reply,
+    community,
+    community_aggregates,
+    community_block,
+    community_follower,
+    person,
+    person_aggregates,
+    person_block,
+  },
+  source::local_user::LocalUser,
+  utils::{functions::lower, get_conn, limit_and_offset, prefix_search, DbPool},
+};
+
+/// How many of the latest replies from and to the user are checked for people to rank first.
+const RECENT_REPLIES_LIMIT: i64 = 50;
+
+/// Prefix search for completing `@person` and `!community` names while typing. Only names are
+/// matched, so that the queries can use the prefix indexes.
+#[derive(Default, Clone)]
+pub struct AutocompleteQuery<'a> {
+  pub prefix: String,
+  pub local_user: Option<&'a LocalUser>,
+  pub show_nsfw: bool,
+  pub limit: Option<i64>,
+}
+
+impl<'a> AutocompleteQuery<'a> {
+  /// An exact name match comes first, then the communities with the most subscribers.
+  pub async fn list_communities(
+    &self,
+    pool: &mut DbPool<'_>,
+  ) -> Result<Vec<CommunityAutocompleteView>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, _) = limit_and_offset(None, self.limit)?;
+    // Matches nothing for anonymous users
+    let person_id = self.local_user.person_id().unwrap_or(PersonId(-1));
+
+    let is_blocked = exists(
+      community_block::table
+        .filter(community_block::community_id.eq(community::id))
+        .filter(community_block::person_id.eq(person_id)),
+    );
+    let is_follower = exists(
+      community_follower::table
+        .filter(community_follower::community_id.eq(community::id))
+        .filter(community_follower::person_id.eq(person_id)),
+    );
+    let mut query = community::table
+      .inner_join(community_aggregates::table)
+      .filter(lower(community::name).like(prefix_search(&self.prefix)))
+      .filter(community::removed.eq(false))
+      .filter(community::deleted.eq(false))
+      .filter(community::hidden.eq(false).or(is_follower))
+      .filter(not(is_blocked))
+      .select((
+        community::id,
+        community::name,
+        community::title,
+        community::icon,
+        community::actor_id,
+        community::local,
+        community::nsfw,
+        community_aggregates::subscribers,
+      ))
+      .into_boxed();
+
+    if !self.show_nsfw {
+      query = query.filter(community::nsfw.eq(false));
+    }
+    query = self.local_user.visible_communities_only(query);
+
+    query
+      .order_by(lower(community::name).eq(self.prefix.to_lowercase()).desc())
+      .then_order_by(community_aggregates::subscribers.desc())
+      .limit(limit)
+      .load::<CommunityAutocompleteView>(conn)
+      .await
+  }
+
+  /// An exact name match comes first, then people who recently replied to the user or got a reply
+  /// from them, then the people with the most comments. Banned people are left out.
+  pub async fn list_persons(
+    &self,
+    pool: &mut DbPool<'_>,
+  ) -> Result<Vec<PersonAutocompleteView>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, _) = limit_and_offset(None, self.limit)?;
+    let recent_partners = match self.local_user.person_id() {
+      Some(person_id) => recent_reply_partners(conn, person_id).await?,
+      None => Vec::new(),
+    };
+    // Matches nothing for anonymous users
+    let person_id = self.local_user.person_id().unwrap_or(PersonId(-1));
+
+    let is_blocked = exists(
+      person_block::table
+        .filter(person_block::target_id.eq(person::id))
+        .filter(person_block::person_id.eq(person_id)),
+    );
+
+    person::table
+      .inner_join(person_aggregates::table)
+      .filter(lower(person::name).like(prefix_search(&self.prefix)))
+      .filter(person::deleted.eq(false))
+      .filter(person::banned.eq(false))
+      .filter(not(is_blocked))
+      .select((
+        person::id,
+        person::name,
+        person::display_name,
+        person::avatar,
+        person::actor_id,
+        person::local,
+        person::bot_account,
+      ))
+      .order_by(lower(person::name).eq(self.prefix.to_lowercase()).desc())
+      .then_order_by(person::id.eq_any(recent_partners).desc())
+      .then_order_by(person_aggregates::comment_count.desc())
+      .limit(limit)
+      .load::<PersonAutocompleteView>(conn)
+      .await
+  }
+}
+
+/// The people in the latest replies to the person, and in the latest replies from them. Only a
+/// bounded number of replies is read, so that this stays cheap for very active users.
+async fn recent_reply_partners(
+  conn: &mut AsyncPgConnection,
+  person_id: PersonId,
+) -> Result<Vec<PersonId>, Error> {
+  let mut partners = comment_reply::table
+    .inner_join(comment::table)
+    .filter(comment_reply::recipient_id.eq(person_id))
+    .select(comment::creator_id)
+    .order_by(comment_reply::published.desc())
+    .limit(RECENT_REPLIES_LIMIT)
+    .load::<PersonId>(conn)
+    .await?;
+  let replied_to = comment_reply::table
+    .inner_join(comment::table)
+    .filter(comment::creator_id.eq(person_id))
+    .select(comment_reply::recipient_id)
+    .order_by(comment_reply::published.desc())
+    .limit(RECENT_REPLIES_LIMIT)
+    .load::<PersonId>(conn)
+    .await?;
+  partners.extend(replied_to);
+  Ok(partners)
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::{
+    autocomplete_view::AutocompleteQuery,
+    structs::{CommunityAutocompleteView, PersonAutocompleteView},
+  };
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm},
+      comment_reply::{CommentReply, CommentReplyInsertForm},
+      community::{Community, CommunityFollower, CommunityFollowerForm, CommunityInsertForm},
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm, PersonUpdateForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::{Crud, Followable},
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_autocomplete() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+
+    let me_form = PersonInsertForm::test_form(inserted_instance.id, "ac_me");
+    let me = Person::create(pool, &me_form).await?;
+    let local_user_form = LocalUserInsertForm::test_form(me.id);
+    let my_local_user = LocalUser::create(pool, &local_user_form, vec![]).await?;
+    let alice_form = PersonInsertForm::test_form(inserted_instance.id, "ac_alice");
+    let alice = Person::create(pool, &alice_form).await?;
+    let alicia_form = PersonInsertForm::test_form(inserted_instance.id, "ac_alicia");
+    let alicia = Person::create(pool, &alicia_form).await?;
+    let banned_form = PersonInsertForm::test_form(inserted_instance.id, "ac_alibanned");
+    let banned = Person::create(pool, &banned_form).await?;
+    let ban_form = PersonUpdateForm {
+      banned: Some(true),
+      ..Default::default()
+    };
+    Person::update(pool, banned.id, &ban_form).await?;
+
+    let community_form = |name: &str| {
+      CommunityInsertForm::builder()
+        .name(name.to_string())
+        .title("nada".to_owned())
+        .public_key("pubkey".to_string())
+        .instance_id(inserted_instance.id)
+        .build()
+    };
+    let rust = Community::create(pool, &community_form("ac_rust")).await?;
+    let rustaceans = Community::create(pool, &community_form("ac_rustaceans")).await?;
+    let follower_form = CommunityFollowerForm {
+      community_id: rustaceans.id,
+      person_id: me.id,
+      pending: false,
+    };
+    CommunityFollower::follow(pool, &follower_form).await?;
+
+    // Alice wrote more comments, but Alicia replied to me
+    let new_post = PostInsertForm::builder()
+      .name("A test post".into())
+      .creator_id(me.id)
+      .community_id(rust.id)
+      .build();
+    let inserted_post = Post::create(pool, &new_post).await?;
+    let comment_form = |creator_id| {
+      CommentInsertForm::builder()
+        .content("A test comment".into())
+        .creator_id(creator_id)
+        .post_id(inserted_post.id)
+        .build()
+    };
+    Comment::create(pool, &comment_form(alice.id), None).await?;
+    Comment::create(pool, &comment_form(alice.id), None).await?;
+    let alicia_comment = Comment::create(pool, &comment_form(alicia.id), None).await?;
+    let reply_form = CommentReplyInsertForm {
+      recipient_id: me.id,
+      comment_id: alicia_comment.id,
+      read: None,
+    };
+    CommentReply::create(pool, &reply_form).await?;
+
+    let names = |communities: Vec<CommunityAutocompleteView>| {
+      communities.into_iter().map(|c| c.name).collect::<Vec<_>>()
+    };
+    let query = AutocompleteQuery {
+      prefix: "AC_RU".to_string(),
+      ..Default::default()
+    };
+    // More subscribers first
+    assert_eq!(
+      vec!["ac_rustaceans", "ac_rust"],
+      names(query.list_communities(pool).await?)
+    );
+    let query = AutocompleteQuery {
+      prefix: "ac_rust".to_string(),
+      ..Default::default()
+    };
+    // Exact match first
+    assert_eq!(
+      vec!["ac_rust", "ac_rustaceans"],
+      names(query.list_communities(pool).await?)
+    );
+    // The underscore only matches itself
+    let query = AutocompleteQuery {
+      prefix: "ac_rust_".to_string(),
+      ..Default::default()
+    };
+    assert!(query.list_communities(pool).await?.is_empty());
+
+    let person_names = |persons: Vec<PersonAutocompleteView>| {
+      persons.into_iter().map(|p| p.name).collect::<Vec<_>>()
+    };
+    // Banned people are left out
+    let anonymous_query = AutocompleteQuery {
+      prefix: "ac_ali".to_string(),
+      ..Default::default()
+    };
+    assert_eq!(
+      vec!["ac_alice", "ac_alicia"],
+      person_names(anonymous_query.list_persons(pool).await?)
+    );
+    let query = AutocompleteQuery {
+      local_user: Some(&my_local_user),
+      ..anonymous_query
+    };
+    assert_eq!(
+      vec!["ac_alicia", "ac_alice"],
+      person_names(query.list_persons(pool).await?)
+    );
+
+    Post::delete(pool, inserted_post.id).await?;
+    Community::delete(pool, rust.id).await?;
+    Community::delete(pool, rustaceans.id).await?;
+    Person::delete(pool, me.id).await?;
+    Person::delete(pool, alice.id).await?;
+    Person::delete(pool, alicia.id).await?;
+    Person::delete(pool, banned.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
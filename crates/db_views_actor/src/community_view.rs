@@ -19,10 +19,20 @@ use lemmy_db_schema::{
     community_block,
     community_follower,
     community_person_ban,
+    community_search,
     instance_block,
   },
   source::{community::CommunityFollower, local_user::LocalUser, site::Site},
-  utils::{fuzzy_search, limit_and_offset, DbConn, DbPool, ListFn, Queries, ReadFn},
+  utils::{
+    functions::{search_query, text_search_matches, ts_rank_cd},
+    fuzzy_search,
+    limit_and_offset,
+    DbConn,
+    DbPool,
+    ListFn,
+    Queries,
+    ReadFn,
+  },
   ListingType,
   SortType,
 };
@@ -109,11 +119,21 @@ fn queries<'a>() -> Queries<
 
     let mut query = all_joins(community::table.into_boxed(), options.local_user).select(selection);
 
-    if let Some(search_term) = options.search_term {
-      let searcher = fuzzy_search(&search_term);
-      query = query
-        .filter(community::name.ilike(searcher.clone()))
-        .or_filter(community::title.ilike(searcher))
+    // Communities have no language, so only the language independent config is used. Names are
+    // also matched partially, which is useful for communities that are only known by their name.
+    if let Some(search_term) = &options.search_term {
+      query = query.filter(
+        community::name.ilike(fuzzy_search(search_term)).or(
+          community::id.eq_any(
+            community_search::table
+              .select(community_search::community_id)
+              .filter(text_search_matches(
+                community_search::search_vector,
+                search_query(search_term.clone(), String::new()),
+              )),
+          ),
+        ),
+      );
     }
 
     // Hide deleted and removed for non-admins or mods
@@ -142,6 +162,19 @@ fn queries<'a>() -> Queries<
       }
       TopMonth => query = query.order_by(community_aggregates::users_active_month.desc()),
       TopWeek => query = query.order_by(community_aggregates::users_active_week.desc()),
+      Relevance => {
+        if let Some(search_term) = &options.search_term {
+          let relevance = community_search::table
+            .filter(community_search::community_id.eq(community::id))
+            .select(ts_rank_cd(
+              community_search::search_vector,
+              search_query(search_term.clone(), String::new()),
+            ))
+            .single_value();
+          query = query.order_by(relevance.desc());
+        }
+        query = query.then_order_by(community_aggregates::hot_rank.desc())
+      }
     };
 
     if let Some(listing_type) = options.listing_type {
@@ -253,6 +286,7 @@ mod tests {
     traits::Crud,
     utils::{build_db_pool_for_tests, DbPool},
     CommunityVisibility,
+    SortType,
   };
   use serial_test::serial;
   use url::Url;
@@ -380,4 +414,67 @@ mod tests {
 
     cleanup(data, pool).await;
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn search() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await;
+
+    let form = CommunityUpdateForm {
+      description: Some(Some("Tips about gardening".to_string())),
+      ..Default::default()
+    };
+    Community::update(pool, data.inserted_community.id, &form)
+      .await
+      .unwrap();
+    let plants_form = CommunityInsertForm::builder()
+      .name("plants".to_string())
+      .title("Gardening and plants".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(data.inserted_instance.id)
+      .build();
+    let plants = Community::create(pool, &plants_form).await.unwrap();
+
+    // Matches in the title rank higher than matches in the description
+    let relevance_query = CommunityQuery {
+      sort: Some(SortType::Relevance),
+      search_term: Some("gardening".to_string()),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await
+    .unwrap();
+    assert_eq!(
+      vec![plants.id, data.inserted_community.id],
+      relevance_query
+        .iter()
+        .map(|c| c.community.id)
+        .collect::<Vec<_>>()
+    );
+
+    // Words are matched without stemming, but names also match partially
+    let word_query = CommunityQuery {
+      search_term: Some("garden".to_string()),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await
+    .unwrap();
+    assert_eq!(0, word_query.len());
+
+    let name_query = CommunityQuery {
+      search_term: Some("plan".to_string()),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await
+    .unwrap();
+    assert_eq!(1, name_query.len());
+    assert_eq!(plants.id, name_query[0].community.id);
+
+    Community::delete(pool, plants.id).await.unwrap();
+    cleanup(data, pool).await;
+  }
 }
@@ -1,4 +1,6 @@
 #[cfg(feature = "full")]
+pub mod autocomplete_view;
+#[cfg(feature = "full")]
 pub mod comment_reply_view;
 #[cfg(feature = "full")]
 pub mod community_block_view;
@@ -13,9 +15,13 @@ pub mod community_view;
 #[cfg(feature = "full")]
 pub mod instance_block_view;
 #[cfg(feature = "full")]
+pub mod mod_action_notification_view;
+#[cfg(feature = "full")]
 pub mod person_block_view;
 #[cfg(feature = "full")]
 pub mod person_mention_view;
 #[cfg(feature = "full")]
 pub mod person_view;
+#[cfg(feature = "full")]
+pub mod saved_search_match_view;
 pub mod structs;
@@ -0,0 +1,180 @@
+use crate::structs::ModActionNotificationView;
+use diesel::{result::Error, ExpressionMethods, NullableExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::PersonId,
+  schema::{comment, community, mod_action_notification, post, private_message},
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+
+#[derive(Default, Clone)]
+pub struct ModActionNotificationQuery {
+  pub recipient_id: PersonId,
+  pub unread_only: bool,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+}
+
+impl ModActionNotificationQuery {
+  pub async fn list(self, pool: &mut DbPool<'_>) -> Result<Vec<ModActionNotificationView>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = mod_action_notification::table
+      .left_join(community::table)
+      .left_join(post::table)
+      .left_join(comment::table)
+      .left_join(private_message::table)
+      .filter(mod_action_notification::recipient_id.eq(self.recipient_id))
+      .select((
+        mod_action_notification::all_columns,
+        community::all_columns.nullable(),
+        post::all_columns.nullable(),
+        comment::all_columns.nullable(),
+        private_message::all_columns.nullable(),
+      ))
+      .into_boxed();
+
+    if self.unread_only {
+      query = query.filter(mod_action_notification::read.eq(false));
+    }
+
+    let (limit, offset) = limit_and_offset(self.page, self.limit)?;
+
+    query
+      .order_by(mod_action_notification::published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<ModActionNotificationView>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::mod_action_notification_view::ModActionNotificationQuery;
+  use lemmy_db_schema::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      mod_action_notification::{
+        ModActionNotification,
+        ModActionNotificationInsertForm,
+        ModActionNotificationUpdateForm,
+      },
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+    ModActionNotificationType,
+    ReportOutcome,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_mod_action_notifications() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "mod_action_recipient");
+    let inserted_person = Person::create(pool, &person_form).await?;
+
+    let new_community = CommunityInsertForm::builder()
+      .name("test community mod action".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await?;
+
+    let new_post = PostInsertForm::builder()
+      .name("A test post".into())
+      .creator_id(inserted_person.id)
+      .community_id(inserted_community.id)
+      .build();
+    let inserted_post = Post::create(pool, &new_post).await?;
+
+    let remove_form = ModActionNotificationInsertForm {
+      post_id: Some(inserted_post.id),
+      reason: Some("spam".to_string()),
+      ..ModActionNotificationInsertForm::new(
+        inserted_person.id,
+        ModActionNotificationType::RemovePost,
+        Some(inserted_community.id),
+      )
+    };
+    let removed = ModActionNotification::create(pool, &remove_form).await?;
+    let ban_form = ModActionNotificationInsertForm::new(
+      inserted_person.id,
+      ModActionNotificationType::BanFromCommunity,
+      Some(inserted_community.id),
+    );
+    ModActionNotification::create(pool, &ban_form).await?;
+    let report_form = ModActionNotificationInsertForm {
+      report_outcome: Some(ReportOutcome::NoAction),
+      ..ModActionNotificationInsertForm::new(
+        inserted_person.id,
+        ModActionNotificationType::ReportResolved,
+        None,
+      )
+    };
+    ModActionNotification::create(pool, &report_form).await?;
+
+    let unread = ModActionNotification::get_unread_count(pool, inserted_person.id).await?;
+    assert_eq!(3, unread);
+
+    let query = ModActionNotificationQuery {
+      recipient_id: inserted_person.id,
+      unread_only: true,
+      ..Default::default()
+    };
+    let notifications = query.clone().list(pool).await?;
+    assert_eq!(3, notifications.len());
+    // Newest first
+    assert_eq!(
+      Some(ReportOutcome::NoAction),
+      notifications[0].mod_action_notification.report_outcome
+    );
+    assert_eq!(None, notifications[0].community);
+    assert_eq!(
+      ModActionNotificationType::BanFromCommunity,
+      notifications[1].mod_action_notification.kind
+    );
+    assert_eq!(None, notifications[1].post);
+    assert_eq!(
+      Some(inserted_post.id),
+      notifications[2].post.as_ref().map(|p| p.id)
+    );
+    assert_eq!(
+      Some("spam".to_string()),
+      notifications[2].mod_action_notification.reason
+    );
+    assert_eq!(
+      Some(inserted_community.id),
+      notifications[2].community.as_ref().map(|c| c.id)
+    );
+
+    let update_form = ModActionNotificationUpdateForm { read: Some(true) };
+    ModActionNotification::update(pool, removed.id, &update_form).await?;
+    assert_eq!(2, query.clone().list(pool).await?.len());
+
+    ModActionNotification::mark_all_as_read(pool, inserted_person.id).await?;
+    assert_eq!(
+      0,
+      ModActionNotification::get_unread_count(pool, inserted_person.id).await?
+    );
+    assert!(query.list(pool).await?.is_empty());
+
+    Post::delete(pool, inserted_post.id).await?;
+    Community::delete(pool, inserted_community.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
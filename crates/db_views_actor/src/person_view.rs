@@ -11,9 +11,9 @@ use diesel::{
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   newtypes::PersonId,
-  schema::{local_user, person, person_aggregates},
+  schema::{local_user, person, person_aggregates, person_search},
   utils::{
-    functions::coalesce,
+    functions::{coalesce, search_query, text_search_matches, ts_rank_cd},
     fuzzy_search,
     limit_and_offset,
     now,
@@ -44,6 +44,7 @@ enum PersonSortType {
   CommentScore,
   PostScore,
   PostCount,
+  Relevance,
 }
 
 fn post_to_person_sort_type(sort: SortType) -> PersonSortType {
@@ -52,6 +53,7 @@ fn post_to_person_sort_type(sort: SortType) -> PersonSortType {
     SortType::New | SortType::NewComments => PersonSortType::New,
     SortType::MostComments => PersonSortType::MostComments,
     SortType::Old => PersonSortType::Old,
+    SortType::Relevance => PersonSortType::Relevance,
     _ => PersonSortType::CommentScore,
   }
 }
@@ -97,11 +99,21 @@ fn queries<'a>(
           .filter(person::deleted.eq(false));
       }
       ListMode::Query(options) => {
-        if let Some(search_term) = options.search_term {
-          let searcher = fuzzy_search(&search_term);
-          query = query
-            .filter(person::name.ilike(searcher.clone()))
-            .or_filter(person::display_name.ilike(searcher));
+        // Persons have no language, so only the language independent config is used. Names are
+        // also matched partially, as users are often searched by the beginning of their name.
+        if let Some(search_term) = &options.search_term {
+          query = query.filter(
+            person::name.ilike(fuzzy_search(search_term)).or(
+              person::id.eq_any(
+                person_search::table
+                  .select(person_search::person_id)
+                  .filter(text_search_matches(
+                    person_search::search_vector,
+                    search_query(search_term.clone(), String::new()),
+                  )),
+              ),
+            ),
+          );
         }
 
         let sort = options.sort.map(post_to_person_sort_type);
@@ -112,6 +124,21 @@ fn queries<'a>(
           PersonSortType::CommentScore => query.order_by(person_aggregates::comment_score.desc()),
           PersonSortType::PostScore => query.order_by(person_aggregates::post_score.desc()),
           PersonSortType::PostCount => query.order_by(person_aggregates::post_count.desc()),
+          PersonSortType::Relevance => match options.search_term {
+            Some(search_term) => query
+              .order_by(
+                person_search::table
+                  .filter(person_search::person_id.eq(person::id))
+                  .select(ts_rank_cd(
+                    person_search::search_vector,
+                    search_query(search_term, String::new()),
+                  ))
+                  .single_value()
+                  .desc(),
+              )
+              .then_order_by(person_aggregates::comment_score.desc()),
+            None => query.order_by(person_aggregates::comment_score.desc()),
+          },
         };
 
         let (limit, offset) = limit_and_offset(options.page, options.limit)?;
@@ -343,4 +370,56 @@ mod tests {
 
     cleanup(data, pool).await
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn search() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let alice_form = PersonUpdateForm {
+      bio: Some(Some("I spend my weekends gardening".to_string())),
+      ..Default::default()
+    };
+    Person::update(pool, data.alice.id, &alice_form).await?;
+    let bob_form = PersonUpdateForm {
+      display_name: Some(Some("Bob from gardening club".to_string())),
+      ..Default::default()
+    };
+    Person::update(pool, data.bob.id, &bob_form).await?;
+
+    // Matches in the name rank higher than matches in the bio
+    let list = PersonQuery {
+      sort: Some(SortType::Relevance),
+      search_term: Some("gardening".to_string()),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_eq!(
+      vec![data.bob.id, data.alice.id],
+      list.iter().map(|p| p.person.id).collect::<Vec<_>>()
+    );
+
+    // Words are matched without stemming, but names also match partially
+    let list = PersonQuery {
+      search_term: Some("garden".to_string()),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_length!(0, list);
+
+    let list = PersonQuery {
+      search_term: Some("ali".to_string()),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_length!(1, list);
+    assert_eq!(list[0].person.id, data.alice.id);
+
+    cleanup(data, pool).await
+  }
 }
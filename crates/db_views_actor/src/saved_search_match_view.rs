@@ -0,0 +1,209 @@
+use crate::structs::SavedSearchMatchView;
+use diesel::{
+  result::Error,
+  ExpressionMethods,
+  JoinOnDsl,
+  NullableExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::{LocalUserId, SavedSearchId},
+  schema::{comment, community, person, post, saved_search, saved_search_match},
+  utils::{functions::coalesce, get_conn, limit_and_offset, DbPool},
+};
+
+#[derive(Default, Clone)]
+pub struct SavedSearchMatchQuery {
+  pub local_user_id: LocalUserId,
+  pub saved_search_id: Option<SavedSearchId>,
+  pub unread_only: bool,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+}
+
+impl SavedSearchMatchQuery {
+  pub async fn list(self, pool: &mut DbPool<'_>) -> Result<Vec<SavedSearchMatchView>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = saved_search_match::table
+      .inner_join(saved_search::table)
+      .inner_join(post::table.inner_join(community::table))
+      .left_join(comment::table)
+      .inner_join(
+        person::table.on(person::id.eq(coalesce(comment::creator_id.nullable(), post::creator_id))),
+      )
+      .filter(saved_search::local_user_id.eq(self.local_user_id))
+      .select((
+        saved_search_match::all_columns,
+        saved_search::all_columns,
+        post::all_columns,
+        comment::all_columns.nullable(),
+        community::all_columns,
+        person::all_columns,
+      ))
+      .into_boxed();
+
+    if let Some(saved_search_id) = self.saved_search_id {
+      query = query.filter(saved_search_match::saved_search_id.eq(saved_search_id));
+    }
+
+    if self.unread_only {
+      query = query.filter(saved_search_match::read.eq(false));
+    }
+
+    let (limit, offset) = limit_and_offset(self.page, self.limit)?;
+
+    query
+      .order_by(saved_search_match::published.desc())
+      .then_order_by(saved_search_match::id.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<SavedSearchMatchView>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use crate::saved_search_match_view::SavedSearchMatchQuery;
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm},
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+      saved_search::{
+        SavedSearch,
+        SavedSearchInsertForm,
+        SavedSearchMatch,
+        SavedSearchMatchInsertForm,
+        SavedSearchMatchUpdateForm,
+      },
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_saved_search_matches() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string()).await?;
+
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "saved_search_owner");
+    let inserted_person = Person::create(pool, &person_form).await?;
+    let local_user_form = LocalUserInsertForm::test_form(inserted_person.id);
+    let inserted_local_user = LocalUser::create(pool, &local_user_form, vec![]).await?;
+
+    let commenter_form = PersonInsertForm::test_form(inserted_instance.id, "search_commenter");
+    let inserted_commenter = Person::create(pool, &commenter_form).await?;
+
+    let new_community = CommunityInsertForm::builder()
+      .name("test community saved search".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await?;
+
+    let new_post = PostInsertForm::builder()
+      .name("Outage in the datacenter".into())
+      .creator_id(inserted_person.id)
+      .community_id(inserted_community.id)
+      .build();
+    let inserted_post = Post::create(pool, &new_post).await?;
+
+    let comment_form = CommentInsertForm::builder()
+      .content("Another outage".into())
+      .creator_id(inserted_commenter.id)
+      .post_id(inserted_post.id)
+      .build();
+    let inserted_comment = Comment::create(pool, &comment_form, None).await?;
+
+    let saved_search_form = SavedSearchInsertForm::new(
+      inserted_local_user.id,
+      "outage".to_string(),
+      None,
+      None,
+      false,
+      false,
+    );
+    let inserted_saved_search = SavedSearch::create(pool, &saved_search_form).await?;
+    assert_eq!(
+      vec![inserted_saved_search.clone()],
+      SavedSearch::list(pool, inserted_local_user.id).await?
+    );
+
+    let match_forms = [
+      SavedSearchMatchInsertForm::new(inserted_saved_search.id, inserted_post.id, None),
+      SavedSearchMatchInsertForm::new(
+        inserted_saved_search.id,
+        inserted_post.id,
+        Some(inserted_comment.id),
+      ),
+    ];
+    let inserted_matches = SavedSearchMatch::create_many(pool, &match_forms).await?;
+    assert_eq!(
+      2,
+      SavedSearchMatch::get_unread_count(pool, inserted_local_user.id).await?
+    );
+
+    let query = SavedSearchMatchQuery {
+      local_user_id: inserted_local_user.id,
+      unread_only: true,
+      ..Default::default()
+    };
+    let matches = query.clone().list(pool).await?;
+    assert_eq!(2, matches.len());
+    // Newest first, with the creator of the comment for comment matches
+    assert_eq!(
+      Some(inserted_comment.id),
+      matches[0].comment.as_ref().map(|c| c.id)
+    );
+    assert_eq!(inserted_commenter.id, matches[0].creator.id);
+    assert_eq!(None, matches[1].comment);
+    assert_eq!(inserted_person.id, matches[1].creator.id);
+    assert_eq!(inserted_community.id, matches[1].community.id);
+    assert_eq!(inserted_saved_search, matches[1].saved_search);
+
+    let first_match = inserted_matches
+      .first()
+      .map(|m| m.id)
+      .expect("match was inserted");
+    let update_form = SavedSearchMatchUpdateForm { read: Some(true) };
+    SavedSearchMatch::update(pool, first_match, &update_form).await?;
+    assert_eq!(1, query.clone().list(pool).await?.len());
+
+    SavedSearchMatch::mark_all_as_read(pool, inserted_local_user.id).await?;
+    assert_eq!(
+      0,
+      SavedSearchMatch::get_unread_count(pool, inserted_local_user.id).await?
+    );
+    assert!(query.list(pool).await?.is_empty());
+
+    // Deleting the saved search also deletes its matches
+    SavedSearch::delete(pool, inserted_saved_search.id).await?;
+    let all_matches = SavedSearchMatchQuery {
+      local_user_id: inserted_local_user.id,
+      ..Default::default()
+    };
+    assert!(all_matches.list(pool).await?.is_empty());
+
+    Comment::delete(pool, inserted_comment.id).await?;
+    Post::delete(pool, inserted_post.id).await?;
+    Community::delete(pool, inserted_community.id).await?;
+    Person::delete(pool, inserted_commenter.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
+}
@@ -2,14 +2,18 @@
 use diesel::Queryable;
 use lemmy_db_schema::{
   aggregates::structs::{CommentAggregates, CommunityAggregates, PersonAggregates},
+  newtypes::{CommunityId, DbUrl, PersonId},
   source::{
     comment::Comment,
     comment_reply::CommentReply,
     community::Community,
     instance::Instance,
+    mod_action_notification::ModActionNotification,
     person::Person,
     person_mention::PersonMention,
     post::Post,
+    private_message::PrivateMessage,
+    saved_search::{SavedSearch, SavedSearchMatch},
     site::Site,
   },
   SubscribedType,
@@ -70,6 +74,24 @@ pub struct CommunityPersonBanView {
   pub person: Person,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A community in autocomplete results, with only the fields needed to show and link it.
+pub struct CommunityAutocompleteView {
+  pub id: CommunityId,
+  pub name: String,
+  pub title: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub icon: Option<DbUrl>,
+  pub actor_id: DbUrl,
+  pub local: bool,
+  pub nsfw: bool,
+  pub subscribers: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS, Queryable))]
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
@@ -141,6 +163,59 @@ pub struct CommentReplyView {
   pub my_vote: Option<i16>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A notification about a moderator action affecting the user.
+pub struct ModActionNotificationView {
+  pub mod_action_notification: ModActionNotification,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub community: Option<Community>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub post: Option<Post>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment: Option<Comment>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub private_message: Option<PrivateMessage>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A new post or comment which matched one of the user's saved searches.
+pub struct SavedSearchMatchView {
+  pub saved_search_match: SavedSearchMatch,
+  pub saved_search: SavedSearch,
+  pub post: Post,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub comment: Option<Comment>,
+  pub community: Community,
+  /// The creator of the comment, or of the post if the match isn't a comment.
+  pub creator: Person,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS, Queryable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A person in autocomplete results, with only the fields needed to show and mention them.
+pub struct PersonAutocompleteView {
+  pub id: PersonId,
+  pub name: String,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub display_name: Option<String>,
+  #[cfg_attr(feature = "full", ts(optional))]
+  pub avatar: Option<DbUrl>,
+  pub actor_id: DbUrl,
+  pub local: bool,
+  pub bot_account: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS, Queryable))]
 #[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
@@ -1,10 +1,14 @@
 use crate::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
-  settings::structs::Settings,
+  settings::structs::{EmailConfig, EmailTransport, Settings},
 };
 use html2text;
 use lettre::{
-  message::{Mailbox, MultiPart},
+  message::{
+    header::{Header, HeaderName, HeaderValue},
+    Mailbox,
+    MultiPart,
+  },
   transport::smtp::{authentication::Credentials, extension::ClientId},
   Address,
   AsyncTransport,
@@ -18,6 +22,45 @@ pub mod translations {
 }
 
 type AsyncSmtpTransport = lettre::AsyncSmtpTransport<lettre::Tokio1Executor>;
+type AsyncSendmailTransport = lettre::AsyncSendmailTransport<lettre::Tokio1Executor>;
+type AsyncFileTransport = lettre::AsyncFileTransport<lettre::Tokio1Executor>;
+
+/// `List-Unsubscribe` header, defined in [RFC2369](https://tools.ietf.org/html/rfc2369#section-3.2)
+#[derive(Debug, Clone)]
+struct ListUnsubscribe(String);
+
+impl Header for ListUnsubscribe {
+  fn name() -> HeaderName {
+    HeaderName::new_from_ascii_str("List-Unsubscribe")
+  }
+
+  fn parse(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(Self(s.to_string()))
+  }
+
+  fn display(&self) -> HeaderValue {
+    HeaderValue::new(Self::name(), format!("<{}>", self.0))
+  }
+}
+
+/// `List-Unsubscribe-Post` header for one-click unsubscribe, defined in
+/// [RFC8058](https://tools.ietf.org/html/rfc8058#section-3.1)
+#[derive(Debug, Clone)]
+struct ListUnsubscribePost;
+
+impl Header for ListUnsubscribePost {
+  fn name() -> HeaderName {
+    HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+  }
+
+  fn parse(_s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(Self)
+  }
+
+  fn display(&self) -> HeaderValue {
+    HeaderValue::new(Self::name(), "List-Unsubscribe=One-Click".to_string())
+  }
+}
 
 pub async fn send_email(
   subject: &str,
@@ -26,35 +69,40 @@ pub async fn send_email(
   html: &str,
   settings: &Settings,
 ) -> LemmyResult<()> {
-  let email_config = settings.email.clone().ok_or(LemmyErrorType::NoEmailSetup)?;
-  let domain = settings.hostname.clone();
-
-  let (smtp_server, smtp_port) = {
-    let email_and_port = email_config.smtp_server.split(':').collect::<Vec<&str>>();
-    let email = *email_and_port
-      .first()
-      .ok_or(LemmyErrorType::MissingAnEmail)?;
-    let port = email_and_port
-      .get(1)
-      .ok_or(LemmyErrorType::EmailSmtpServerNeedsAPort)?
-      .parse::<u16>()?;
+  send_email_with_unsubscribe(subject, to_email, to_username, html, None, settings).await
+}
 
-    (email, port)
-  };
+/// Send an email which can be unsubscribed from with one click, by sending a POST request to
+/// the unsubscribe link.
+pub async fn send_email_with_unsubscribe(
+  subject: &str,
+  to_email: &str,
+  to_username: &str,
+  html: &str,
+  unsubscribe_link: Option<&str>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let email_config = settings.email.clone().ok_or(LemmyErrorType::NoEmailSetup)?;
 
   // use usize::MAX as the line wrap length, since lettre handles the wrapping for us
   let plain_text = html2text::from_read(html.as_bytes(), usize::MAX);
 
-  let email = Message::builder()
+  let mut message = Message::builder();
+  if let Some(unsubscribe_link) = unsubscribe_link {
+    message = message
+      .header(ListUnsubscribe(unsubscribe_link.to_string()))
+      .header(ListUnsubscribePost);
+  }
+  let email = message
     .from(
       email_config
         .smtp_from_address
         .parse()
-        .expect("email from address isn't valid"),
+        .with_lemmy_type(LemmyErrorType::EmailSendFailed)?,
     )
     .to(Mailbox::new(
       Some(to_username.to_string()),
-      Address::from_str(to_email).expect("email to address isn't valid"),
+      Address::from_str(to_email).with_lemmy_type(LemmyErrorType::EmailSendFailed)?,
     ))
     .message_id(Some(format!("<{}@{}>", Uuid::new_v4(), settings.hostname)))
     .subject(subject)
@@ -62,7 +110,56 @@ pub async fn send_email(
       plain_text,
       html.to_string(),
     ))
-    .expect("email built incorrectly");
+    .with_lemmy_type(LemmyErrorType::EmailSendFailed)?;
+
+  match email_config.transport {
+    EmailTransport::Smtp => smtp_transport(&email_config, settings)?
+      .send(email)
+      .await
+      .map(|_| ())
+      .with_lemmy_type(LemmyErrorType::EmailSendFailed)?,
+    EmailTransport::Sendmail => {
+      let transport = match &email_config.sendmail_command {
+        Some(command) => AsyncSendmailTransport::new_with_command(command),
+        None => AsyncSendmailTransport::new(),
+      };
+      transport
+        .send(email)
+        .await
+        .with_lemmy_type(LemmyErrorType::EmailSendFailed)?
+    }
+    EmailTransport::File => {
+      let directory = email_config
+        .file_directory
+        .as_ref()
+        .ok_or(LemmyErrorType::EmailFileDirectoryMissing)?;
+      AsyncFileTransport::new(directory)
+        .send(email)
+        .await
+        .map(|_| ())
+        .with_lemmy_type(LemmyErrorType::EmailSendFailed)?
+    }
+  }
+
+  Ok(())
+}
+
+fn smtp_transport(
+  email_config: &EmailConfig,
+  settings: &Settings,
+) -> LemmyResult<AsyncSmtpTransport> {
+  let (smtp_server, smtp_port) = {
+    let email_and_port = email_config.smtp_server.split(':').collect::<Vec<&str>>();
+    let email = *email_and_port
+      .first()
+      .ok_or(LemmyErrorType::MissingAnEmail)?;
+    let port = email_and_port
+      .get(1)
+      .ok_or(LemmyErrorType::EmailSmtpServerNeedsAPort)?
+      .parse::<u16>()?;
+
+    (email, port)
+  };
 
   // don't worry about 'dangeous'. it's just that leaving it at the default configuration
   // is bad.
@@ -76,16 +173,103 @@ pub async fn send_email(
 
   // Set the creds if they exist
   let smtp_password = email_config.smtp_password();
-  if let (Some(username), Some(password)) = (email_config.smtp_login, smtp_password) {
+  if let (Some(username), Some(password)) = (email_config.smtp_login.clone(), smtp_password) {
     builder = builder.credentials(Credentials::new(username, password));
   }
 
-  let mailer = builder.hello_name(ClientId::Domain(domain)).build();
+  Ok(
+    builder
+      .hello_name(ClientId::Domain(settings.hostname.clone()))
+      .build(),
+  )
+}
 
-  mailer
-    .send(email)
-    .await
-    .with_lemmy_type(LemmyErrorType::EmailSendFailed)?;
+/// Replace placeholders like `{username}` in an admin defined email template. Unknown placeholders
+/// are left as they are. Values are inserted in a single pass, so placeholders inside of values
+/// (eg in comment content) are not replaced.
+pub fn render_email_template(template: &str, vars: &[(&str, &str)]) -> String {
+  let mut out = String::with_capacity(template.len());
+  let mut rest = template;
+  while let Some(start) = rest.find('{') {
+    out.push_str(&rest[..start]);
+    let after = &rest[start + 1..];
+    let value = after.find('}').and_then(|end| {
+      let name = &after[..end];
+      vars
+        .iter()
+        .find(|(var, _)| *var == name)
+        .map(|(_, value)| (*value, end))
+    });
+    match value {
+      Some((value, end)) => {
+        out.push_str(value);
+        rest = &after[end + 1..];
+      }
+      None => {
+        out.push('{');
+        rest = after;
+      }
+    }
+  }
+  out.push_str(rest);
+  out
+}
 
-  Ok(())
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use super::{render_email_template, send_email_with_unsubscribe};
+  use crate::{
+    error::LemmyResult,
+    settings::structs::{EmailConfig, EmailTransport, Settings},
+  };
+  use pretty_assertions::assert_eq;
+  use std::fs;
+  use uuid::Uuid;
+
+  #[test]
+  fn test_render_email_template() {
+    let vars = [("username", "alice"), ("content", "{username} {link}")];
+    assert_eq!(
+      "Hi alice, you wrote: {username} {link}. {unknown} {",
+      render_email_template("Hi {username}, you wrote: {content}. {unknown} {", &vars)
+    );
+  }
+
+  #[tokio::test]
+  async fn test_file_transport() -> LemmyResult<()> {
+    let directory = std::env::temp_dir().join(format!("lemmy_emails_{}", Uuid::new_v4()));
+    fs::create_dir(&directory)?;
+    // Some settings are private, so they can only be changed after creating the default
+    let mut email_config = EmailConfig::default();
+    email_config.transport = EmailTransport::File;
+    email_config.smtp_from_address = "noreply@example.com".to_string();
+    email_config.file_directory = Some(directory.to_string_lossy().to_string());
+    let mut settings = Settings::default();
+    settings.email = Some(email_config);
+
+    send_email_with_unsubscribe(
+      "Test subject",
+      "alice@example.com",
+      "alice",
+      "<p>Hello</p>",
+      Some("https://example.com/unsubscribe"),
+      &settings,
+    )
+    .await?;
+
+    let files = fs::read_dir(&directory)?.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(1, files.len());
+    let path = files[0].path();
+    assert_eq!(Some("eml"), path.extension().and_then(|e| e.to_str()));
+    let email = fs::read_to_string(&path)?;
+    assert!(email.contains("Subject: Test subject"));
+    assert!(email.contains("To: alice <alice@example.com>"));
+    assert!(email.contains("List-Unsubscribe: <https://example.com/unsubscribe>"));
+
+    fs::remove_dir_all(&directory)?;
+    Ok(())
+  }
 }
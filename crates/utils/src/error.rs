@@ -181,6 +181,38 @@ pub enum LemmyErrorType {
   Unknown(String),
   CantDeleteSite,
   UrlLengthOverflow,
+  InvalidApiTokenName,
+  ApiTokenScopesRequired,
+  /// The api token used for this request lacks the given scope.
+  ApiTokenMissingScope(String),
+  /// This action can only be performed with a regular login, not with an api token.
+  ApiTokenNotAllowed,
+  CouldntFindApiToken,
+  CouldntFindLoginToken,
+  TotpNotEnabled,
+  /// Too many failed login attempts, try again later.
+  LoginTemporarilyLocked,
+  NotABotAccount,
+  EmailTemplateAlreadyExists,
+  CouldntFindEmailTemplate,
+  InvalidEmailTemplateLanguage,
+  InvalidUnsubscribeToken,
+  EmailFileDirectoryMissing,
+  InvalidPushSubscription,
+  TooManyPushSubscriptions,
+  CouldntFindModActionNotification,
+  CouldntUpdateModActionNotification,
+  CouldntFindWebhook,
+  /// Webhook urls need to use http or https, and can't point to internal addresses.
+  InvalidWebhookUrl,
+  WebhookEventsRequired,
+  TooManyWebhooks,
+  CouldntFindSavedSearch,
+  InvalidSavedSearch,
+  TooManySavedSearches,
+  CouldntFindSavedSearchMatch,
+  CouldntUpdateSavedSearchMatch,
+  AutocompletePrefixTooShort,
 }
 
 cfg_if! {
@@ -236,6 +268,9 @@ cfg_if! {
         if self.error_type == LemmyErrorType::IncorrectLogin {
           return http::StatusCode::UNAUTHORIZED;
         }
+        if self.error_type == LemmyErrorType::RateLimitError {
+          return http::StatusCode::TOO_MANY_REQUESTS;
+        }
         match self.inner.downcast_ref::<diesel::result::Error>() {
           Some(diesel::result::Error::NotFound) => http::StatusCode::NOT_FOUND,
           _ => http::StatusCode::BAD_REQUEST,
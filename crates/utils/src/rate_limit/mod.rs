@@ -1,9 +1,21 @@
-use crate::error::{LemmyError, LemmyErrorType};
-use actix_web::dev::{ConnectionInfo, Service, ServiceRequest, ServiceResponse, Transform};
+use crate::error::{LemmyError, LemmyErrorType, LemmyResult};
+use actix_web::{
+  dev::{ConnectionInfo, Service, ServiceRequest, ServiceResponse, Transform},
+  http::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER},
+  HttpMessage,
+};
 use enum_map::{enum_map, EnumMap};
 use futures::future::{ok, Ready};
-pub use rate_limiter::{ActionType, BucketConfig};
+pub use rate_limiter::{
+  AccountLimitConfig,
+  ActionType,
+  BucketConfig,
+  BucketState,
+  RateLimitAccount,
+  RateLimitInfo,
+};
 use rate_limiter::{InstantSecs, RateLimitState};
+pub use shared::SharedRateLimitBackend;
 use std::{
   future::Future,
   net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -14,12 +26,15 @@ use std::{
   task::{Context, Poll},
   time::Duration,
 };
+use tracing::warn;
 
 pub mod rate_limiter;
+mod shared;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct RateLimitChecker {
   state: Arc<Mutex<RateLimitState>>,
+  shared: Option<Arc<dyn SharedRateLimitBackend>>,
   action_type: ActionType,
 }
 
@@ -27,13 +42,24 @@ pub struct RateLimitChecker {
 #[derive(Clone)]
 pub struct RateLimitCell {
   state: Arc<Mutex<RateLimitState>>,
+  shared: Option<Arc<dyn SharedRateLimitBackend>>,
 }
 
 impl RateLimitCell {
   pub fn new(rate_limit_config: EnumMap<ActionType, BucketConfig>) -> Self {
+    Self::new_with_shared_backend(rate_limit_config, None)
+  }
+
+  /// If a shared backend is given, buckets are stored there instead of in memory. The in-memory
+  /// state is still used as fallback when the shared backend fails.
+  pub fn new_with_shared_backend(
+    rate_limit_config: EnumMap<ActionType, BucketConfig>,
+    shared: Option<Arc<dyn SharedRateLimitBackend>>,
+  ) -> Self {
     let state = Arc::new(Mutex::new(RateLimitState::new(rate_limit_config)));
 
     let state_weak_ref = Arc::downgrade(&state);
+    let shared_ = shared.clone();
 
     tokio::spawn(async move {
       let interval = Duration::from_secs(120);
@@ -41,14 +67,24 @@ impl RateLimitCell {
       // This loop stops when all other references to `state` are dropped
       while let Some(state) = state_weak_ref.upgrade() {
         tokio::time::sleep(interval).await;
-        state
-          .lock()
-          .expect("Failed to lock rate limit mutex for reading")
-          .remove_full_buckets(InstantSecs::now());
+        let max_secs_to_refill = {
+          let mut state = state
+            .lock()
+            .expect("Failed to lock rate limit mutex for reading");
+          state.remove_full_buckets(InstantSecs::now());
+          state.max_secs_to_refill()
+        };
+        if let Some(shared) = &shared_ {
+          shared
+            .remove_full_buckets(max_secs_to_refill)
+            .await
+            .map_err(|e| warn!("Failed to remove full shared rate limit buckets: {e}"))
+            .ok();
+        }
       }
     });
 
-    RateLimitCell { state }
+    RateLimitCell { state, shared }
   }
 
   pub fn set_config(&self, config: EnumMap<ActionType, BucketConfig>) {
@@ -59,6 +95,14 @@ impl RateLimitCell {
       .set_config(config);
   }
 
+  pub fn set_account_config(&self, config: AccountLimitConfig) {
+    self
+      .state
+      .lock()
+      .expect("Failed to lock rate limit mutex for updating")
+      .set_account_config(config);
+  }
+
   pub fn message(&self) -> RateLimitChecker {
     self.new_checker(ActionType::Message)
   }
@@ -87,9 +131,26 @@ impl RateLimitCell {
     self.new_checker(ActionType::ImportUserSettings)
   }
 
+  pub fn vote(&self) -> RateLimitChecker {
+    self.new_checker(ActionType::Vote)
+  }
+
+  pub fn report(&self) -> RateLimitChecker {
+    self.new_checker(ActionType::Report)
+  }
+
+  pub fn follow(&self) -> RateLimitChecker {
+    self.new_checker(ActionType::Follow)
+  }
+
+  pub fn federation_fetch(&self) -> RateLimitChecker {
+    self.new_checker(ActionType::FederationFetch)
+  }
+
   fn new_checker(&self, action_type: ActionType) -> RateLimitChecker {
     RateLimitChecker {
       state: self.state.clone(),
+      shared: self.shared.clone(),
       action_type,
     }
   }
@@ -124,6 +185,22 @@ impl RateLimitCell {
         capacity: 1,
         secs_to_refill: 24 * 60 * 60,
       },
+      ActionType::Vote => BucketConfig {
+        capacity: 60,
+        secs_to_refill: 60,
+      },
+      ActionType::Report => BucketConfig {
+        capacity: 10,
+        secs_to_refill: 600,
+      },
+      ActionType::Follow => BucketConfig {
+        capacity: 30,
+        secs_to_refill: 600,
+      },
+      ActionType::FederationFetch => BucketConfig {
+        capacity: 60,
+        secs_to_refill: 600,
+      },
     })
   }
 }
@@ -134,8 +211,48 @@ pub struct RateLimitedMiddleware<S> {
 }
 
 impl RateLimitChecker {
-  /// Returns true if the request passed the rate limit, false if it failed and should be rejected.
-  pub fn check(self, ip_addr: IpAddr) -> bool {
+  /// Checks if the request passed the rate limit, or if it failed and should be rejected.
+  pub async fn check(self, ip_addr: IpAddr, account: Option<RateLimitAccount>) -> RateLimitInfo {
+    if let Some(shared) = &self.shared {
+      match self.check_shared(shared.as_ref(), ip_addr, account).await {
+        Ok(passed) => return passed,
+        Err(e) => warn!("Failed to check shared rate limit, using local state instead: {e}"),
+      }
+    }
+    self.check_local(ip_addr, account)
+  }
+
+  async fn check_shared(
+    &self,
+    shared: &dyn SharedRateLimitBackend,
+    ip_addr: IpAddr,
+    account: Option<RateLimitAccount>,
+  ) -> LemmyResult<RateLimitInfo> {
+    let (config, account_config) = {
+      let state = self
+        .state
+        .lock()
+        .expect("Failed to lock rate limit mutex for reading");
+      (
+        state.bucket_config(self.action_type),
+        state.account_config(),
+      )
+    };
+
+    // Every bucket takes a token, even if an earlier one is already empty. This is the same as
+    // for the in-memory ip address groups.
+    let mut info = RateLimitInfo::UNLIMITED;
+    for (key, capacity_factor) in shared::bucket_keys(ip_addr, account, account_config) {
+      let config = BucketConfig {
+        capacity: config.capacity.saturating_mul(capacity_factor),
+        ..config
+      };
+      info = info.combine(shared.check(self.action_type, &key, config).await?);
+    }
+    Ok(info)
+  }
+
+  fn check_local(&self, ip_addr: IpAddr, account: Option<RateLimitAccount>) -> RateLimitInfo {
     // Does not need to be blocking because the RwLock in settings never held across await points,
     // and the operation here locks only long enough to clone
     let mut state = self
@@ -143,7 +260,22 @@ impl RateLimitChecker {
       .lock()
       .expect("Failed to lock rate limit mutex for reading");
 
-    state.check(self.action_type, ip_addr, InstantSecs::now())
+    let now = InstantSecs::now();
+    match account {
+      // Trusted accounts are only limited per account, so that they are not affected by other
+      // users of the same ip address
+      Some(account) if account.trusted => state.check_account(self.action_type, account, now),
+      Some(account) => {
+        let ip_info = state.check(self.action_type, ip_addr, now);
+        // Don't take a token from the account if the ip address is already limited
+        if ip_info.passed {
+          ip_info.combine(state.check_account(self.action_type, account, now))
+        } else {
+          ip_info
+        }
+      }
+      None => state.check(self.action_type, ip_addr, now),
+    }
   }
 }
 
@@ -183,24 +315,48 @@ where
 
   fn call(&self, req: ServiceRequest) -> Self::Future {
     let ip_addr = get_ip(&req.connection_info());
+    let account = req.extensions().get::<RateLimitAccount>().copied();
 
     let checker = self.checker.clone();
     let service = self.service.clone();
 
     Box::pin(async move {
-      if checker.check(ip_addr) {
-        service.call(req).await
+      let info = checker.check(ip_addr, account).await;
+      let mut res = if info.passed {
+        service.call(req).await?
       } else {
         let (http_req, _) = req.into_parts();
-        Ok(ServiceResponse::from_err(
-          LemmyError::from(LemmyErrorType::RateLimitError),
-          http_req,
-        ))
-      }
+        ServiceResponse::from_err(LemmyError::from(LemmyErrorType::RateLimitError), http_req)
+      };
+      insert_rate_limit_headers(res.headers_mut(), info);
+      Ok(res)
     })
   }
 }
 
+/// Adds the `RateLimit-*` headers so that clients can pace themselves, and `Retry-After` once the
+/// bucket is empty.
+fn insert_rate_limit_headers(headers: &mut HeaderMap, info: RateLimitInfo) {
+  let Some(bucket) = info.bucket else {
+    return;
+  };
+  headers.insert(
+    HeaderName::from_static("ratelimit-limit"),
+    HeaderValue::from(bucket.limit),
+  );
+  headers.insert(
+    HeaderName::from_static("ratelimit-remaining"),
+    HeaderValue::from(bucket.remaining),
+  );
+  headers.insert(
+    HeaderName::from_static("ratelimit-reset"),
+    HeaderValue::from(bucket.reset),
+  );
+  if !info.passed || bucket.remaining == 0 {
+    headers.insert(RETRY_AFTER, HeaderValue::from(bucket.retry_after.max(1)));
+  }
+}
+
 fn get_ip(conn_info: &ConnectionInfo) -> IpAddr {
   conn_info
     .realip_remote_addr()
@@ -1,5 +1,6 @@
 use enum_map::EnumMap;
 use std::{
+  cmp::Reverse,
   collections::HashMap,
   hash::Hash,
   net::{IpAddr, Ipv4Addr, Ipv6Addr},
@@ -42,7 +43,81 @@ pub struct BucketConfig {
   pub secs_to_refill: u32,
 }
 
+/// Result of a rate limit check, used for the rate limit response headers.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct RateLimitInfo {
+  pub passed: bool,
+  /// The bucket with the fewest remaining tokens, or `None` if no limit applies.
+  pub bucket: Option<BucketState>,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct BucketState {
+  pub limit: u32,
+  pub remaining: u32,
+  /// Seconds until the bucket is full again
+  pub reset: u32,
+  /// Seconds until the next token is available, 0 if there are tokens left
+  pub retry_after: u32,
+}
+
+impl RateLimitInfo {
+  pub const UNLIMITED: Self = RateLimitInfo {
+    passed: true,
+    bucket: None,
+  };
+
+  /// `tokens` is the fractional amount of tokens which are currently in the bucket, after taking
+  /// one if the check passed.
+  pub fn new(passed: bool, tokens: f64, config: BucketConfig) -> Self {
+    let secs_until = |target: f64| {
+      if config.capacity == 0 {
+        return config.secs_to_refill;
+      }
+      let missing_tokens = (target - tokens).max(0.0);
+      (missing_tokens * f64::from(config.secs_to_refill) / f64::from(config.capacity)).ceil() as u32
+    };
+    RateLimitInfo {
+      passed,
+      bucket: Some(BucketState {
+        limit: config.capacity,
+        remaining: tokens.max(0.0) as u32,
+        reset: secs_until(config.capacity.into()),
+        retry_after: secs_until(1.0),
+      }),
+    }
+  }
+
+  /// Combines the results for two buckets which were checked for the same request. The request
+  /// only passes if both passed, and the headers describe the bucket which is closer to running
+  /// out.
+  pub fn combine(self, other: Self) -> Self {
+    let bucket = match (self.bucket, other.bucket) {
+      (Some(a), Some(b)) => {
+        let key = |s: BucketState| (s.remaining, Reverse(s.retry_after), Reverse(s.reset));
+        Some(if key(b) < key(a) { b } else { a })
+      }
+      (a, b) => a.or(b),
+    };
+    RateLimitInfo {
+      passed: self.passed && other.passed,
+      bucket,
+    }
+  }
+}
+
 impl Bucket {
+  /// The fractional amount of tokens at `now`, for the rate limit headers.
+  fn tokens_at(self, now: InstantSecs, config: BucketConfig) -> f64 {
+    if config.secs_to_refill == 0 {
+      return config.capacity.into();
+    }
+    let secs_since_last_checked = now.secs.saturating_sub(self.last_checked.secs);
+    let added_tokens = f64::from(secs_since_last_checked) * f64::from(config.capacity)
+      / f64::from(config.secs_to_refill);
+    (f64::from(self.tokens) + added_tokens).min(config.capacity.into())
+  }
+
   fn update(self, now: InstantSecs, config: BucketConfig) -> Self {
     let secs_since_last_checked = now.secs.saturating_sub(self.last_checked.secs);
 
@@ -66,6 +141,33 @@ impl Bucket {
   }
 }
 
+/// Capacity of the per-account buckets, as a multiple of the per-ip bucket capacity. A factor of 0
+/// disables the per-account limit.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct AccountLimitConfig {
+  pub factor: u32,
+  /// Used for admins and bot accounts which were marked as trusted by an admin.
+  pub trusted_factor: u32,
+}
+
+impl Default for AccountLimitConfig {
+  /// Same as the database defaults
+  fn default() -> Self {
+    AccountLimitConfig {
+      factor: 1,
+      trusted_factor: 10,
+    }
+  }
+}
+
+/// The logged in account which is making a request.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct RateLimitAccount {
+  pub local_user_id: i32,
+  /// Trusted accounts get separate limits, and are not limited by ip address.
+  pub trusted: bool,
+}
+
 #[derive(Debug, enum_map::Enum, Copy, Clone, Display, AsRefStr)]
 pub enum ActionType {
   Message,
@@ -75,6 +177,11 @@ pub enum ActionType {
   Comment,
   Search,
   ImportUserSettings,
+  Vote,
+  Report,
+  Follow,
+  /// Fetching remote objects, which causes outgoing http requests.
+  FederationFetch,
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -97,7 +204,7 @@ trait MapLevel: Default {
     configs: EnumMap<ActionType, BucketConfig>,
     capacity_factors: Self::CapacityFactors,
     addr_parts: Self::AddrParts,
-  ) -> bool;
+  ) -> RateLimitInfo;
 
   /// Remove full buckets and return `true` if there's any buckets remaining
   fn remove_full_buckets(
@@ -118,7 +225,7 @@ impl<K: Eq + Hash, C: MapLevel> MapLevel for Map<K, C> {
     configs: EnumMap<ActionType, BucketConfig>,
     (capacity_factor, child_capacity_factors): Self::CapacityFactors,
     (addr_part, child_addr_parts): Self::AddrParts,
-  ) -> bool {
+  ) -> RateLimitInfo {
     // Multiplies capacities by `capacity_factor` for groups in `self`
     let adjusted_configs = configs.map(|_, config| BucketConfig {
       capacity: config.capacity.saturating_mul(capacity_factor),
@@ -137,9 +244,9 @@ impl<K: Eq + Hash, C: MapLevel> MapLevel for Map<K, C> {
       .or_insert(RateLimitedGroup::new(now, adjusted_configs));
 
     #[allow(clippy::indexing_slicing)]
-    let total_passes = group.check_total(action_type, now, adjusted_configs[action_type]);
+    let total = group.check_total(action_type, now, adjusted_configs[action_type]);
 
-    let children_pass = group.children.check(
+    let children = group.children.check(
       action_type,
       now,
       configs,
@@ -147,7 +254,7 @@ impl<K: Eq + Hash, C: MapLevel> MapLevel for Map<K, C> {
       child_addr_parts,
     );
 
-    total_passes && children_pass
+    total.combine(children)
   }
 
   fn remove_full_buckets(
@@ -187,8 +294,8 @@ impl MapLevel for () {
     _: EnumMap<ActionType, BucketConfig>,
     _: Self::CapacityFactors,
     _: Self::AddrParts,
-  ) -> bool {
-    true
+  ) -> RateLimitInfo {
+    RateLimitInfo::UNLIMITED
   }
 
   fn remove_full_buckets(&mut self, _: InstantSecs, _: EnumMap<ActionType, BucketConfig>) -> bool {
@@ -213,13 +320,13 @@ impl<C: Default> RateLimitedGroup<C> {
     action_type: ActionType,
     now: InstantSecs,
     config: BucketConfig,
-  ) -> bool {
+  ) -> RateLimitInfo {
     #[allow(clippy::indexing_slicing)] // `EnumMap` has no `get` function
     let bucket = &mut self.total[action_type];
 
     let new_bucket = bucket.update(now, config);
 
-    if new_bucket.tokens == 0 {
+    let passed = if new_bucket.tokens == 0 {
       // Not enough tokens yet
       // Setting `bucket` to `new_bucket` here is useless and would cause the bucket to start over
       // at 0 tokens because of rounding
@@ -229,11 +336,13 @@ impl<C: Default> RateLimitedGroup<C> {
       *bucket = new_bucket;
       bucket.tokens -= 1;
       true
-    }
+    };
+
+    RateLimitInfo::new(passed, bucket.tokens_at(now, config), config)
   }
 }
 
-/// Rate limiting based on rate type and IP addr
+/// Rate limiting based on rate type, IP addr and account
 #[derive(PartialEq, Debug, Clone)]
 pub struct RateLimitState {
   /// Each individual IPv4 address gets one `RateLimitedGroup`.
@@ -246,9 +355,14 @@ pub struct RateLimitState {
   /// first 64 bits. It could be as low as 48 bits for some networks, which is the reason for 48
   /// and 56 bit address groups.
   ipv6_buckets: Map<[u8; 6], Map<u8, Map<u8, ()>>>,
+  /// Each logged in account gets one `RateLimitedGroup`, keyed by local user id. Otherwise a
+  /// single account could avoid the limits by switching between many ip addresses, while many
+  /// people behind the same NAT would be limited together.
+  account_buckets: Map<i32, ()>,
   /// This stores a `BucketConfig` for each `ActionType`. `EnumMap` makes it impossible to have a
   /// missing `BucketConfig`.
   bucket_configs: EnumMap<ActionType, BucketConfig>,
+  account_config: AccountLimitConfig,
 }
 
 impl RateLimitState {
@@ -256,14 +370,17 @@ impl RateLimitState {
     RateLimitState {
       ipv4_buckets: HashMap::new(),
       ipv6_buckets: HashMap::new(),
+      account_buckets: HashMap::new(),
       bucket_configs,
+      account_config: AccountLimitConfig::default(),
     }
   }
 
   /// Rate limiting Algorithm described here: https://stackoverflow.com/a/668327/1655478
   ///
-  /// Returns true if the request passed the rate limit, false if it failed and should be rejected.
-  pub fn check(&mut self, action_type: ActionType, ip: IpAddr, now: InstantSecs) -> bool {
+  /// Returns whether the request passed the rate limit, and the state of the most exhausted bucket
+  /// for the rate limit response headers.
+  pub fn check(&mut self, action_type: ActionType, ip: IpAddr, now: InstantSecs) -> RateLimitInfo {
     let result = match ip {
       IpAddr::V4(ipv4) => {
         self
@@ -283,13 +400,47 @@ impl RateLimitState {
       }
     };
 
-    if !result {
+    if !result.passed {
       debug!("Rate limited IP: {ip}, type: {action_type:?}");
     }
 
     result
   }
 
+  /// Same as [RateLimitState::check], but for the buckets of a logged in account.
+  pub fn check_account(
+    &mut self,
+    action_type: ActionType,
+    account: RateLimitAccount,
+    now: InstantSecs,
+  ) -> RateLimitInfo {
+    let factor = if account.trusted {
+      self.account_config.trusted_factor
+    } else {
+      self.account_config.factor
+    };
+    if factor == 0 {
+      return RateLimitInfo::UNLIMITED;
+    }
+
+    let result = self.account_buckets.check(
+      action_type,
+      now,
+      self.bucket_configs,
+      (factor, ()),
+      (account.local_user_id, ()),
+    );
+
+    if !result.passed {
+      debug!(
+        "Rate limited account: {}, type: {action_type:?}",
+        account.local_user_id
+      );
+    }
+
+    result
+  }
+
   /// Remove buckets that are now full
   pub fn remove_full_buckets(&mut self, now: InstantSecs) {
     self
@@ -298,14 +449,40 @@ impl RateLimitState {
     self
       .ipv6_buckets
       .remove_full_buckets(now, self.bucket_configs);
+    self
+      .account_buckets
+      .remove_full_buckets(now, self.bucket_configs);
   }
 
   pub fn set_config(&mut self, new_configs: EnumMap<ActionType, BucketConfig>) {
     self.bucket_configs = new_configs;
   }
+
+  pub fn set_account_config(&mut self, new_config: AccountLimitConfig) {
+    self.account_config = new_config;
+  }
+
+  pub fn bucket_config(&self, action_type: ActionType) -> BucketConfig {
+    #[allow(clippy::indexing_slicing)]
+    self.bucket_configs[action_type]
+  }
+
+  pub fn account_config(&self) -> AccountLimitConfig {
+    self.account_config
+  }
+
+  /// The longest time which any bucket needs to fill up again after it was emptied.
+  pub fn max_secs_to_refill(&self) -> u32 {
+    self
+      .bucket_configs
+      .values()
+      .map(|config| config.secs_to_refill)
+      .max()
+      .unwrap_or_default()
+  }
 }
 
-fn split_ipv6(ip: Ipv6Addr) -> ([u8; 6], u8, u8) {
+pub(super) fn split_ipv6(ip: Ipv6Addr) -> ([u8; 6], u8, u8) {
   let [a0, a1, a2, a3, a4, a5, b, c, ..] = ip.octets();
   ([a0, a1, a2, a3, a4, a5], b, c)
 }
@@ -315,7 +492,17 @@ fn split_ipv6(ip: Ipv6Addr) -> ([u8; 6], u8, u8) {
 #[allow(clippy::indexing_slicing)]
 mod tests {
 
-  use super::{ActionType, BucketConfig, InstantSecs, RateLimitState, RateLimitedGroup};
+  use super::{
+    AccountLimitConfig,
+    ActionType,
+    BucketConfig,
+    BucketState,
+    InstantSecs,
+    RateLimitAccount,
+    RateLimitInfo,
+    RateLimitState,
+    RateLimitedGroup,
+  };
   use pretty_assertions::assert_eq;
 
   #[test]
@@ -355,8 +542,8 @@ mod tests {
     ];
     for ip in ips {
       let ip = ip.parse().unwrap();
-      let message_passed = rate_limiter.check(ActionType::Message, ip, now);
-      let post_passed = rate_limiter.check(ActionType::Post, ip, now);
+      let message_passed = rate_limiter.check(ActionType::Message, ip, now).passed;
+      let post_passed = rate_limiter.check(ActionType::Post, ip, now).passed;
       assert!(message_passed);
       assert!(post_passed);
     }
@@ -382,6 +569,8 @@ mod tests {
       rate_limiter,
       RateLimitState {
         bucket_configs,
+        account_config: AccountLimitConfig::default(),
+        account_buckets: [].into(),
         ipv4_buckets: [([123, 123, 123, 123].into(), bottom_group(1))].into(),
         ipv6_buckets: [(
           [0, 1, 0, 2, 0, 3],
@@ -413,7 +602,7 @@ mod tests {
     // Do 2 `Message` actions for 1 IP address and expect only the 2nd one to fail
     for expected_to_pass in [true, false] {
       let ip = "1:2:3:0400::".parse().unwrap();
-      let passed = rate_limiter.check(ActionType::Message, ip, now);
+      let passed = rate_limiter.check(ActionType::Message, ip, now).passed;
       assert_eq!(passed, expected_to_pass);
     }
 
@@ -426,8 +615,8 @@ mod tests {
     // `remove full buckets` should not remove empty buckets
     let ip = "1.1.1.1".parse().unwrap();
     // empty the bucket with 2 requests
-    assert!(rate_limiter.check(ActionType::Post, ip, now));
-    assert!(rate_limiter.check(ActionType::Post, ip, now));
+    assert!(rate_limiter.check(ActionType::Post, ip, now).passed);
+    assert!(rate_limiter.check(ActionType::Post, ip, now).passed);
 
     rate_limiter.remove_full_buckets(now);
     assert!(!rate_limiter.ipv4_buckets.is_empty());
@@ -436,9 +625,101 @@ mod tests {
     now.secs += 2;
     let ip = "1.1.1.1".parse().unwrap();
     // Only make one request, so bucket still has 1 token
-    assert!(rate_limiter.check(ActionType::Post, ip, now));
+    assert!(rate_limiter.check(ActionType::Post, ip, now).passed);
 
     rate_limiter.remove_full_buckets(now);
     assert!(!rate_limiter.ipv4_buckets.is_empty());
   }
+  #[test]
+  fn test_account_rate_limiter() {
+    let bucket_configs = enum_map::enum_map! {
+      _ => BucketConfig {
+        capacity: 2,
+        secs_to_refill: 1,
+      },
+    };
+    let mut rate_limiter = RateLimitState::new(bucket_configs);
+    rate_limiter.set_account_config(AccountLimitConfig {
+      factor: 1,
+      trusted_factor: 3,
+    });
+    let now = InstantSecs::now();
+    let account = RateLimitAccount {
+      local_user_id: 1,
+      trusted: false,
+    };
+    let trusted_account = RateLimitAccount {
+      local_user_id: 2,
+      trusted: true,
+    };
+
+    // The per-account limit applies regardless of ip address
+    for expected_to_pass in [true, true, false] {
+      let passed = rate_limiter
+        .check_account(ActionType::Post, account, now)
+        .passed;
+      assert_eq!(passed, expected_to_pass);
+    }
+
+    // Trusted accounts get a larger capacity
+    for expected_to_pass in [true, true, true, true, true, true, false] {
+      let passed = rate_limiter
+        .check_account(ActionType::Post, trusted_account, now)
+        .passed;
+      assert_eq!(passed, expected_to_pass);
+    }
+
+    // A factor of 0 disables the limit
+    rate_limiter.set_account_config(AccountLimitConfig {
+      factor: 0,
+      trusted_factor: 0,
+    });
+    assert!(
+      rate_limiter
+        .check_account(ActionType::Post, account, now)
+        .passed
+    );
+    assert!(
+      rate_limiter
+        .check_account(ActionType::Post, trusted_account, now)
+        .passed
+    );
+  }
+
+  #[test]
+  fn test_rate_limit_info() {
+    let bucket_configs = enum_map::enum_map! {
+      _ => BucketConfig {
+        capacity: 2,
+        secs_to_refill: 10,
+      },
+    };
+    let mut rate_limiter = RateLimitState::new(bucket_configs);
+    let now = InstantSecs::now();
+    let ip = "1.2.3.4".parse().unwrap();
+
+    let expected = [(true, 1, 5, 0), (true, 0, 10, 5), (false, 0, 10, 5)];
+    for (passed, remaining, reset, retry_after) in expected {
+      let info = rate_limiter.check(ActionType::Post, ip, now);
+      assert_eq!(
+        RateLimitInfo {
+          passed,
+          bucket: Some(BucketState {
+            limit: 2,
+            remaining,
+            reset,
+            retry_after,
+          }),
+        },
+        info
+      );
+    }
+
+    // The combined result describes the bucket with fewer remaining tokens
+    let other = rate_limiter.check(ActionType::Comment, ip, now);
+    let combined = other.combine(rate_limiter.check(ActionType::Post, ip, now));
+    assert!(!combined.passed);
+    assert_eq!(Some(0), combined.bucket.map(|b| b.remaining));
+    assert_eq!(other, other.combine(RateLimitInfo::UNLIMITED));
+  }
 }
@@ -0,0 +1,108 @@
+use super::rate_limiter::{
+  split_ipv6,
+  AccountLimitConfig,
+  ActionType,
+  BucketConfig,
+  RateLimitAccount,
+  RateLimitInfo,
+};
+use crate::error::LemmyResult;
+use futures::future::BoxFuture;
+use std::net::{IpAddr, Ipv6Addr};
+
+/// Stores rate limit buckets outside of the lemmy process, so that multiple processes behind a
+/// load balancer share the same limits.
+pub trait SharedRateLimitBackend: Send + Sync {
+  /// Take one token from the bucket with the given key. The result doesn't pass if the bucket is
+  /// empty and the request should be rejected.
+  fn check<'a>(
+    &'a self,
+    action_type: ActionType,
+    key: &'a str,
+    config: BucketConfig,
+  ) -> BoxFuture<'a, LemmyResult<RateLimitInfo>>;
+
+  /// Remove buckets which haven't been used for longer than the given time, meaning that they
+  /// are full again.
+  fn remove_full_buckets(&self, max_secs_to_refill: u32) -> BoxFuture<'_, LemmyResult<()>>;
+}
+
+/// Returns the keys of all buckets which need to be checked for a request, along with their
+/// capacity factor. This mirrors the grouping of the in-memory `RateLimitState`.
+pub(super) fn bucket_keys(
+  ip: IpAddr,
+  account: Option<RateLimitAccount>,
+  account_config: AccountLimitConfig,
+) -> Vec<(String, u32)> {
+  let mut keys = vec![];
+
+  // Trusted accounts are only limited per account
+  if !account.is_some_and(|a| a.trusted) {
+    match ip {
+      IpAddr::V4(ipv4) => keys.push((format!("ip:{ipv4}"), 1)),
+      IpAddr::V6(ipv6) => {
+        let (key_48, key_56, key_64) = split_ipv6(ipv6);
+        let mut octets = [0; 16];
+        octets[..6].copy_from_slice(&key_48);
+        keys.push((format!("ip:{}/48", Ipv6Addr::from(octets)), 16));
+        octets[6] = key_56;
+        keys.push((format!("ip:{}/56", Ipv6Addr::from(octets)), 4));
+        octets[7] = key_64;
+        keys.push((format!("ip:{}/64", Ipv6Addr::from(octets)), 1));
+      }
+    }
+  }
+
+  if let Some(account) = account {
+    let factor = if account.trusted {
+      account_config.trusted_factor
+    } else {
+      account_config.factor
+    };
+    if factor != 0 {
+      keys.push((format!("user:{}", account.local_user_id), factor));
+    }
+  }
+
+  keys
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use super::{bucket_keys, AccountLimitConfig, RateLimitAccount};
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_bucket_keys() {
+    let config = AccountLimitConfig::default();
+    let account = RateLimitAccount {
+      local_user_id: 5,
+      trusted: false,
+    };
+    let trusted_account = RateLimitAccount {
+      local_user_id: 6,
+      trusted: true,
+    };
+
+    assert_eq!(
+      vec![("ip:1.2.3.4".to_string(), 1)],
+      bucket_keys("1.2.3.4".parse().unwrap(), None, config)
+    );
+    assert_eq!(
+      vec![
+        ("ip:1:2:3::/48".to_string(), 16),
+        ("ip:1:2:3:400::/56".to_string(), 4),
+        ("ip:1:2:3:405::/64".to_string(), 1),
+        ("user:5".to_string(), 1),
+      ],
+      bucket_keys("1:2:3:0405:6::".parse().unwrap(), Some(account), config)
+    );
+    assert_eq!(
+      vec![("user:6".to_string(), 10)],
+      bucket_keys("1.2.3.4".parse().unwrap(), Some(trusted_account), config)
+    );
+  }
+}
@@ -16,7 +16,8 @@ pub struct Settings {
   /// Pictrs image server configuration.
   #[default(Some(Default::default()))]
   pub(crate) pictrs: Option<PictrsConfig>,
-  /// Email sending configuration. All options except login/password are mandatory
+  /// Email sending configuration. With smtp transport, all options except login/password are
+  /// mandatory
   #[default(None)]
   #[doku(example = "Some(Default::default())")]
   pub email: Option<EmailConfig>,
@@ -45,6 +46,11 @@ pub struct Settings {
   pub opentelemetry_url: Option<Url>,
   #[default(Default::default())]
   pub federation: FederationWorkerConfig,
+  /// Where rate limit state is stored. Set this to `Postgres` if multiple lemmy processes run
+  /// behind a load balancer, so that they enforce the same limits.
+  #[default(RateLimitBackend::Memory)]
+  #[doku(meta(r#"fmt.enums_style = "Commented""#))]
+  pub rate_limit_backend: RateLimitBackend,
   // Prometheus configuration.
   #[default(None)]
   #[doku(example = "Some(Default::default())")]
@@ -128,6 +134,16 @@ pub enum PictrsImageMode {
   ProxyAllImages,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, SmartDefault, Document, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub enum RateLimitBackend {
+  /// Keep rate limits in memory. This is the fastest option, but each process has its own limits.
+  #[default]
+  Memory,
+  /// Store rate limits in the database, so that they are shared between all processes.
+  Postgres,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
 #[serde(default)]
 pub struct DatabaseConfig {
@@ -192,8 +208,14 @@ pub struct DatabaseConnectionParts {
 #[derive(Debug, Deserialize, Serialize, Clone, Document, SmartDefault)]
 #[serde(deny_unknown_fields)]
 pub struct EmailConfig {
+  /// How emails are delivered
+  #[default(EmailTransport::Smtp)]
+  #[serde(default)]
+  #[doku(meta(r#"fmt.enums_style = "Commented""#))]
+  pub transport: EmailTransport,
   /// Hostname and port of the smtp server
   #[doku(example = "localhost:25")]
+  #[serde(default)]
   pub smtp_server: String,
   /// Login name for smtp server
   pub smtp_login: Option<String>,
@@ -206,6 +228,29 @@ pub struct EmailConfig {
   #[default("none")]
   #[doku(example = "none")]
   pub tls_type: String,
+  /// Path of the sendmail binary for the `Sendmail` transport. If not set, `sendmail` is looked
+  /// up in `PATH`.
+  #[default(None)]
+  #[doku(example = "/usr/sbin/sendmail")]
+  pub sendmail_command: Option<String>,
+  /// Directory where emails are written as `.eml` files with the `File` transport. It needs to
+  /// exist already.
+  #[default(None)]
+  #[doku(example = "/tmp/lemmy_emails")]
+  pub file_directory: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, SmartDefault, Document, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub enum EmailTransport {
+  /// Send emails to the smtp server in `smtp_server`.
+  #[default]
+  Smtp,
+  /// Pass emails to the local sendmail binary, for hosts which relay through a local MTA.
+  Sendmail,
+  /// Write each email to a file in `file_directory` instead of sending it. Useful for
+  /// development and tests.
+  File,
 }
 
 impl EmailConfig {
@@ -23,6 +23,8 @@ const ALT_TEXT_MAX_LENGTH: usize = 1500;
 const SITE_NAME_MAX_LENGTH: usize = 20;
 const SITE_NAME_MIN_LENGTH: usize = 1;
 const SITE_DESCRIPTION_MAX_LENGTH: usize = 150;
+const API_TOKEN_NAME_MAX_LENGTH: usize = 50;
+const SAVED_SEARCH_MAX_LENGTH: usize = 200;
 //Invisible unicode characters, taken from https://invisible-characters.com/
 const FORBIDDEN_DISPLAY_CHARS: [char; 53] = [
   '\u{0009}',
@@ -200,6 +202,28 @@ pub fn site_description_length_check(description: &str) -> LemmyResult<()> {
   )
 }
 
+/// Checks the name given to an api token.
+pub fn is_valid_api_token_name(name: &str) -> LemmyResult<()> {
+  let check = !name.trim().is_empty() && !has_newline(name);
+  if !check {
+    Err(LemmyErrorType::InvalidApiTokenName)?
+  }
+  max_length_check(
+    name,
+    API_TOKEN_NAME_MAX_LENGTH,
+    LemmyErrorType::InvalidApiTokenName,
+  )
+}
+
+/// Checks the query of a saved search.
+pub fn is_valid_saved_search(q: &str) -> LemmyResult<()> {
+  let check = !q.trim().is_empty() && !has_newline(q);
+  if !check {
+    Err(LemmyErrorType::InvalidSavedSearch)?
+  }
+  max_length_check(q, SAVED_SEARCH_MAX_LENGTH, LemmyErrorType::InvalidSavedSearch)
+}
+
 /// Check minimum and maximum length of input string. If the string is too short or too long, the
 /// corresponding error is returned.
 ///
@@ -364,10 +388,12 @@ mod tests {
       clean_urls_in_text,
       is_url_blocked,
       is_valid_actor_name,
+      is_valid_api_token_name,
       is_valid_bio_field,
       is_valid_display_name,
       is_valid_matrix_id,
       is_valid_post_title,
+      is_valid_saved_search,
       is_valid_url,
       site_description_length_check,
       site_name_length_check,
@@ -482,6 +508,22 @@ mod tests {
     assert!(is_valid_matrix_id("@dess:matrix.org t").is_err());
   }
 
+  #[test]
+  fn test_valid_api_token_name() {
+    assert!(is_valid_api_token_name("my bot").is_ok());
+    assert!(is_valid_api_token_name("   ").is_err());
+    assert!(is_valid_api_token_name("my\nbot").is_err());
+    assert!(is_valid_api_token_name(&"a".repeat(51)).is_err());
+  }
+
+  #[test]
+  fn test_valid_saved_search() {
+    assert!(is_valid_saved_search(r#"outage site:github.com -"false alarm""#).is_ok());
+    assert!(is_valid_saved_search("  ").is_err());
+    assert!(is_valid_saved_search("outage\ndown").is_err());
+    assert!(is_valid_saved_search(&"a".repeat(201)).is_err());
+  }
+
   #[test]
   fn test_valid_site_name() -> LemmyResult<()> {
     let valid_names = [
@@ -0,0 +1,15 @@
+-- Long-lived, revocable tokens which allow bots and integrations to use the API without logging in
+-- with a password. Only a hash of the token is stored.
+CREATE TABLE api_token (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    token_hash text NOT NULL UNIQUE,
+    scopes text[] NOT NULL CHECK (scopes <@ ARRAY['Read', 'Post', 'Vote', 'Moderate', 'Admin']::text[]),
+    published timestamp with time zone NOT NULL DEFAULT now(),
+    expires timestamp with time zone,
+    last_used timestamp with time zone
+);
+
+CREATE INDEX idx_api_token_local_user ON api_token (local_user_id);
+
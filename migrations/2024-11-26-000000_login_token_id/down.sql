@@ -0,0 +1,3 @@
+ALTER TABLE login_token
+    DROP COLUMN id;
+
@@ -0,0 +1,4 @@
+-- The token itself is never exposed to clients, so add an id which allows revoking a single session.
+ALTER TABLE login_token
+    ADD COLUMN id serial NOT NULL UNIQUE;
+
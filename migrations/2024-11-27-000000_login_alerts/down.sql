@@ -0,0 +1,3 @@
+ALTER TABLE local_user
+    DROP COLUMN send_login_alerts;
+
@@ -0,0 +1,3 @@
+ALTER TABLE local_user
+    ADD COLUMN send_login_alerts boolean DEFAULT TRUE NOT NULL;
+
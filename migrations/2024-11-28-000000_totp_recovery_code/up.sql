@@ -0,0 +1,11 @@
+-- One-time codes which can be used to login instead of a totp token, in case the 2fa device is lost.
+-- Only a hash of each code is stored.
+CREATE TABLE totp_recovery_code (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    code_hash text NOT NULL,
+    published timestamp with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_totp_recovery_code_local_user ON totp_recovery_code (local_user_id);
+
@@ -0,0 +1,5 @@
+ALTER TABLE local_user
+    DROP COLUMN failed_login_count,
+    DROP COLUMN last_failed_login,
+    DROP COLUMN login_locked_until;
+
@@ -0,0 +1,10 @@
+-- Track failed logins per account, to lock it temporarily against password guessing.
+ALTER TABLE local_user
+    ADD COLUMN failed_login_count int DEFAULT 0 NOT NULL,
+    ADD COLUMN last_failed_login timestamp with time zone,
+    ADD COLUMN login_locked_until timestamp with time zone;
+
+CREATE INDEX idx_local_user_failed_login ON local_user (last_failed_login DESC)
+WHERE
+    failed_login_count > 0;
+
@@ -0,0 +1,7 @@
+ALTER TABLE local_site_rate_limit
+    DROP COLUMN account_factor,
+    DROP COLUMN trusted_account_factor;
+
+ALTER TABLE local_user
+    DROP COLUMN trusted_bot;
+
@@ -0,0 +1,9 @@
+-- Per-account rate limits, given as a multiple of the per-ip limits. A value of 0 disables them.
+ALTER TABLE local_site_rate_limit
+    ADD COLUMN account_factor int DEFAULT 1 NOT NULL,
+    ADD COLUMN trusted_account_factor int DEFAULT 10 NOT NULL;
+
+-- Bot accounts which were marked as trusted by an admin get the same rate limits as admins.
+ALTER TABLE local_user
+    ADD COLUMN trusted_bot boolean DEFAULT FALSE NOT NULL;
+
@@ -0,0 +1,10 @@
+ALTER TABLE local_site_rate_limit
+    DROP COLUMN vote,
+    DROP COLUMN vote_per_second,
+    DROP COLUMN report,
+    DROP COLUMN report_per_second,
+    DROP COLUMN follow,
+    DROP COLUMN follow_per_second,
+    DROP COLUMN federation_fetch,
+    DROP COLUMN federation_fetch_per_second;
+
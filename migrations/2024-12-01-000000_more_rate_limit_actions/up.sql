@@ -0,0 +1,10 @@
+ALTER TABLE local_site_rate_limit
+    ADD COLUMN vote int DEFAULT 60 NOT NULL,
+    ADD COLUMN vote_per_second int DEFAULT 60 NOT NULL,
+    ADD COLUMN report int DEFAULT 10 NOT NULL,
+    ADD COLUMN report_per_second int DEFAULT 600 NOT NULL,
+    ADD COLUMN follow int DEFAULT 30 NOT NULL,
+    ADD COLUMN follow_per_second int DEFAULT 600 NOT NULL,
+    ADD COLUMN federation_fetch int DEFAULT 60 NOT NULL,
+    ADD COLUMN federation_fetch_per_second int DEFAULT 600 NOT NULL;
+
@@ -0,0 +1,12 @@
+-- Rate limit buckets which are shared between multiple lemmy processes. Losing them on a crash is
+-- harmless, so the table is unlogged for better performance.
+CREATE UNLOGGED TABLE rate_limit_bucket (
+    key text NOT NULL,
+    action_type text NOT NULL,
+    tokens double precision NOT NULL,
+    last_checked timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (key, action_type)
+);
+
+CREATE INDEX idx_rate_limit_bucket_last_checked ON rate_limit_bucket (last_checked);
+
@@ -0,0 +1,11 @@
+CREATE TYPE email_notification_frequency_enum AS enum (
+    'Instant',
+    'Hourly',
+    'Daily',
+    'Weekly'
+);
+
+ALTER TABLE local_user
+    ADD COLUMN email_notification_frequency email_notification_frequency_enum DEFAULT 'Instant' NOT NULL,
+    ADD COLUMN last_email_digest timestamptz;
+
@@ -0,0 +1,28 @@
+CREATE TYPE email_template_type_enum AS enum (
+    'EmailVerification',
+    'PasswordReset',
+    'ApplicationApproved',
+    'NewApplication',
+    'NewReport',
+    'Mention',
+    'CommentReply',
+    'PostReply',
+    'PrivateMessage',
+    'SavedSearchMatch',
+    'LoginLocked'
+);
+
+-- Admin defined emails, which replace the built-in translations. A template without language
+-- is used for all languages which don't have their own template.
+CREATE TABLE email_template (
+    id serial PRIMARY KEY,
+    template_type email_template_type_enum NOT NULL,
+    language varchar(20),
+    subject text NOT NULL,
+    body text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz
+);
+
+CREATE UNIQUE INDEX idx_email_template_type_language ON email_template (template_type, coalesce(language, ''));
+
@@ -0,0 +1,5 @@
+ALTER TABLE local_user
+    DROP COLUMN send_reply_emails,
+    DROP COLUMN send_mention_emails,
+    DROP COLUMN send_private_message_emails;
+
@@ -0,0 +1,6 @@
+-- Allow turning off notification emails per category, eg with an unsubscribe link
+ALTER TABLE local_user
+    ADD COLUMN send_reply_emails boolean DEFAULT TRUE NOT NULL,
+    ADD COLUMN send_mention_emails boolean DEFAULT TRUE NOT NULL,
+    ADD COLUMN send_private_message_emails boolean DEFAULT TRUE NOT NULL;
+
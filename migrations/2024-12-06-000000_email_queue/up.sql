@@ -0,0 +1,20 @@
+-- Outgoing emails, which are delivered by a background worker so that api requests don't wait
+-- for the mail server. Sent emails are deleted, failed ones are kept for admins to review.
+CREATE TABLE email_queue (
+    id serial PRIMARY KEY,
+    to_email text NOT NULL,
+    to_username text NOT NULL,
+    subject text NOT NULL,
+    body text NOT NULL,
+    unsubscribe_link text,
+    attempts int NOT NULL DEFAULT 0,
+    last_error text,
+    failed boolean NOT NULL DEFAULT FALSE,
+    next_attempt timestamptz NOT NULL DEFAULT now(),
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_email_queue_next_attempt ON email_queue (next_attempt)
+WHERE
+    NOT failed;
+
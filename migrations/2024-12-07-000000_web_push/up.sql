@@ -0,0 +1,17 @@
+-- Keys which identify this instance to push services (VAPID, RFC 8292). They are generated on
+-- startup.
+ALTER TABLE secret
+    ADD COLUMN vapid_private_key text,
+    ADD COLUMN vapid_public_key text;
+
+-- One Web Push subscription per device of a user
+CREATE TABLE push_subscription (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    endpoint text NOT NULL,
+    p256dh text NOT NULL,
+    auth text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (local_user_id, endpoint)
+);
+
@@ -0,0 +1,30 @@
+ALTER TABLE local_user
+    DROP COLUMN report_resolved_notifications;
+
+DELETE FROM mod_action_notification
+WHERE kind = 'ReportResolved'
+    OR community_id IS NULL;
+
+ALTER TABLE mod_action_notification
+    DROP COLUMN report_outcome,
+    DROP COLUMN private_message_id,
+    ALTER COLUMN community_id SET NOT NULL;
+
+DROP TYPE report_outcome_enum;
+
+-- Enum values can't be removed, so recreate the type
+ALTER TYPE mod_action_notification_type_enum RENAME TO mod_action_notification_type_enum__;
+
+CREATE TYPE mod_action_notification_type_enum AS enum (
+    'RemovePost',
+    'RemoveComment',
+    'LockPost',
+    'BanFromCommunity'
+);
+
+ALTER TABLE mod_action_notification
+    ALTER COLUMN kind TYPE mod_action_notification_type_enum
+    USING kind::text::mod_action_notification_type_enum;
+
+DROP TYPE mod_action_notification_type_enum__;
+
@@ -0,0 +1,19 @@
+-- Tell reporters what happened after their report was resolved
+ALTER TYPE mod_action_notification_type_enum
+    ADD VALUE 'ReportResolved';
+
+CREATE TYPE report_outcome_enum AS enum (
+    'ContentRemoved',
+    'UserBanned',
+    'NoAction'
+);
+
+-- Private message reports are not tied to a community
+ALTER TABLE mod_action_notification
+    ALTER COLUMN community_id DROP NOT NULL,
+    ADD COLUMN private_message_id int REFERENCES private_message ON UPDATE CASCADE ON DELETE CASCADE,
+    ADD COLUMN report_outcome report_outcome_enum;
+
+ALTER TABLE local_user
+    ADD COLUMN report_resolved_notifications boolean DEFAULT TRUE NOT NULL;
+
@@ -0,0 +1,6 @@
+DROP TABLE webhook_delivery;
+
+DROP TABLE webhook;
+
+DROP TYPE webhook_event_type_enum;
+
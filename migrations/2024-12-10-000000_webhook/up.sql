@@ -0,0 +1,45 @@
+CREATE TYPE webhook_event_type_enum AS enum (
+    'ReportCreated',
+    'RegistrationApplicationCreated',
+    'ModAction',
+    'PostCreated'
+);
+
+-- Outgoing http requests for site events, configured by admins. Webhooks with a community are
+-- configured by its moderators, and only receive the events of that community.
+CREATE TABLE webhook (
+    id serial PRIMARY KEY,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    url text NOT NULL,
+    secret text NOT NULL,
+    events webhook_event_type_enum[] NOT NULL,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz
+);
+
+CREATE INDEX idx_webhook_community ON webhook (community_id);
+
+-- Every event which is sent to a webhook. Delivered and failed ones are kept for some time as a
+-- log for the webhook owners.
+CREATE TABLE webhook_delivery (
+    id serial PRIMARY KEY,
+    webhook_id int REFERENCES webhook ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    event webhook_event_type_enum NOT NULL,
+    payload text NOT NULL,
+    attempts int NOT NULL DEFAULT 0,
+    response_status int,
+    last_error text,
+    delivered boolean NOT NULL DEFAULT FALSE,
+    failed boolean NOT NULL DEFAULT FALSE,
+    next_attempt timestamptz NOT NULL DEFAULT now(),
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_webhook_delivery_webhook ON webhook_delivery (webhook_id, published DESC);
+
+CREATE INDEX idx_webhook_delivery_next_attempt ON webhook_delivery (next_attempt)
+WHERE
+    NOT delivered AND NOT failed;
+
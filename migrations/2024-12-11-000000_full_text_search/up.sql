@@ -0,0 +1,66 @@
+-- Sort search results by how well they match
+ALTER TYPE sort_type_enum
+    ADD VALUE 'Relevance';
+
+-- Full text search vectors, which are kept up to date by triggers
+CREATE TABLE post_search (
+    post_id int PRIMARY KEY REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    search_vector tsvector NOT NULL
+);
+
+CREATE INDEX idx_post_search_vector ON post_search USING gin (search_vector);
+
+CREATE TABLE comment_search (
+    comment_id int PRIMARY KEY REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    search_vector tsvector NOT NULL
+);
+
+CREATE INDEX idx_comment_search_vector ON comment_search USING gin (search_vector);
+
+CREATE TABLE community_search (
+    community_id int PRIMARY KEY REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    search_vector tsvector NOT NULL
+);
+
+CREATE INDEX idx_community_search_vector ON community_search USING gin (search_vector);
+
+CREATE TABLE person_search (
+    person_id int PRIMARY KEY REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    search_vector tsvector NOT NULL
+);
+
+CREATE INDEX idx_person_search_vector ON person_search USING gin (search_vector);
+
+-- The search vectors of existing rows are filled in by a batched job on startup, because the
+-- functions that compute them are only created after the migrations have run. Newer rows get them
+-- from the triggers, so only rows up to the current highest id need it. The job deletes the entry
+-- of a table when it's done, so that it doesn't need to scan the table again.
+CREATE TABLE search_vector_backfill (
+    table_name text PRIMARY KEY,
+    last_id int NOT NULL
+);
+
+INSERT INTO search_vector_backfill (table_name, last_id)
+SELECT
+    'post',
+    coalesce(max(id), 0)
+FROM
+    post
+UNION ALL
+SELECT
+    'comment',
+    coalesce(max(id), 0)
+FROM
+    comment
+UNION ALL
+SELECT
+    'community',
+    coalesce(max(id), 0)
+FROM
+    community
+UNION ALL
+SELECT
+    'person',
+    coalesce(max(id), 0)
+FROM
+    person;
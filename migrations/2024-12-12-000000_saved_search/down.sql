@@ -0,0 +1,4 @@
+DROP TABLE saved_search_match;
+
+DROP TABLE saved_search;
+
@@ -0,0 +1,33 @@
+-- Searches which are checked regularly for new posts and comments. Content with a higher id than
+-- the last checked one is evaluated, and also a margin behind it, because ids are assigned before
+-- the content is committed. These are null until the first check, so that content from before the
+-- search was saved doesn't match.
+CREATE TABLE saved_search (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    query text NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    post_title_only boolean NOT NULL DEFAULT FALSE,
+    send_email boolean NOT NULL DEFAULT FALSE,
+    last_post_id int,
+    last_comment_id int,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_saved_search_local_user ON saved_search (local_user_id);
+
+-- New posts and comments which matched a saved search. For comments, the post is also set.
+CREATE TABLE saved_search_match (
+    id serial PRIMARY KEY,
+    saved_search_id int REFERENCES saved_search ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    read boolean NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_saved_search_match_saved_search ON saved_search_match (saved_search_id, published DESC);
+
+-- Content in the margin behind the last checked ids is checked again, so it must only match once
+CREATE UNIQUE INDEX idx_saved_search_match_unique ON saved_search_match (saved_search_id, post_id, coalesce(comment_id, 0));
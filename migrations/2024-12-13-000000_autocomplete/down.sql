@@ -0,0 +1,2 @@
+DROP INDEX idx_person_lower_name_prefix, idx_community_lower_name_prefix, idx_custom_emoji_lower_shortcode_prefix, idx_custom_emoji_keyword_lower_keyword_prefix;
+
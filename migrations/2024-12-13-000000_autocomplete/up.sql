@@ -0,0 +1,10 @@
+-- Prefix indexes for autocomplete, which matches the start of lowercased names with LIKE. The
+-- existing indexes on lower(name) can't be used for this, unless the database uses the C locale.
+CREATE INDEX idx_person_lower_name_prefix ON person (lower(name) text_pattern_ops);
+
+CREATE INDEX idx_community_lower_name_prefix ON community (lower(name) text_pattern_ops);
+
+CREATE INDEX idx_custom_emoji_lower_shortcode_prefix ON custom_emoji (lower(shortcode) text_pattern_ops);
+
+CREATE INDEX idx_custom_emoji_keyword_lower_keyword_prefix ON custom_emoji_keyword (lower(keyword) text_pattern_ops);
+
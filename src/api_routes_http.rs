@@ -25,24 +25,42 @@ use lemmy_api::{
     block::block_person,
     change_password::change_password,
     change_password_after_reset::change_password_after_reset,
+    create_api_token::create_api_token,
+    delete_push_subscription::delete_push_subscription,
+    delete_saved_search::delete_saved_search,
     generate_totp_secret::generate_totp_secret,
     get_captcha::get_captcha,
+    get_vapid_public_key::get_vapid_public_key,
+    list_api_tokens::list_api_tokens,
     list_banned::list_banned_users,
     list_logins::list_logins,
     list_media::list_media,
+    list_saved_searches::list_saved_searches,
     login::login,
     logout::logout,
     notifications::{
+      event_stream::get_event_stream,
       list_mentions::list_mentions,
+      list_mod_actions::list_mod_action_notifications,
       list_replies::list_replies,
+      list_saved_search_matches::list_saved_search_matches,
       mark_all_read::mark_all_notifications_read,
       mark_mention_read::mark_person_mention_as_read,
+      mark_mod_action_read::mark_mod_action_notification_as_read,
       mark_reply_read::mark_reply_as_read,
+      mark_saved_search_match_read::mark_saved_search_match_as_read,
       unread_count::unread_count,
     },
+    regenerate_totp_recovery_codes::regenerate_totp_recovery_codes,
+    register_push_subscription::register_push_subscription,
     report_count::report_count,
     reset_password::reset_password,
+    revoke_api_token::revoke_api_token,
+    revoke_login::revoke_login,
+    revoke_other_logins::revoke_other_logins,
     save_settings::save_user_settings,
+    trust_bot::trust_bot,
+    unsubscribe::{unsubscribe, unsubscribe_page},
     update_totp::update_totp,
     validate_auth::validate_auth,
     verify_email::verify_email,
@@ -69,10 +87,13 @@ use lemmy_api::{
     resolve::resolve_pm_report,
   },
   site::{
+    autocomplete::autocomplete,
     block::block_instance,
     federated_instances::get_federated_instances,
     leave_admin::leave_admin,
     list_all_media::list_all_media,
+    list_failed_emails::list_failed_emails,
+    list_failed_logins::list_failed_logins,
     mod_log::get_mod_log,
     purge::{
       comment::purge_comment,
@@ -109,6 +130,13 @@ use lemmy_api_crud::{
     delete::delete_custom_emoji,
     update::update_custom_emoji,
   },
+  email_template::{
+    create::create_email_template,
+    delete::delete_email_template,
+    list::list_email_templates,
+    preview::preview_email_template,
+    update::update_email_template,
+  },
   post::{
     create::create_post,
     delete::delete_post,
@@ -124,8 +152,17 @@ use lemmy_api_crud::{
   },
   site::{create::create_site, read::get_site, update::update_site},
   user::{create::register, delete::delete_account},
+  webhook::{
+    create::create_webhook,
+    delete::delete_webhook,
+    list::list_webhooks,
+    list_deliveries::list_webhook_deliveries,
+    update::update_webhook,
+  },
 };
 use lemmy_apub::api::{
+  create_saved_search::create_saved_search,
+  list_comment_tree::list_comment_tree,
   list_comments::list_comments,
   list_posts::list_posts,
   read_community::get_community,
@@ -161,9 +198,14 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .wrap(rate_limit.search())
           .route(web::get().to(search)),
       )
+      .service(
+        web::resource("/autocomplete")
+          .wrap(rate_limit.search())
+          .route(web::get().to(autocomplete)),
+      )
       .service(
         web::resource("/resolve_object")
-          .wrap(rate_limit.message())
+          .wrap(rate_limit.federation_fetch())
           .route(web::get().to(resolve_object)),
       )
       // Community
@@ -173,6 +215,12 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .wrap(rate_limit.register())
           .route(web::post().to(create_community)),
       )
+      .service(
+        web::resource("/community/follow")
+          .guard(guard::Post())
+          .wrap(rate_limit.follow())
+          .route(web::post().to(follow_community)),
+      )
       .service(
         web::scope("/community")
           .wrap(rate_limit.message())
@@ -180,7 +228,6 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .route("", web::put().to(update_community))
           .route("/hide", web::put().to(hide_community))
           .route("/list", web::get().to(list_communities))
-          .route("/follow", web::post().to(follow_community))
           .route("/block", web::post().to(block_community))
           .route("/delete", web::post().to(delete_community))
           // Mod Actions
@@ -202,6 +249,18 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .wrap(rate_limit.post())
           .route(web::post().to(create_post)),
       )
+      .service(
+        web::resource("/post/like")
+          .guard(guard::Post())
+          .wrap(rate_limit.vote())
+          .route(web::post().to(like_post)),
+      )
+      .service(
+        web::resource("/post/report")
+          .guard(guard::Post())
+          .wrap(rate_limit.report())
+          .route(web::post().to(create_post_report)),
+      )
       .service(
         web::scope("/post")
           .wrap(rate_limit.message())
@@ -214,10 +273,8 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .route("/lock", web::post().to(lock_post))
           .route("/feature", web::post().to(feature_post))
           .route("/list", web::get().to(list_posts))
-          .route("/like", web::post().to(like_post))
           .route("/like/list", web::get().to(list_post_likes))
           .route("/save", web::put().to(save_post))
-          .route("/report", web::post().to(create_post_report))
           .route("/report/resolve", web::put().to(resolve_post_report))
           .route("/report/list", web::get().to(list_post_reports))
           .route("/site_metadata", web::get().to(get_link_metadata)),
@@ -230,6 +287,18 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .wrap(rate_limit.comment())
           .route(web::post().to(create_comment)),
       )
+      .service(
+        web::resource("/comment/like")
+          .guard(guard::Post())
+          .wrap(rate_limit.vote())
+          .route(web::post().to(like_comment)),
+      )
+      .service(
+        web::resource("/comment/report")
+          .guard(guard::Post())
+          .wrap(rate_limit.report())
+          .route(web::post().to(create_comment_report)),
+      )
       .service(
         web::scope("/comment")
           .wrap(rate_limit.message())
@@ -239,15 +308,20 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .route("/remove", web::post().to(remove_comment))
           .route("/mark_as_read", web::post().to(mark_reply_as_read))
           .route("/distinguish", web::post().to(distinguish_comment))
-          .route("/like", web::post().to(like_comment))
           .route("/like/list", web::get().to(list_comment_likes))
           .route("/save", web::put().to(save_comment))
           .route("/list", web::get().to(list_comments))
-          .route("/report", web::post().to(create_comment_report))
+          .route("/tree", web::get().to(list_comment_tree))
           .route("/report/resolve", web::put().to(resolve_comment_report))
           .route("/report/list", web::get().to(list_comment_reports)),
       )
       // Private Message
+      .service(
+        web::resource("/private_message/report")
+          .guard(guard::Post())
+          .wrap(rate_limit.report())
+          .route(web::post().to(create_pm_report)),
+      )
       .service(
         web::scope("/private_message")
           .wrap(rate_limit.message())
@@ -256,7 +330,6 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .route("", web::put().to(update_private_message))
           .route("/delete", web::post().to(delete_private_message))
           .route("/mark_as_read", web::post().to(mark_pm_as_read))
-          .route("/report", web::post().to(create_pm_report))
           .route("/report/resolve", web::put().to(resolve_pm_report))
           .route("/report/list", web::get().to(list_pm_reports)),
       )
@@ -318,6 +391,20 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
             web::post().to(mark_person_mention_as_read),
           )
           .route("/replies", web::get().to(list_replies))
+          .route("/events", web::get().to(get_event_stream))
+          .route("/mod_actions", web::get().to(list_mod_action_notifications))
+          .route(
+            "/mod_actions/mark_as_read",
+            web::post().to(mark_mod_action_notification_as_read),
+          )
+          .route("/saved_search", web::post().to(create_saved_search))
+          .route("/saved_search/list", web::get().to(list_saved_searches))
+          .route("/saved_search/delete", web::post().to(delete_saved_search))
+          .route("/saved_search/matches", web::get().to(list_saved_search_matches))
+          .route(
+            "/saved_search/matches/mark_as_read",
+            web::post().to(mark_saved_search_match_as_read),
+          )
           // Admin action. I don't like that it's in /user
           .route("/ban", web::post().to(ban_from_site))
           .route("/banned", web::get().to(list_banned_users))
@@ -339,10 +426,33 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .route("/report_count", web::get().to(report_count))
           .route("/unread_count", web::get().to(unread_count))
           .route("/verify_email", web::post().to(verify_email))
+          .route("/unsubscribe", web::get().to(unsubscribe_page))
+          .route("/unsubscribe", web::post().to(unsubscribe))
           .route("/leave_admin", web::post().to(leave_admin))
           .route("/totp/generate", web::post().to(generate_totp_secret))
           .route("/totp/update", web::post().to(update_totp))
+          .route(
+            "/totp/recovery_codes",
+            web::post().to(regenerate_totp_recovery_codes),
+          )
           .route("/list_logins", web::get().to(list_logins))
+          .route("/revoke_login", web::post().to(revoke_login))
+          .route("/revoke_other_logins", web::post().to(revoke_other_logins))
+          .route("/api_token", web::post().to(create_api_token))
+          .route("/api_token/list", web::get().to(list_api_tokens))
+          .route("/api_token/revoke", web::post().to(revoke_api_token))
+          .route(
+            "/push_subscription",
+            web::post().to(register_push_subscription),
+          )
+          .route(
+            "/push_subscription/delete",
+            web::post().to(delete_push_subscription),
+          )
+          .route(
+            "/push_subscription/vapid_public_key",
+            web::get().to(get_vapid_public_key),
+          )
           .route("/validate_auth", web::get().to(validate_auth)),
       )
       // Admin Actions
@@ -350,6 +460,7 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
         web::scope("/admin")
           .wrap(rate_limit.message())
           .route("/add", web::post().to(add_admin))
+          .route("/trust_bot", web::post().to(trust_bot))
           .route(
             "/registration_application/count",
             web::get().to(get_unread_registration_application_count),
@@ -367,6 +478,16 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
             web::get().to(get_registration_application),
           )
           .route("/list_all_media", web::get().to(list_all_media))
+          .route("/failed_logins", web::get().to(list_failed_logins))
+          .route("/failed_emails", web::get().to(list_failed_emails))
+          .service(
+            web::scope("/email_template")
+              .route("", web::post().to(create_email_template))
+              .route("", web::put().to(update_email_template))
+              .route("/delete", web::post().to(delete_email_template))
+              .route("/list", web::get().to(list_email_templates))
+              .route("/preview", web::post().to(preview_email_template)),
+          )
           .service(
             web::scope("/purge")
               .route("/person", web::post().to(purge_person))
@@ -381,6 +502,15 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .route("", web::post().to(create_custom_emoji))
           .route("", web::put().to(update_custom_emoji))
           .route("/delete", web::post().to(delete_custom_emoji)),
+      )
+      .service(
+        web::scope("/webhook")
+          .wrap(rate_limit.message())
+          .route("", web::post().to(create_webhook))
+          .route("", web::put().to(update_webhook))
+          .route("/delete", web::post().to(delete_webhook))
+          .route("/list", web::get().to(list_webhooks))
+          .route("/deliveries", web::get().to(list_webhook_deliveries)),
       ),
   );
   cfg.service(
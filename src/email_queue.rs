@@ -0,0 +1,93 @@
+use chrono::{Duration, Utc};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::source::email_queue::EmailQueue;
+use lemmy_utils::{email::send_email_with_unsubscribe, error::LemmyResult};
+use std::time::Duration as StdDuration;
+use tracing::{error, info, warn};
+
+/// How often the queue is checked for new emails
+const POLL_INTERVAL: StdDuration = StdDuration::from_secs(5);
+/// Number of emails which are taken from the queue at once
+const BATCH_SIZE: i64 = 20;
+/// Delivery is given up after this many failed attempts, about two hours after the first one
+const MAX_ATTEMPTS: i32 = 8;
+
+/// Sends the emails from the queue in the background. Failed deliveries are retried with
+/// exponential backoff, starting at one minute.
+pub async fn deliver_emails(context: LemmyContext) -> LemmyResult<()> {
+  info!("Starting email delivery");
+  loop {
+    match EmailQueue::claim_due(&mut context.pool(), BATCH_SIZE).await {
+      Ok(emails) => {
+        for email in &emails {
+          deliver_email(email, &context).await;
+        }
+        // Continue immediately if there might be more emails waiting
+        if emails.len() as i64 == BATCH_SIZE {
+          continue;
+        }
+      }
+      Err(e) => error!("Failed to read email queue: {e}"),
+    }
+    tokio::time::sleep(POLL_INTERVAL).await;
+  }
+}
+
+async fn deliver_email(email: &EmailQueue, context: &LemmyContext) {
+  let result = send_email_with_unsubscribe(
+    &email.subject,
+    &email.to_email,
+    &email.to_username,
+    &email.body,
+    email.unsubscribe_link.as_deref(),
+    context.settings(),
+  )
+  .await;
+  let pool = &mut context.pool();
+  let result = match result {
+    Ok(()) => EmailQueue::delete(pool, email.id).await.map(|_| ()),
+    Err(e) => {
+      let retry_at = next_attempt_delay(email.attempts).map(|delay| Utc::now() + delay);
+      if retry_at.is_none() {
+        warn!(
+          "Giving up on email {} to {} after {} attempts: {e}",
+          email.id, email.to_username, email.attempts
+        );
+      }
+      EmailQueue::record_error(pool, email.id, &e.to_string(), retry_at)
+        .await
+        .map(|_| ())
+    }
+  };
+  if let Err(e) = result {
+    error!("Failed to update queued email {}: {e}", email.id);
+  }
+}
+
+/// Time until the next delivery attempt, or `None` if there are no more attempts. Also used for
+/// webhooks.
+pub(crate) fn next_attempt_delay(attempts: i32) -> Option<Duration> {
+  if attempts >= MAX_ATTEMPTS {
+    return None;
+  }
+  let exponent = u32::try_from(attempts - 1).unwrap_or(0);
+  Some(Duration::minutes(2_i64.pow(exponent)))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::indexing_slicing)]
+mod tests {
+
+  use super::next_attempt_delay;
+  use chrono::Duration;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_next_attempt_delay() {
+    assert_eq!(Some(Duration::minutes(1)), next_attempt_delay(1));
+    assert_eq!(Some(Duration::minutes(2)), next_attempt_delay(2));
+    assert_eq!(Some(Duration::minutes(64)), next_attempt_delay(7));
+    assert_eq!(None, next_attempt_delay(8));
+  }
+}
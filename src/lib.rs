@@ -1,11 +1,13 @@
 pub mod api_routes_http;
 pub mod code_migrations;
+pub mod email_queue;
 pub mod prometheus_metrics;
 pub mod root_span_builder;
 pub mod scheduled_tasks;
 pub mod session_middleware;
 #[cfg(feature = "console")]
 pub mod telemetry;
+pub mod webhook_delivery;
 
 use crate::{
   code_migrations::run_advanced_migrations,
@@ -27,10 +29,12 @@ use clap::Parser;
 use lemmy_api_common::{
   context::LemmyContext,
   lemmy_db_views::structs::SiteView,
+  push::init_vapid_keys,
   request::client_builder,
   send_activity::{ActivityChannel, MATCH_OUTGOING_ACTIVITIES},
   utils::{
     check_private_instance_and_federation_enabled,
+    local_site_rate_limit_to_account_limit_config,
     local_site_rate_limit_to_rate_limit_config,
   },
 };
@@ -40,14 +44,21 @@ use lemmy_apub::{
   VerifyUrlData,
   FEDERATION_HTTP_FETCH_LIMIT,
 };
-use lemmy_db_schema::{source::secret::Secret, utils::build_db_pool};
+use lemmy_db_schema::{
+  impls::rate_limit_bucket::PostgresRateLimitBackend,
+  source::secret::Secret,
+  utils::build_db_pool,
+};
 use lemmy_federate::{Opts, SendManager};
 use lemmy_routes::{feeds, images, nodeinfo, webfinger};
 use lemmy_utils::{
   error::LemmyResult,
   rate_limit::RateLimitCell,
   response::jsonify_plain_text_errors,
-  settings::{structs::Settings, SETTINGS},
+  settings::{
+    structs::{RateLimitBackend, Settings},
+    SETTINGS,
+  },
   VERSION,
 };
 use mimalloc::MiMalloc;
@@ -56,7 +67,7 @@ use prometheus_metrics::serve_prometheus;
 use reqwest_middleware::ClientBuilder;
 use reqwest_tracing::TracingMiddleware;
 use serde_json::json;
-use std::{env, ops::Deref, time::Duration};
+use std::{env, ops::Deref, sync::Arc, time::Duration};
 use tokio::signal::unix::SignalKind;
 use tracing::subscriber::set_global_default;
 use tracing_actix_web::TracingLogger;
@@ -88,6 +99,7 @@ pub struct CmdArgs {
   ///
   /// If you are running multiple Lemmy server processes, you probably want to disable scheduled
   /// tasks on all but one of the processes, to avoid running the tasks more often than intended.
+  /// Queued emails and webhook events are also sent by the process which runs scheduled tasks.
   #[arg(long, default_value_t = false, env = "LEMMY_DISABLE_SCHEDULED_TASKS")]
   disable_scheduled_tasks: bool,
   /// Disables the HTTP server.
@@ -141,6 +153,7 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   let secret = Secret::init(&mut (&pool).into())
     .await?
     .expect("Couldn't initialize secrets.");
+  let secret = init_vapid_keys(secret, &mut (&pool).into()).await?;
 
   // Make sure the local site is set up.
   let site_view = SiteView::read_local(&mut (&pool).into())
@@ -158,7 +171,15 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   // Set up the rate limiter
   let rate_limit_config =
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
-  let rate_limit_cell = RateLimitCell::new(rate_limit_config);
+  let shared_rate_limit_backend = match SETTINGS.rate_limit_backend {
+    RateLimitBackend::Memory => None,
+    RateLimitBackend::Postgres => Some(Arc::new(PostgresRateLimitBackend::new(pool.clone())) as _),
+  };
+  let rate_limit_cell =
+    RateLimitCell::new_with_shared_backend(rate_limit_config, shared_rate_limit_backend);
+  rate_limit_cell.set_account_config(local_site_rate_limit_to_account_limit_config(
+    &site_view.local_site_rate_limit,
+  ));
 
   println!(
     "Starting HTTP server at {}:{}",
@@ -179,6 +200,14 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
     // Schedules various cleanup tasks for the DB
     tokio::task::spawn(scheduled_tasks::setup(context.clone()))
   });
+  if !args.disable_scheduled_tasks && SETTINGS.email.is_some() {
+    // Sends the queued emails
+    tokio::task::spawn(email_queue::deliver_emails(context.clone()));
+  }
+  if !args.disable_scheduled_tasks {
+    // Sends the queued webhook events
+    tokio::task::spawn(webhook_delivery::deliver_webhooks(context.clone()));
+  }
 
   if let Some(prometheus) = SETTINGS.prometheus.clone() {
     serve_prometheus(prometheus, context.clone())?;
@@ -10,7 +10,10 @@ use diesel::{
   QueryableByName,
 };
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
-use lemmy_api_common::context::LemmyContext;
+use lemmy_api_common::{
+  context::LemmyContext,
+  utils::{check_saved_search, send_email_digest},
+};
 use lemmy_db_schema::{
   schema::{
     captcha_answer,
@@ -20,16 +23,21 @@ use lemmy_db_schema::{
     person,
     post,
     received_activity,
+    search_vector_backfill,
     sent_activity,
   },
   source::{
+    email_queue::EmailQueue,
     instance::{Instance, InstanceForm},
     local_user::LocalUser,
+    saved_search::SavedSearch,
+    webhook::WebhookDelivery,
   },
   utils::{get_conn, naive_now, now, DbPool, DELETED_REPLACEMENT_TEXT},
 };
+use lemmy_db_views::structs::{LocalUserView, SiteView};
 use lemmy_routes::nodeinfo::{NodeInfo, NodeInfoWellKnown};
-use lemmy_utils::error::LemmyResult;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 use reqwest_middleware::ClientWithMiddleware;
 use std::time::Duration;
 use tracing::{error, info, warn};
@@ -48,6 +56,7 @@ pub async fn setup(context: LemmyContext) -> LemmyResult<()> {
     async move {
       active_counts(&mut context.pool()).await;
       update_banned_when_expired(&mut context.pool()).await;
+      send_email_digests(&context).await;
     }
   });
 
@@ -61,6 +70,16 @@ pub async fn setup(context: LemmyContext) -> LemmyResult<()> {
     }
   });
 
+  let context_1 = context.clone();
+  // Check new content against saved searches every 10 minutes
+  scheduler.every(CTimeUnits::minutes(10)).run(move || {
+    let context = context_1.clone();
+
+    async move {
+      check_saved_searches(&context).await;
+    }
+  });
+
   let context_1 = context.clone();
   // Delete any captcha answers older than ten minutes, every ten minutes
   scheduler.every(CTimeUnits::minutes(10)).run(move || {
@@ -92,6 +111,8 @@ pub async fn setup(context: LemmyContext) -> LemmyResult<()> {
     async move {
       overwrite_deleted_posts_and_comments(&mut context.pool()).await;
       delete_old_denied_users(&mut context.pool()).await;
+      delete_old_failed_emails(&mut context.pool()).await;
+      delete_old_webhook_deliveries(&mut context.pool()).await;
       update_instance_software(&mut context.pool(), context.client())
         .await
         .map_err(|e| warn!("Failed to update instance software: {e}"))
@@ -114,6 +135,7 @@ async fn startup_jobs(pool: &mut DbPool<'_>) {
   clear_old_activities(pool).await;
   overwrite_deleted_posts_and_comments(pool).await;
   delete_old_denied_users(pool).await;
+  backfill_search_vectors(pool).await;
 }
 
 /// Update the hot_rank columns for the aggregates tables
@@ -265,6 +287,142 @@ async fn process_post_aggregates_ranks_in_batches(conn: &mut AsyncPgConnection)
   );
 }
 
+/// Fill in the full text search vectors of rows which existed before the search tables were added.
+/// Runs in batches, and only until each table is done once.
+async fn backfill_search_vectors(pool: &mut DbPool<'_>) {
+  let conn = get_conn(pool).await;
+
+  match conn {
+    Ok(mut conn) => {
+      let pending = search_vector_backfill::table
+        .select((
+          search_vector_backfill::table_name,
+          search_vector_backfill::last_id,
+        ))
+        .load::<(String, i32)>(&mut conn)
+        .await;
+      let pending = match pending {
+        Ok(pending) => pending,
+        Err(e) => {
+          error!("Failed to read pending search vector backfills: {e}");
+          return;
+        }
+      };
+      for (table_name, last_id) in pending {
+        let (join_clause, vector) = match table_name.as_str() {
+          "post" => (
+            "LEFT JOIN language ON language.id = t.language_id",
+            "r.search_vector(r.text_search_config(language.code), t.name, t.body)",
+          ),
+          "comment" => (
+            "LEFT JOIN language ON language.id = t.language_id",
+            "r.search_vector(r.text_search_config(language.code), t.content, NULL)",
+          ),
+          "community" => (
+            "",
+            "r.search_vector('simple', t.name || ' ' || t.title, t.description)",
+          ),
+          "person" => (
+            "",
+            "r.search_vector('simple', t.name || ' ' || coalesce(t.display_name, ''), t.bio)",
+          ),
+          _ => continue,
+        };
+        let done =
+          process_search_vectors_in_batches(&mut conn, &table_name, last_id, join_clause, vector)
+            .await;
+        if done {
+          diesel::delete(search_vector_backfill::table.find(&table_name))
+            .execute(&mut conn)
+            .await
+            .inspect_err(|e| error!("Failed to finish {table_name} search vector backfill: {e}"))
+            .ok();
+        }
+      }
+    }
+    Err(e) => {
+      error!("Failed to get connection from pool: {e}");
+    }
+  }
+}
+
+#[derive(QueryableByName)]
+struct SearchVectorBackfillResult {
+  #[diesel(sql_type = Integer)]
+  id: i32,
+}
+
+/// Inserts the missing search vectors of `table_name` up to `last_id` in batches, ordered by id.
+/// In `join_clause` and `vector`, "t" will refer to the current table. Returns true if all rows
+/// were processed.
+async fn process_search_vectors_in_batches(
+  conn: &mut AsyncPgConnection,
+  table_name: &str,
+  last_id: i32,
+  join_clause: &str,
+  vector: &str,
+) -> bool {
+  let batch_size = 1000;
+  let mut processed_rows_count = 0;
+  let mut previous_batch_last_id = 0;
+  let done = loop {
+    let result = sql_query(format!(
+      r#"SELECT t.id
+           FROM {table_name} t
+           WHERE t.id > $1 AND t.id <= $2
+           ORDER BY t.id
+           LIMIT $3
+    "#
+    ))
+    .bind::<Integer, _>(previous_batch_last_id)
+    .bind::<Integer, _>(last_id)
+    .bind::<Integer, _>(batch_size)
+    .get_results::<SearchVectorBackfillResult>(conn)
+    .await;
+    let batch_last_id = match result {
+      Ok(rows) => match rows.iter().map(|row| row.id).max() {
+        Some(id) => id,
+        None => break true,
+      },
+      Err(e) => {
+        error!("Failed to backfill {} search vectors: {}", table_name, e);
+        break false;
+      }
+    };
+
+    let result = sql_query(format!(
+      r#"INSERT INTO {table_name}_search ({table_name}_id, search_vector)
+         SELECT t.id, {vector}
+           FROM {table_name} t {join_clause}
+           WHERE t.id > $1 AND t.id <= $2
+         ON CONFLICT DO NOTHING
+    "#
+    ))
+    .bind::<Integer, _>(previous_batch_last_id)
+    .bind::<Integer, _>(batch_last_id)
+    .execute(conn)
+    .await;
+
+    match result {
+      Ok(inserted_rows) => {
+        processed_rows_count += inserted_rows;
+        previous_batch_last_id = batch_last_id;
+      }
+      Err(e) => {
+        error!("Failed to backfill {} search vectors: {}", table_name, e);
+        break false;
+      }
+    }
+  };
+  if processed_rows_count > 0 {
+    info!(
+      "Backfilled {} search vectors (processed {} rows)",
+      table_name, processed_rows_count
+    );
+  }
+  done
+}
+
 async fn delete_expired_captcha_answers(pool: &mut DbPool<'_>) {
   let conn = get_conn(pool).await;
 
@@ -329,6 +487,28 @@ async fn delete_old_denied_users(pool: &mut DbPool<'_>) {
     .ok();
 }
 
+async fn delete_old_failed_emails(pool: &mut DbPool<'_>) {
+  info!("Deleting old failed emails...");
+  EmailQueue::delete_old_failed(pool)
+    .await
+    .map(|_| {
+      info!("Done.");
+    })
+    .map_err(|e| error!("Failed to delete old failed emails: {e}"))
+    .ok();
+}
+
+async fn delete_old_webhook_deliveries(pool: &mut DbPool<'_>) {
+  info!("Deleting old webhook deliveries...");
+  WebhookDelivery::delete_old(pool)
+    .await
+    .map(|_| {
+      info!("Done.");
+    })
+    .map_err(|e| error!("Failed to delete old webhook deliveries: {e}"))
+    .ok();
+}
+
 /// overwrite posts and comments 30d after deletion
 async fn overwrite_deleted_posts_and_comments(pool: &mut DbPool<'_>) {
   info!("Overwriting deleted posts...");
@@ -418,6 +598,48 @@ async fn active_counts(pool: &mut DbPool<'_>) {
   }
 }
 
+/// Send notification digests to users who don't want an email for each notification
+async fn send_email_digests(context: &LemmyContext) {
+  info!("Sending email digests...");
+  match LocalUserView::list_due_email_digests(&mut context.pool()).await {
+    Ok(users) => {
+      for user in &users {
+        if let Err(e) = send_email_digest(user, context).await {
+          warn!("Failed to send email digest to {}: {e}", user.person.name);
+        }
+      }
+      info!("Done sending {} email digests.", users.len());
+    }
+    Err(e) => {
+      error!("Failed to list users with due email digests: {e}");
+    }
+  }
+}
+
+/// Check the new posts and comments against all saved searches. The latest ids are read once, so
+/// that all searches check the same content.
+async fn check_saved_searches(context: &LemmyContext) {
+  info!("Checking saved searches...");
+  let result = async {
+    let site = SiteView::read_local(&mut context.pool())
+      .await?
+      .ok_or(LemmyErrorType::LocalSiteNotSetup)?
+      .site;
+    let latest_ids = SavedSearch::latest_content_ids(&mut context.pool()).await?;
+    let saved_searches = SavedSearch::list_all(&mut context.pool()).await?;
+    for saved_search in &saved_searches {
+      if let Err(e) = check_saved_search(saved_search, latest_ids, &site, context).await {
+        warn!("Failed to check saved search {:?}: {e}", saved_search.id);
+      }
+    }
+    LemmyResult::Ok(saved_searches.len())
+  };
+  match result.await {
+    Ok(count) => info!("Done checking {count} saved searches."),
+    Err(e) => error!("Failed to check saved searches: {e}"),
+  }
+}
+
 /// Set banned to false after ban expires
 async fn update_banned_when_expired(pool: &mut DbPool<'_>) {
   info!("Updating banned column if it expires ...");
@@ -1,14 +1,20 @@
 use actix_web::{
   body::MessageBody,
   dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-  http::header::CACHE_CONTROL,
+  http::{header::CACHE_CONTROL, Method},
   Error,
   HttpMessage,
 };
 use core::future::Ready;
 use futures_util::future::LocalBoxFuture;
-use lemmy_api::{local_user_view_from_jwt, read_auth_token};
+use lemmy_api::{local_user_view_from_api_token, local_user_view_from_jwt, read_auth_token};
 use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::{source::api_token::ApiToken, ApiTokenScope};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  rate_limit::RateLimitAccount,
+};
 use reqwest::header::HeaderValue;
 use std::{future::ready, rc::Rc};
 
@@ -66,14 +72,20 @@ where
     Box::pin(async move {
       let jwt = read_auth_token(req.request())?;
 
-      if let Some(jwt) = &jwt {
+      if let Some(token) = jwt.as_deref().filter(|t| ApiToken::is_api_token(t)) {
+        // Api tokens are used by bots and integrations, so unlike login sessions any error is
+        // returned to the client.
+        let (local_user_view, api_token) = local_user_view_from_api_token(token, &context).await?;
+        check_api_token_scope(&api_token, req.method(), req.path())?;
+        insert_local_user_view(&req, local_user_view);
+      } else if let Some(jwt) = &jwt {
         // Ignore any invalid auth so the site can still be used
         // TODO: this means it will be impossible to get any error message for invalid jwt. Need
         //       to add a separate endpoint for that.
         //       https://github.com/LemmyNet/lemmy/issues/3702
         let local_user_view = local_user_view_from_jwt(jwt, &context).await.ok();
         if let Some(local_user_view) = local_user_view {
-          req.extensions_mut().insert(local_user_view);
+          insert_local_user_view(&req, local_user_view);
         }
       }
 
@@ -97,6 +109,161 @@ where
   }
 }
 
+/// Make the user available to api handlers, and to the rate limiter which runs afterwards so that
+/// it can also limit the account. Admins and trusted bots get separate, higher limits.
+fn insert_local_user_view(req: &ServiceRequest, local_user_view: LocalUserView) {
+  let account = RateLimitAccount {
+    local_user_id: local_user_view.local_user.id.0,
+    trusted: local_user_view.local_user.admin || local_user_view.local_user.trusted_bot,
+  };
+  let mut extensions = req.extensions_mut();
+  extensions.insert(account);
+  extensions.insert(local_user_view);
+}
+
+/// Ensure that the api token has the scope which is required for the requested route.
+fn check_api_token_scope(api_token: &ApiToken, method: &Method, path: &str) -> LemmyResult<()> {
+  let scope = required_api_token_scope(method, path).ok_or(LemmyErrorType::ApiTokenNotAllowed)?;
+  if api_token.scopes.contains(&scope) {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::ApiTokenMissingScope(scope.to_string()))?
+  }
+}
+
+/// Returns the scope which an api token needs for the given route, or `None` if the route can't
+/// be used with an api token. Routes which aren't listed here are rejected, so that new routes
+/// need to be classified before they are available to tokens. The account routes, which manage
+/// the account and its credentials, are only available with a regular login so that a leaked
+/// token can't be used to take over the account.
+fn required_api_token_scope(method: &Method, path: &str) -> Option<ApiTokenScope> {
+  const ACCOUNT_ROUTES: [&str; 13] = [
+    "/user/api_token",
+    "/user/change_password",
+    "/user/password_change",
+    "/user/delete_account",
+    "/user/totp",
+    "/user/logout",
+    "/user/list_logins",
+    "/user/revoke_login",
+    "/user/revoke_other_logins",
+    "/user/save_user_settings",
+    "/user/export_settings",
+    "/user/import_settings",
+    "/user/push_subscription",
+  ];
+  const ADMIN_ROUTES: [&str; 6] = [
+    "/admin",
+    "/custom_emoji",
+    "/user/ban",
+    "/user/leave_admin",
+    "/community/remove",
+    "/community/hide",
+  ];
+  const MOD_ROUTES: [&str; 15] = [
+    "/post/remove",
+    "/post/lock",
+    "/post/feature",
+    "/post/like/list",
+    "/post/report/",
+    "/comment/remove",
+    "/comment/distinguish",
+    "/comment/like/list",
+    "/comment/report/",
+    "/private_message/report/",
+    "/community/ban_user",
+    "/community/mod",
+    "/community/transfer",
+    "/user/report_count",
+    "/webhook",
+  ];
+  const VOTE_ROUTES: [&str; 2] = ["/post/like", "/comment/like"];
+  const READ_ROUTES: [&str; 26] = [
+    "/image_proxy",
+    "/site",
+    "/modlog",
+    "/search",
+    "/autocomplete",
+    "/resolve_object",
+    "/community",
+    "/community/list",
+    "/federated_instances",
+    "/post",
+    "/post/list",
+    "/post/site_metadata",
+    "/comment",
+    "/comment/list",
+    "/comment/tree",
+    "/private_message/list",
+    "/account/list_media",
+    "/user",
+    "/user/mention",
+    "/user/replies",
+    "/user/events",
+    "/user/mod_actions",
+    "/user/saved_search/list",
+    "/user/saved_search/matches",
+    "/user/unread_count",
+    "/user/validate_auth",
+  ];
+  const POST_ROUTES: [&str; 27] = [
+    "/site/block",
+    "/community",
+    "/community/follow",
+    "/community/block",
+    "/community/delete",
+    "/post",
+    "/post/delete",
+    "/post/mark_as_read",
+    "/post/hide",
+    "/post/save",
+    "/post/report",
+    "/comment",
+    "/comment/delete",
+    "/comment/mark_as_read",
+    "/comment/save",
+    "/comment/report",
+    "/private_message",
+    "/private_message/delete",
+    "/private_message/mark_as_read",
+    "/private_message/report",
+    "/user/block",
+    "/user/mention/mark_as_read",
+    "/user/mod_actions/mark_as_read",
+    "/user/mark_all_as_read",
+    "/user/saved_search",
+    "/user/saved_search/delete",
+    "/user/saved_search/matches/mark_as_read",
+  ];
+
+  let Some(path) = path.strip_prefix("/api/v3") else {
+    // Image uploads and deletions, everything else outside of the api is public
+    return if path == "/pictrs/image" || path.starts_with("/pictrs/image/delete/") {
+      Some(ApiTokenScope::Post)
+    } else if method == Method::GET {
+      Some(ApiTokenScope::Read)
+    } else {
+      None
+    };
+  };
+  let matches = |routes: &[&str]| routes.iter().any(|r| path.starts_with(r));
+  if matches(&ACCOUNT_ROUTES) {
+    None
+  } else if matches(&ADMIN_ROUTES) || (path == "/site" && method != Method::GET) {
+    Some(ApiTokenScope::Admin)
+  } else if matches(&MOD_ROUTES) {
+    Some(ApiTokenScope::Moderate)
+  } else if VOTE_ROUTES.contains(&path) {
+    Some(ApiTokenScope::Vote)
+  } else if method == Method::GET && READ_ROUTES.contains(&path) {
+    Some(ApiTokenScope::Read)
+  } else if method != Method::GET && POST_ROUTES.contains(&path) {
+    Some(ApiTokenScope::Post)
+  } else {
+    None
+  }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 #[allow(clippy::indexing_slicing)]
@@ -163,4 +330,64 @@ mod tests {
     let num_deleted = Person::delete(pool, inserted_person.id).await.unwrap();
     assert_eq!(1, num_deleted);
   }
+
+  #[test]
+  fn test_required_api_token_scope() {
+    let scope = |method, path| required_api_token_scope(&method, path);
+    assert_eq!(
+      Some(ApiTokenScope::Read),
+      scope(Method::GET, "/api/v3/post/list")
+    );
+    assert_eq!(
+      Some(ApiTokenScope::Post),
+      scope(Method::POST, "/api/v3/post")
+    );
+    assert_eq!(
+      Some(ApiTokenScope::Post),
+      scope(Method::POST, "/api/v3/post/report")
+    );
+    assert_eq!(
+      Some(ApiTokenScope::Vote),
+      scope(Method::POST, "/api/v3/comment/like")
+    );
+    assert_eq!(
+      Some(ApiTokenScope::Moderate),
+      scope(Method::GET, "/api/v3/comment/like/list")
+    );
+    assert_eq!(
+      Some(ApiTokenScope::Moderate),
+      scope(Method::PUT, "/api/v3/post/report/resolve")
+    );
+    assert_eq!(
+      Some(ApiTokenScope::Read),
+      scope(Method::GET, "/api/v3/site")
+    );
+    assert_eq!(
+      Some(ApiTokenScope::Admin),
+      scope(Method::PUT, "/api/v3/site")
+    );
+    assert_eq!(
+      Some(ApiTokenScope::Admin),
+      scope(Method::GET, "/api/v3/admin/registration_application/list")
+    );
+    assert_eq!(None, scope(Method::POST, "/api/v3/user/api_token"));
+    assert_eq!(None, scope(Method::PUT, "/api/v3/user/change_password"));
+    assert_eq!(
+      Some(ApiTokenScope::Admin),
+      scope(Method::POST, "/api/v3/user/leave_admin")
+    );
+    assert_eq!(None, scope(Method::POST, "/api/v3/user/push_subscription"));
+    assert_eq!(
+      Some(ApiTokenScope::Moderate),
+      scope(Method::GET, "/api/v3/webhook/list")
+    );
+    // Routes which aren't classified can't be used with api tokens
+    assert_eq!(None, scope(Method::GET, "/api/v3/unknown"));
+    assert_eq!(None, scope(Method::POST, "/api/v3/post/list"));
+    assert_eq!(
+      Some(ApiTokenScope::Post),
+      scope(Method::POST, "/pictrs/image")
+    );
+    assert_eq!(Some(ApiTokenScope::Read), scope(Method::GET, "/feeds/all.xml"));
+  }
 }
@@ -0,0 +1,131 @@
+use crate::email_queue::next_attempt_delay;
+use chrono::Utc;
+use lemmy_api_common::{
+  context::LemmyContext,
+  request::check_url_is_global,
+  send_webhook::{
+    sign_webhook_payload,
+    WEBHOOK_DELIVERY_HEADER,
+    WEBHOOK_EVENT_HEADER,
+    WEBHOOK_SIGNATURE_HEADER,
+  },
+};
+use lemmy_db_schema::{
+  source::webhook::{Webhook, WebhookDelivery},
+  traits::Crud,
+};
+use lemmy_utils::error::LemmyResult;
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use url::Url;
+
+/// How often the queue is checked for new deliveries
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Number of deliveries which are taken from the queue at once
+const BATCH_SIZE: i64 = 20;
+
+/// Sends the queued webhook events in the background. Failed deliveries are retried with
+/// exponential backoff, the same way as emails.
+pub async fn deliver_webhooks(context: LemmyContext) -> LemmyResult<()> {
+  info!("Starting webhook delivery");
+  loop {
+    match WebhookDelivery::claim_due(&mut context.pool(), BATCH_SIZE).await {
+      Ok(deliveries) => {
+        for delivery in &deliveries {
+          deliver_webhook(delivery, &context).await;
+        }
+        // Continue immediately if there might be more deliveries waiting
+        if deliveries.len() as i64 == BATCH_SIZE {
+          continue;
+        }
+      }
+      Err(e) => error!("Failed to read webhook queue: {e}"),
+    }
+    tokio::time::sleep(POLL_INTERVAL).await;
+  }
+}
+
+/// The outcome of a delivery attempt.
+enum Attempt {
+  Delivered(u16),
+  Failed { status: Option<u16>, error: String },
+  /// Retrying won't help, for example because the webhook was disabled.
+  GiveUp(String),
+}
+
+async fn deliver_webhook(delivery: &WebhookDelivery, context: &LemmyContext) {
+  let attempt = send_request(delivery, context).await;
+  let pool = &mut context.pool();
+  let result = match attempt {
+    Attempt::Delivered(status) => {
+      WebhookDelivery::mark_delivered(pool, delivery.id, status.into()).await
+    }
+    Attempt::Failed { status, error } => {
+      let retry_at = next_attempt_delay(delivery.attempts).map(|delay| Utc::now() + delay);
+      if retry_at.is_none() {
+        warn!(
+          "Giving up on webhook delivery {} after {} attempts: {error}",
+          delivery.id, delivery.attempts
+        );
+      }
+      let status = status.map(i32::from);
+      WebhookDelivery::record_error(pool, delivery.id, status, &error, retry_at).await
+    }
+    Attempt::GiveUp(error) => {
+      WebhookDelivery::record_error(pool, delivery.id, None, &error, None).await
+    }
+  };
+  if let Err(e) = result {
+    error!("Failed to update webhook delivery {}: {e}", delivery.id);
+  }
+}
+
+async fn send_request(delivery: &WebhookDelivery, context: &LemmyContext) -> Attempt {
+  let webhook = match Webhook::read(&mut context.pool(), delivery.webhook_id).await {
+    Ok(Some(webhook)) => webhook,
+    Ok(None) => return Attempt::GiveUp("Webhook was deleted".to_string()),
+    Err(e) => {
+      return Attempt::Failed {
+        status: None,
+        error: e.to_string(),
+      }
+    }
+  };
+  if !webhook.enabled {
+    return Attempt::GiveUp("Webhook is disabled".to_string());
+  }
+  // The domain may point somewhere else than when the webhook was created
+  let url_is_global = match Url::parse(&webhook.url) {
+    Ok(url) => check_url_is_global(&url).await.is_ok(),
+    Err(_) => false,
+  };
+  if !url_is_global {
+    return Attempt::GiveUp("Url points to an internal address".to_string());
+  }
+
+  let signature = sign_webhook_payload(&webhook.secret, &delivery.payload);
+  let response = context
+    .client()
+    .post(&webhook.url)
+    .header(CONTENT_TYPE, "application/json")
+    .header(WEBHOOK_EVENT_HEADER, delivery.event.to_string())
+    .header(WEBHOOK_DELIVERY_HEADER, delivery.id.to_string())
+    .header(WEBHOOK_SIGNATURE_HEADER, signature)
+    .body(delivery.payload.clone())
+    .send()
+    .await;
+  match response {
+    Ok(response) if response.status().is_success() => {
+      Attempt::Delivered(response.status().as_u16())
+    }
+    Ok(response) => Attempt::Failed {
+      status: Some(response.status().as_u16()),
+      error: format!("Received status {}", response.status()),
+    },
+    Err(e) => Attempt::Failed {
+      status: None,
+      error: e.to_string(),
+    },
+  }
+}